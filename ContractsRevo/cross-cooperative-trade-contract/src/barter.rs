@@ -21,10 +21,30 @@ pub fn create_barter_agreement(
         &DataKey::BarterAgreement(agreement_id.clone()),
         &barter_agreement,
     );
+    env.storage().persistent().set(
+        &DataKey::OfferAgreement(barter_agreement.trade_offer_id),
+        &agreement_id,
+    );
 
     agreement_id
 }
 
+/// Update the status of the agreement created for a trade offer, if any
+pub fn set_offer_agreement_status(env: &Env, offer_id: &BytesN<32>, status: &str) {
+    let agreement_id: Option<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::OfferAgreement(offer_id.clone()));
+
+    if let Some(agreement_id) = agreement_id {
+        let key = DataKey::BarterAgreement(agreement_id);
+        if let Some(mut agreement) = env.storage().persistent().get::<_, BarterAgreement>(&key) {
+            agreement.status = String::from_str(env, status);
+            env.storage().persistent().set(&key, &agreement);
+        }
+    }
+}
+
 pub fn get_barter_agreement(
     env: Env,
     agreement_id: BytesN<32>,
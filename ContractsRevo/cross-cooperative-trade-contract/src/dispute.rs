@@ -0,0 +1,308 @@
+use crate::{
+    escrow, AdminError, BarterAgreement, DataKey, Dispute, DisputeRuling, TradeAsset, TradeError,
+    TradeOffer,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), AdminError> {
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(AdminError::NotInitialized)?;
+
+    if stored_admin != *admin {
+        return Err(AdminError::UnauthorizedAccess);
+    }
+
+    admin.require_auth();
+    Ok(())
+}
+
+pub fn register_arbiter(env: Env, admin: Address, arbiter: Address) -> Result<(), AdminError> {
+    require_admin(&env, &admin)?;
+
+    let mut panel = get_arbiters(env.clone());
+    if !panel.contains(&arbiter) {
+        panel.push_back(arbiter);
+        env.storage().instance().set(&DataKey::ArbiterPanel, &panel);
+    }
+
+    Ok(())
+}
+
+pub fn remove_arbiter(env: Env, admin: Address, arbiter: Address) -> Result<(), AdminError> {
+    require_admin(&env, &admin)?;
+
+    let panel = get_arbiters(env.clone());
+    let mut new_panel = Vec::new(&env);
+    for member in panel.iter() {
+        if member != arbiter {
+            new_panel.push_back(member);
+        }
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::ArbiterPanel, &new_panel);
+
+    Ok(())
+}
+
+pub fn get_arbiters(env: Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ArbiterPanel)
+        .unwrap_or(Vec::new(&env))
+}
+
+/// Pick the next panel arbiter in rotation who is not a party to the agreement
+fn assign_arbiter(env: &Env, agreement: &BarterAgreement) -> Option<Address> {
+    let panel = get_arbiters(env.clone());
+    let counter: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::DisputeCounter)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::DisputeCounter, &(counter + 1));
+
+    for offset in 0..panel.len() {
+        let candidate = panel.get((counter + offset) % panel.len()).unwrap();
+        if candidate != agreement.offering_cooperative
+            && candidate != agreement.accepting_cooperative
+        {
+            return Some(candidate);
+        }
+    }
+
+    None
+}
+
+pub fn raise_dispute(
+    env: Env,
+    agreement_id: BytesN<32>,
+    caller: Address,
+    evidence_hash: BytesN<32>,
+) -> Result<Dispute, TradeError> {
+    caller.require_auth();
+
+    let mut agreement = crate::barter::get_barter_agreement(env.clone(), agreement_id.clone())?;
+
+    if caller != agreement.offering_cooperative && caller != agreement.accepting_cooperative {
+        return Err(TradeError::UnauthorizedAccess);
+    }
+
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Dispute(agreement_id.clone()))
+    {
+        return Err(TradeError::DisputeAlreadyExists);
+    }
+
+    // Only agreements whose trade has not settled can be disputed
+    let mut trade_offer = get_trade_offer(&env, &agreement.trade_offer_id)?;
+    if agreement.status != String::from_str(&env, "Active")
+        || trade_offer.status != String::from_str(&env, "Accepted")
+    {
+        return Err(TradeError::InvalidTradeStatus);
+    }
+
+    let mut evidence = Vec::new(&env);
+    evidence.push_back(evidence_hash);
+
+    let dispute = Dispute {
+        agreement_id: agreement_id.clone(),
+        raised_by: caller.clone(),
+        arbiter: assign_arbiter(&env, &agreement),
+        evidence,
+        ruling: DisputeRuling::Pending,
+        at_fault: None,
+        raised_at: env.ledger().timestamp(),
+        resolved_at: None,
+    };
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Dispute(agreement_id.clone()), &dispute);
+
+    // Freeze the trade so it cannot be completed while under dispute
+    let disputed = String::from_str(&env, "Disputed");
+    trade_offer.status = disputed.clone();
+    env.storage().persistent().set(
+        &DataKey::TradeOffer(trade_offer.offer_id.clone()),
+        &trade_offer,
+    );
+
+    agreement.status = disputed;
+    env.storage()
+        .persistent()
+        .set(&DataKey::BarterAgreement(agreement_id), &agreement);
+
+    Ok(dispute)
+}
+
+pub fn submit_evidence(
+    env: Env,
+    agreement_id: BytesN<32>,
+    caller: Address,
+    evidence_hash: BytesN<32>,
+) -> Result<(), TradeError> {
+    caller.require_auth();
+
+    let agreement = crate::barter::get_barter_agreement(env.clone(), agreement_id.clone())?;
+    if caller != agreement.offering_cooperative && caller != agreement.accepting_cooperative {
+        return Err(TradeError::UnauthorizedAccess);
+    }
+
+    let mut dispute = get_dispute(env.clone(), agreement_id.clone())?;
+    if dispute.ruling != DisputeRuling::Pending {
+        return Err(TradeError::InvalidTradeStatus);
+    }
+
+    dispute.evidence.push_back(evidence_hash);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Dispute(agreement_id), &dispute);
+
+    Ok(())
+}
+
+pub fn resolve_dispute(
+    env: Env,
+    agreement_id: BytesN<32>,
+    arbiter: Address,
+    ruling: DisputeRuling,
+    at_fault: Option<Address>,
+) -> Result<(), TradeError> {
+    arbiter.require_auth();
+
+    let mut dispute = get_dispute(env.clone(), agreement_id.clone())?;
+    if dispute.ruling != DisputeRuling::Pending {
+        return Err(TradeError::InvalidTradeStatus);
+    }
+
+    // The assigned panel arbiter or the admin may rule
+    let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+    if dispute.arbiter != Some(arbiter.clone()) && admin != Some(arbiter) {
+        return Err(TradeError::UnauthorizedAccess);
+    }
+
+    let mut agreement = crate::barter::get_barter_agreement(env.clone(), agreement_id.clone())?;
+    if let Some(party) = &at_fault {
+        if *party != agreement.offering_cooperative && *party != agreement.accepting_cooperative {
+            return Err(TradeError::InvalidRuling);
+        }
+    }
+
+    let mut trade_offer = get_trade_offer(&env, &agreement.trade_offer_id)?;
+    let offering = trade_offer.cooperative_id.clone();
+    let accepting = agreement.accepting_cooperative.clone();
+
+    // Release escrow according to the ruling
+    let status = match ruling {
+        DisputeRuling::FullCompletion => {
+            settle(&env, &trade_offer, &offering, &accepting, 100);
+            "Completed"
+        }
+        DisputeRuling::PartialSettlement(percentage) => {
+            if percentage == 0 || percentage >= 100 {
+                return Err(TradeError::InvalidRuling);
+            }
+            settle(&env, &trade_offer, &offering, &accepting, percentage);
+            "Settled"
+        }
+        DisputeRuling::Reversal => {
+            settle(&env, &trade_offer, &offering, &accepting, 0);
+            "Reversed"
+        }
+        DisputeRuling::Pending => return Err(TradeError::InvalidRuling),
+    };
+
+    if let Some(party) = &at_fault {
+        crate::reputation::update_reputation_after_trade(&env, party, false)?;
+    }
+
+    trade_offer.status = String::from_str(&env, status);
+    env.storage().persistent().set(
+        &DataKey::TradeOffer(trade_offer.offer_id.clone()),
+        &trade_offer,
+    );
+
+    agreement.status = String::from_str(&env, status);
+    env.storage()
+        .persistent()
+        .set(&DataKey::BarterAgreement(agreement_id.clone()), &agreement);
+
+    dispute.ruling = ruling;
+    dispute.at_fault = at_fault;
+    dispute.resolved_at = Some(env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Dispute(agreement_id), &dispute);
+
+    Ok(())
+}
+
+pub fn get_dispute(env: Env, agreement_id: BytesN<32>) -> Result<Dispute, TradeError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Dispute(agreement_id))
+        .ok_or(TradeError::DisputeNotFound)
+}
+
+fn get_trade_offer(env: &Env, offer_id: &BytesN<32>) -> Result<TradeOffer, TradeError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TradeOffer(offer_id.clone()))
+        .ok_or(TradeError::TradeOfferNotFound)
+}
+
+/// Deliver `percentage` of each escrowed side to its counterparty and refund the rest
+fn settle(
+    env: &Env,
+    trade_offer: &TradeOffer,
+    offering: &Address,
+    accepting: &Address,
+    percentage: u32,
+) {
+    if let Some(offered_asset) = escrow::offered_asset(trade_offer) {
+        split_release(env, &offered_asset, accepting, offering, percentage);
+    }
+    if let Some(requested_asset) = escrow::requested_asset(trade_offer) {
+        split_release(env, &requested_asset, offering, accepting, percentage);
+    }
+}
+
+fn split_release(
+    env: &Env,
+    asset: &TradeAsset,
+    counterparty: &Address,
+    depositor: &Address,
+    percentage: u32,
+) {
+    let delivered = asset.amount * percentage as i128 / 100;
+    let refunded = asset.amount - delivered;
+
+    if delivered > 0 {
+        escrow::release_asset(
+            env,
+            counterparty,
+            &TradeAsset {
+                token: asset.token.clone(),
+                amount: delivered,
+            },
+        );
+    }
+    if refunded > 0 {
+        escrow::release_asset(
+            env,
+            depositor,
+            &TradeAsset {
+                token: asset.token.clone(),
+                amount: refunded,
+            },
+        );
+    }
+}
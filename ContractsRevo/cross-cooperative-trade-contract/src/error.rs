@@ -18,4 +18,17 @@ pub enum TradeError {
     CannotAcceptOwnOffer = 5,
     InvalidQuantity = 6,
     BarterAgreementNotFound = 7,
+    InvalidAmount = 8,
+    DisputeNotFound = 9,
+    DisputeAlreadyExists = 10,
+    InvalidRuling = 11,
+    OrderNotFound = 12,
+    InvalidPrice = 13,
+    OrderNotExpired = 14,
+    InvalidRating = 15,
+    AlreadyRated = 16,
+    CannotRateSelf = 17,
+    AmountOverflow = 18,
+    TradeNotExpired = 19,
+    TooManyPriceLevels = 20,
 }
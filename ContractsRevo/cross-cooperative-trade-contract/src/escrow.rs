@@ -0,0 +1,38 @@
+use crate::{TradeAsset, TradeError, TradeOffer};
+use soroban_sdk::{token, Address, Env};
+
+/// Ensure an asset carries a positive quantity
+pub fn validate_asset(asset: &TradeAsset) -> Result<(), TradeError> {
+    if asset.amount <= 0 {
+        return Err(TradeError::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Move an asset from a cooperative into contract custody
+pub fn lock_asset(env: &Env, from: &Address, asset: &TradeAsset) {
+    let token_client = token::Client::new(env, &asset.token);
+    token_client.transfer(from, &env.current_contract_address(), &asset.amount);
+}
+
+/// Release an escrowed asset from contract custody
+pub fn release_asset(env: &Env, to: &Address, asset: &TradeAsset) {
+    let token_client = token::Client::new(env, &asset.token);
+    token_client.transfer(&env.current_contract_address(), to, &asset.amount);
+}
+
+/// Asset the offering cooperative escrowed, if the offer is token-backed
+pub fn offered_asset(trade_offer: &TradeOffer) -> Option<TradeAsset> {
+    trade_offer.offered_token.clone().map(|token| TradeAsset {
+        token,
+        amount: trade_offer.offered_amount,
+    })
+}
+
+/// Asset the accepting cooperative must escrow, if the offer is token-backed
+pub fn requested_asset(trade_offer: &TradeOffer) -> Option<TradeAsset> {
+    trade_offer.requested_token.clone().map(|token| TradeAsset {
+        token,
+        amount: trade_offer.requested_amount,
+    })
+}
@@ -2,13 +2,19 @@
 use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, String, Vec};
 
 mod barter;
+mod dispute;
 mod error;
+mod escrow;
+mod orderbook;
 mod reputation;
 mod trade;
 mod utils;
 
 pub use barter::*;
+pub use dispute::*;
 pub use error::*;
+pub use escrow::*;
+pub use orderbook::*;
 pub use reputation::*;
 pub use trade::*;
 pub use utils::*;
@@ -18,6 +24,14 @@ mod test;
 #[cfg(test)]
 mod tests;
 
+// Quantity of a token-backed asset on one side of a trade
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeAsset {
+    pub token: Address, // SEP-41 token contract (e.g. a commodity-backed token)
+    pub amount: i128,
+}
+
 // Data structures for trade offers
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -26,7 +40,13 @@ pub struct TradeOffer {
     pub cooperative_id: Address,
     pub offered_product: BytesN<32>,
     pub requested_product: BytesN<32>,
-    pub status: String, // "Pending", "Accepted", "Completed"
+    pub offered_token: Option<Address>, // Escrowed from the offering cooperative
+    pub offered_amount: i128,
+    pub requested_token: Option<Address>, // Escrowed from the accepting cooperative
+    pub requested_amount: i128,
+    pub accepting_cooperative: Option<Address>,
+    pub accepted_at: u64, // Ledger time of acceptance, 0 while pending
+    pub status: String,   // "Pending", "Accepted", "Completed", "Cancelled", "Expired"
 }
 
 // Reputation tracking structure
@@ -35,7 +55,12 @@ pub struct TradeOffer {
 pub struct Reputation {
     pub cooperative_id: Address,
     pub successful_trades: u32,
-    pub rating: u32, // 1-5 scale
+    pub failed_trades: u32,    // Disputes ruled against the cooperative
+    pub rating: u32,           // 1-5 scale
+    pub weighted_score: u64,   // Sum of rating x decayed weight
+    pub total_weight: u64,     // Sum of decayed weights
+    pub ratings_received: u32, // Counterparty ratings received
+    pub last_updated: u64,     // Timestamp decay was last applied
 }
 
 // Barter agreement structure
@@ -46,7 +71,73 @@ pub struct BarterAgreement {
     pub trade_offer_id: BytesN<32>,
     pub offering_cooperative: Address,
     pub accepting_cooperative: Address,
-    pub status: String, // "Active", "Completed", "Disputed"
+    pub status: String, // "Active", "Completed", "Disputed", "Settled", "Reversed", "Expired"
+}
+
+// Arbiter ruling on a disputed barter agreement
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeRuling {
+    Pending,
+    FullCompletion,
+    PartialSettlement(u32), // Percentage of each escrowed side delivered to the counterparty
+    Reversal,
+}
+
+// Dispute raised over a barter agreement
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub agreement_id: BytesN<32>,
+    pub raised_by: Address,
+    pub arbiter: Option<Address>, // Assigned from the panel; admin may always rule
+    pub evidence: Vec<BytesN<32>>,
+    pub ruling: DisputeRuling,
+    pub at_fault: Option<Address>,
+    pub raised_at: u64,
+    pub resolved_at: Option<u64>,
+}
+
+// Resting order in a product-pair order book
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub order_id: BytesN<32>,
+    pub cooperative_id: Address,
+    pub offered_product: BytesN<32>,
+    pub requested_product: BytesN<32>,
+    pub offered_token: Address,
+    pub requested_token: Address,
+    pub price: i128, // Minimum requested units per offered unit, scaled by PRICE_SCALE
+    pub quantity: i128, // Offered units originally placed
+    pub remaining: i128, // Offered units still escrowed and unfilled
+    pub expires_at: u64, // 0 means the order never expires
+    pub status: String, // "Open", "PartiallyFilled", "Filled", "Cancelled", "Expired"
+}
+
+// Oldest and newest resting order at one price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LadderLevel {
+    pub head: BytesN<32>,
+    pub tail: BytesN<32>,
+}
+
+// A resting order's place in its price level queue
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LadderNode {
+    pub prev: Option<BytesN<32>>,
+    pub next: Option<BytesN<32>>,
+}
+
+// Paginated offer and order indexes
+#[contracttype]
+#[derive(Clone)]
+pub enum OfferIndex {
+    Active,
+    Product(BytesN<32>),
+    OrderBook(BytesN<32>, BytesN<32>), // (offered_product, requested_product)
 }
 
 // Data storage keys
@@ -56,10 +147,21 @@ pub enum DataKey {
     Admin,
     TradeOffer(BytesN<32>),
     BarterAgreement(BytesN<32>),
+    OfferAgreement(BytesN<32>), // Trade offer ID -> agreement ID
     Reputation(Address),
-    ActiveOffers,
     OfferCounter,
     AgreementCounter,
+    Dispute(BytesN<32>),
+    AgreementRating(BytesN<32>, Address),
+    ArbiterPanel,
+    DisputeCounter,
+    Order(BytesN<32>),
+    PriceLevels(BytesN<32>, BytesN<32>), // (offered_product, requested_product) -> ascending prices with resting orders
+    PriceLevel(BytesN<32>, BytesN<32>, i128), // (offered_product, requested_product, price) -> LadderLevel
+    LadderNode(BytesN<32>),                   // Order ID -> its neighbours in the price level queue
+    IndexLen(OfferIndex),
+    IndexEntry(OfferIndex, u32),
+    IndexPosition(OfferIndex, BytesN<32>),
 }
 
 #[contract]
@@ -80,12 +182,6 @@ impl CrossCooperativeTradeContract {
             .instance()
             .set(&DataKey::AgreementCounter, &0u32);
 
-        // Initialize active offers list
-        let active_offers: Vec<BytesN<32>> = Vec::new(&env);
-        env.storage()
-            .instance()
-            .set(&DataKey::ActiveOffers, &active_offers);
-
         Ok(())
     }
 
@@ -108,6 +204,26 @@ impl CrossCooperativeTradeContract {
         trade::create_trade_offer(env, cooperative_id, offered_product, requested_product)
     }
 
+    /// Create a trade offer backed by token quantities on both sides.
+    /// The offered asset is escrowed immediately.
+    pub fn create_escrowed_trade_offer(
+        env: Env,
+        cooperative_id: Address,
+        offered_product: BytesN<32>,
+        requested_product: BytesN<32>,
+        offered_asset: TradeAsset,
+        requested_asset: TradeAsset,
+    ) -> Result<BytesN<32>, TradeError> {
+        trade::create_escrowed_trade_offer(
+            env,
+            cooperative_id,
+            offered_product,
+            requested_product,
+            offered_asset,
+            requested_asset,
+        )
+    }
+
     /// Accept a trade offer
     pub fn accept_trade(
         env: Env,
@@ -117,7 +233,7 @@ impl CrossCooperativeTradeContract {
         trade::accept_trade(env, offer_id, accepting_cooperative)
     }
 
-    /// Complete a trade
+    /// Complete an accepted trade; either cooperative may settle it
     pub fn complete_trade(
         env: Env,
         offer_id: BytesN<32>,
@@ -126,14 +242,88 @@ impl CrossCooperativeTradeContract {
         trade::complete_trade(env, offer_id, caller)
     }
 
+    /// Cancel a pending trade offer and refund any escrowed asset
+    pub fn cancel_trade(env: Env, offer_id: BytesN<32>, caller: Address) -> Result<(), TradeError> {
+        trade::cancel_trade(env, offer_id, caller)
+    }
+
+    /// Unwind an accepted trade left unsettled past its deadline and refund both sides
+    pub fn expire_trade(env: Env, offer_id: BytesN<32>, caller: Address) -> Result<(), TradeError> {
+        trade::expire_trade(env, offer_id, caller)
+    }
+
     /// Get trade details
     pub fn get_trade_details(env: Env, offer_id: BytesN<32>) -> Result<TradeOffer, TradeError> {
         trade::get_trade_details(env, offer_id)
     }
 
-    /// List active offers
-    pub fn list_active_offers(env: Env) -> Result<Vec<BytesN<32>>, TradeError> {
-        trade::list_active_offers(env)
+    /// List active offers, one page at a time
+    pub fn list_active_offers(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<BytesN<32>>, TradeError> {
+        trade::list_active_offers(env, start, limit)
+    }
+
+    /// List active offers for an offered product, one page at a time
+    pub fn list_offers_by_product(
+        env: Env,
+        product: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<BytesN<32>>, TradeError> {
+        trade::list_offers_by_product(env, product, start, limit)
+    }
+
+    // Order Book Functions
+    /// Place a limit order to sell `offered_asset` for at least `requested_asset`,
+    /// matching it against the opposite book. Any unfilled remainder rests in the
+    /// book until filled, cancelled or expired.
+    pub fn place_order(
+        env: Env,
+        cooperative_id: Address,
+        offered_product: BytesN<32>,
+        requested_product: BytesN<32>,
+        offered_asset: TradeAsset,
+        requested_asset: TradeAsset,
+        expires_at: u64,
+    ) -> Result<BytesN<32>, TradeError> {
+        orderbook::place_order(
+            env,
+            cooperative_id,
+            offered_product,
+            requested_product,
+            offered_asset,
+            requested_asset,
+            expires_at,
+        )
+    }
+
+    /// Cancel a resting order and refund its unfilled remainder
+    pub fn cancel_order(env: Env, order_id: BytesN<32>, caller: Address) -> Result<(), TradeError> {
+        orderbook::cancel_order(env, order_id, caller)
+    }
+
+    /// Remove an expired order from the book and refund its owner
+    pub fn expire_order(env: Env, order_id: BytesN<32>) -> Result<(), TradeError> {
+        orderbook::expire_order(env, order_id)
+    }
+
+    /// Get order details
+    pub fn get_order(env: Env, order_id: BytesN<32>) -> Result<Order, TradeError> {
+        orderbook::get_order(env, order_id)
+    }
+
+    /// List resting orders for a product pair, one page at a time
+    pub fn list_orders(
+        env: Env,
+        offered_product: BytesN<32>,
+        requested_product: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<BytesN<32>>, TradeError> {
+        orderbook::list_orders(env, offered_product, requested_product, start, limit)
     }
 
     // Barter Agreement Functions
@@ -145,13 +335,92 @@ impl CrossCooperativeTradeContract {
         barter::get_barter_agreement(env, agreement_id)
     }
 
+    // Dispute Resolution Functions
+    /// Register an arbiter on the dispute panel
+    pub fn register_arbiter(env: Env, admin: Address, arbiter: Address) -> Result<(), AdminError> {
+        dispute::register_arbiter(env, admin, arbiter)
+    }
+
+    /// Remove an arbiter from the dispute panel
+    pub fn remove_arbiter(env: Env, admin: Address, arbiter: Address) -> Result<(), AdminError> {
+        dispute::remove_arbiter(env, admin, arbiter)
+    }
+
+    /// List the registered arbiter panel
+    pub fn get_arbiters(env: Env) -> Vec<Address> {
+        dispute::get_arbiters(env)
+    }
+
+    /// Raise a dispute over an accepted barter agreement
+    pub fn raise_dispute(
+        env: Env,
+        agreement_id: BytesN<32>,
+        caller: Address,
+        evidence_hash: BytesN<32>,
+    ) -> Result<Dispute, TradeError> {
+        dispute::raise_dispute(env, agreement_id, caller, evidence_hash)
+    }
+
+    /// Attach additional evidence to an open dispute
+    pub fn submit_evidence(
+        env: Env,
+        agreement_id: BytesN<32>,
+        caller: Address,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), TradeError> {
+        dispute::submit_evidence(env, agreement_id, caller, evidence_hash)
+    }
+
+    /// Rule on an open dispute, releasing escrow accordingly
+    pub fn resolve_dispute(
+        env: Env,
+        agreement_id: BytesN<32>,
+        arbiter: Address,
+        ruling: DisputeRuling,
+        at_fault: Option<Address>,
+    ) -> Result<(), TradeError> {
+        dispute::resolve_dispute(env, agreement_id, arbiter, ruling, at_fault)
+    }
+
+    /// Get dispute details
+    pub fn get_dispute(env: Env, agreement_id: BytesN<32>) -> Result<Dispute, TradeError> {
+        dispute::get_dispute(env, agreement_id)
+    }
+
     // Reputation Functions
-    /// Update reputation after trade
+    /// Record a trade outcome for a cooperative (admin only)
     pub fn update_reputation(
         env: Env,
         cooperative_id: Address,
         successful: bool,
     ) -> Result<(), TradeError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TradeError::UnauthorizedAccess)?;
+        admin.require_auth();
+
         reputation::update_reputation_after_trade(&env, &cooperative_id, successful)
     }
+
+    /// Rate the counterparty of a completed barter agreement (1-5)
+    pub fn rate_counterparty(
+        env: Env,
+        agreement_id: BytesN<32>,
+        rater: Address,
+        rating: u32,
+    ) -> Result<(), TradeError> {
+        reputation::rate_counterparty(env, agreement_id, rater, rating)
+    }
+
+    /// Get a cooperative's reputation with rating decay applied
+    pub fn get_reputation(env: Env, cooperative_id: Address) -> Reputation {
+        reputation::get_reputation(env, cooperative_id)
+    }
+
+    /// Get a cooperative's reputation score on a 100-500 scale
+    pub fn get_reputation_score(env: Env, cooperative_id: Address) -> u32 {
+        reputation::get_reputation_score(env, cooperative_id)
+    }
 }
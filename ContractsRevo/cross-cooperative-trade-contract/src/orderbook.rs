@@ -0,0 +1,442 @@
+use crate::{
+    escrow,
+    utils::{generate_id, index_add, index_page, index_remove},
+    DataKey, LadderLevel, LadderNode, OfferIndex, Order, TradeAsset, TradeError,
+};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Vec};
+
+/// Fixed-point scale for order prices (requested units per offered unit)
+pub const PRICE_SCALE: i128 = 10_000_000;
+
+/// Live resting orders a placement may skip over, bounding matching cost
+pub const MAX_MATCH_SCAN: u32 = 20;
+
+/// Distinct prices a product pair's book may rest orders at
+pub const MAX_PRICE_LEVELS: u32 = 64;
+
+pub fn place_order(
+    env: Env,
+    cooperative_id: Address,
+    offered_product: BytesN<32>,
+    requested_product: BytesN<32>,
+    offered_asset: TradeAsset,
+    requested_asset: TradeAsset,
+    expires_at: u64,
+) -> Result<BytesN<32>, TradeError> {
+    cooperative_id.require_auth();
+
+    if offered_product == requested_product || offered_asset.token == requested_asset.token {
+        return Err(TradeError::InvalidQuantity);
+    }
+    escrow::validate_asset(&offered_asset)?;
+    escrow::validate_asset(&requested_asset)?;
+
+    // Limit price: minimum requested units per offered unit
+    let price = requested_asset
+        .amount
+        .checked_mul(PRICE_SCALE)
+        .ok_or(TradeError::AmountOverflow)?
+        / offered_asset.amount;
+    if price <= 0 {
+        return Err(TradeError::InvalidPrice);
+    }
+    if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+        return Err(TradeError::TradeExpired);
+    }
+
+    escrow::lock_asset(&env, &cooperative_id, &offered_asset);
+
+    let mut order = Order {
+        order_id: generate_id(&env),
+        cooperative_id,
+        offered_product,
+        requested_product,
+        offered_token: offered_asset.token,
+        requested_token: requested_asset.token,
+        price,
+        quantity: offered_asset.amount,
+        remaining: offered_asset.amount,
+        expires_at,
+        status: String::from_str(&env, "Open"),
+    };
+
+    match_order(&env, &mut order)?;
+
+    if order.remaining > 0 {
+        ladder_insert(&env, &order)?;
+        index_add(&env, &book_index(&order), &order.order_id);
+    }
+    save_order(&env, &order);
+
+    Ok(order.order_id)
+}
+
+pub fn cancel_order(env: Env, order_id: BytesN<32>, caller: Address) -> Result<(), TradeError> {
+    caller.require_auth();
+
+    let mut order = get_order(env.clone(), order_id)?;
+    if order.cooperative_id != caller {
+        return Err(TradeError::UnauthorizedAccess);
+    }
+    if !is_resting(&env, &order) {
+        return Err(TradeError::InvalidTradeStatus);
+    }
+
+    close_order(&env, &mut order, "Cancelled");
+    Ok(())
+}
+
+pub fn expire_order(env: Env, order_id: BytesN<32>) -> Result<(), TradeError> {
+    let mut order = get_order(env.clone(), order_id)?;
+    if !is_resting(&env, &order) {
+        return Err(TradeError::InvalidTradeStatus);
+    }
+    if !is_expired(&env, &order) {
+        return Err(TradeError::OrderNotExpired);
+    }
+
+    close_order(&env, &mut order, "Expired");
+    Ok(())
+}
+
+pub fn get_order(env: Env, order_id: BytesN<32>) -> Result<Order, TradeError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Order(order_id))
+        .ok_or(TradeError::OrderNotFound)
+}
+
+pub fn list_orders(
+    env: Env,
+    offered_product: BytesN<32>,
+    requested_product: BytesN<32>,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<BytesN<32>>, TradeError> {
+    Ok(index_page(
+        &env,
+        &OfferIndex::OrderBook(offered_product, requested_product),
+        start,
+        limit,
+    ))
+}
+
+/// Fill an incoming order against the opposite book, best price first and oldest first
+/// within a price. Expired and closed makers met along the way are pruned from the book.
+fn match_order(env: &Env, taker: &mut Order) -> Result<(), TradeError> {
+    let levels_key = DataKey::PriceLevels(
+        taker.requested_product.clone(),
+        taker.offered_product.clone(),
+    );
+
+    let mut cursor: Option<BytesN<32>> = None;
+    let mut last_price: Option<i128> = None;
+    let mut skipped = 0;
+    while taker.remaining > 0 && skipped < MAX_MATCH_SCAN {
+        let maker_id = match cursor.take() {
+            Some(maker_id) => maker_id,
+            None => {
+                // Move on to the next price level above the last one visited
+                let price = match load_levels(env, &levels_key)
+                    .iter()
+                    .find(|price| last_price.is_none_or(|last| *price > last))
+                {
+                    Some(price) => price,
+                    None => break,
+                };
+
+                // The maker's ask must give the taker at least its limit price; levels
+                // are sorted, so no later maker can either
+                match price.checked_mul(taker.price) {
+                    Some(product) if product <= PRICE_SCALE * PRICE_SCALE => {}
+                    _ => break,
+                }
+
+                last_price = Some(price);
+                load_level(env, &taker.requested_product, &taker.offered_product, price).head
+            }
+        };
+        cursor = load_node(env, &maker_id).next;
+
+        let mut maker = get_order(env.clone(), maker_id)?;
+        if !is_resting(env, &maker) {
+            ladder_remove(env, &maker);
+            continue;
+        }
+        if is_expired(env, &maker) {
+            refund_and_close(env, &mut maker, "Expired");
+            ladder_remove(env, &maker);
+            continue;
+        }
+        if maker.cooperative_id == taker.cooperative_id
+            || maker.offered_token != taker.requested_token
+            || maker.requested_token != taker.offered_token
+        {
+            skipped += 1;
+            continue;
+        }
+
+        if !fill(env, taker, &mut maker)? {
+            break;
+        }
+        if maker.remaining == 0 {
+            ladder_remove(env, &maker);
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute one fill at the maker's price. Returns false if nothing could be exchanged.
+fn fill(env: &Env, taker: &mut Order, maker: &mut Order) -> Result<bool, TradeError> {
+    // Maker units the taker can afford, and the taker units owed for them (rounded up)
+    let affordable = taker
+        .remaining
+        .checked_mul(PRICE_SCALE)
+        .ok_or(TradeError::AmountOverflow)?
+        / maker.price;
+    let maker_units = affordable.min(maker.remaining);
+    if maker_units == 0 {
+        return Ok(false);
+    }
+    let taker_units = (maker_units
+        .checked_mul(maker.price)
+        .ok_or(TradeError::AmountOverflow)?
+        + PRICE_SCALE
+        - 1)
+        / PRICE_SCALE;
+
+    escrow::release_asset(
+        env,
+        &taker.cooperative_id,
+        &TradeAsset {
+            token: maker.offered_token.clone(),
+            amount: maker_units,
+        },
+    );
+    escrow::release_asset(
+        env,
+        &maker.cooperative_id,
+        &TradeAsset {
+            token: taker.offered_token.clone(),
+            amount: taker_units,
+        },
+    );
+
+    maker.remaining -= maker_units;
+    taker.remaining -= taker_units;
+    update_fill_status(env, maker);
+    update_fill_status(env, taker);
+
+    if maker.remaining == 0 {
+        index_remove(env, &book_index(maker), &maker.order_id);
+    }
+    save_order(env, maker);
+
+    env.events().publish(
+        (symbol_short!("fill"), maker.order_id.clone()),
+        (taker.order_id.clone(), maker_units, taker_units),
+    );
+
+    Ok(true)
+}
+
+fn update_fill_status(env: &Env, order: &mut Order) {
+    order.status = if order.remaining == 0 {
+        String::from_str(env, "Filled")
+    } else {
+        String::from_str(env, "PartiallyFilled")
+    };
+}
+
+/// Refund the unfilled remainder and drop the order from its book
+fn close_order(env: &Env, order: &mut Order, status: &str) {
+    refund_and_close(env, order, status);
+    ladder_remove(env, order);
+}
+
+/// Refund the unfilled remainder and drop the order from the paginated book
+fn refund_and_close(env: &Env, order: &mut Order, status: &str) {
+    if order.remaining > 0 {
+        escrow::release_asset(
+            env,
+            &order.cooperative_id,
+            &TradeAsset {
+                token: order.offered_token.clone(),
+                amount: order.remaining,
+            },
+        );
+    }
+
+    index_remove(env, &book_index(order), &order.order_id);
+    order.remaining = 0;
+    order.status = String::from_str(env, status);
+    save_order(env, order);
+}
+
+/// Queue a resting order behind every order at the same or a better price.
+/// Touches the pair's bounded price list, the price level and the previous tail only.
+fn ladder_insert(env: &Env, order: &Order) -> Result<(), TradeError> {
+    let storage = env.storage().persistent();
+    let level_key = DataKey::PriceLevel(
+        order.offered_product.clone(),
+        order.requested_product.clone(),
+        order.price,
+    );
+
+    let mut node = LadderNode {
+        prev: None,
+        next: None,
+    };
+    let level = match storage.get::<_, LadderLevel>(&level_key) {
+        Some(mut level) => {
+            let mut tail = load_node(env, &level.tail);
+            tail.next = Some(order.order_id.clone());
+            save_node(env, &level.tail, &tail);
+
+            node.prev = Some(level.tail.clone());
+            level.tail = order.order_id.clone();
+            level
+        }
+        None => {
+            let levels_key = levels_key(order);
+            let mut levels = load_levels(env, &levels_key);
+            if levels.len() >= MAX_PRICE_LEVELS {
+                return Err(TradeError::TooManyPriceLevels);
+            }
+            let position = levels
+                .iter()
+                .position(|price| price > order.price)
+                .map_or(levels.len(), |position| position as u32);
+            levels.insert(position, order.price);
+            storage.set(&levels_key, &levels);
+
+            LadderLevel {
+                head: order.order_id.clone(),
+                tail: order.order_id.clone(),
+            }
+        }
+    };
+
+    save_node(env, &order.order_id, &node);
+    storage.set(&level_key, &level);
+    Ok(())
+}
+
+/// Unlink an order from its price level, dropping the level once it is empty
+fn ladder_remove(env: &Env, order: &Order) {
+    let storage = env.storage().persistent();
+    let node_key = DataKey::LadderNode(order.order_id.clone());
+    let node: LadderNode = match storage.get(&node_key) {
+        Some(node) => node,
+        None => return,
+    };
+    storage.remove(&node_key);
+
+    let level_key = DataKey::PriceLevel(
+        order.offered_product.clone(),
+        order.requested_product.clone(),
+        order.price,
+    );
+    let mut level = load_level(
+        env,
+        &order.offered_product,
+        &order.requested_product,
+        order.price,
+    );
+
+    match (&node.prev, &node.next) {
+        (None, None) => {
+            storage.remove(&level_key);
+            let levels_key = levels_key(order);
+            let mut levels = load_levels(env, &levels_key);
+            if let Some(position) = levels.first_index_of(order.price) {
+                levels.remove(position);
+            }
+            storage.set(&levels_key, &levels);
+            return;
+        }
+        (Some(prev), None) => level.tail = prev.clone(),
+        (None, Some(next)) => level.head = next.clone(),
+        (Some(_), Some(_)) => {}
+    }
+
+    if let Some(prev) = &node.prev {
+        let mut prev_node = load_node(env, prev);
+        prev_node.next = node.next.clone();
+        save_node(env, prev, &prev_node);
+    }
+    if let Some(next) = &node.next {
+        let mut next_node = load_node(env, next);
+        next_node.prev = node.prev.clone();
+        save_node(env, next, &next_node);
+    }
+    if node.prev.is_none() || node.next.is_none() {
+        storage.set(&level_key, &level);
+    }
+}
+
+fn load_levels(env: &Env, key: &DataKey) -> Vec<i128> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn load_level(
+    env: &Env,
+    offered_product: &BytesN<32>,
+    requested_product: &BytesN<32>,
+    price: i128,
+) -> LadderLevel {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PriceLevel(
+            offered_product.clone(),
+            requested_product.clone(),
+            price,
+        ))
+        .unwrap()
+}
+
+fn load_node(env: &Env, order_id: &BytesN<32>) -> LadderNode {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LadderNode(order_id.clone()))
+        .unwrap()
+}
+
+fn save_node(env: &Env, order_id: &BytesN<32>, node: &LadderNode) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LadderNode(order_id.clone()), node);
+}
+
+fn levels_key(order: &Order) -> DataKey {
+    DataKey::PriceLevels(
+        order.offered_product.clone(),
+        order.requested_product.clone(),
+    )
+}
+
+fn is_resting(env: &Env, order: &Order) -> bool {
+    order.status == String::from_str(env, "Open")
+        || order.status == String::from_str(env, "PartiallyFilled")
+}
+
+fn is_expired(env: &Env, order: &Order) -> bool {
+    order.expires_at != 0 && order.expires_at <= env.ledger().timestamp()
+}
+
+fn book_index(order: &Order) -> OfferIndex {
+    OfferIndex::OrderBook(
+        order.offered_product.clone(),
+        order.requested_product.clone(),
+    )
+}
+
+fn save_order(env: &Env, order: &Order) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Order(order.order_id.clone()), order);
+}
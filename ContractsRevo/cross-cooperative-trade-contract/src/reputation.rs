@@ -1,44 +1,176 @@
 use crate::{DataKey, Reputation, TradeError};
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, BytesN, Env, String};
 
-pub fn update_reputation_after_trade(
-    env: &Env,
-    cooperative_id: &Address,
-    trade_successful: bool,
-) -> Result<(), TradeError> {
-    // Get existing reputation or create new one
-    let mut reputation: Reputation = env
-        .storage()
+/// Weight of a single counterparty rating
+pub const RATING_WEIGHT: u64 = 1_000;
+
+/// Weight of the one-star penalty recorded when a cooperative fails a trade
+pub const PENALTY_WEIGHT: u64 = 3_000;
+
+/// Length of one decay period (30 days)
+pub const DECAY_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// Percentage of weight retained per elapsed decay period
+pub const DECAY_RETAINED_PERCENT: u64 = 90;
+
+/// Periods after which older ratings stop counting entirely (3 years)
+pub const MAX_DECAY_PERIODS: u64 = 36;
+
+fn load_reputation(env: &Env, cooperative_id: &Address) -> Reputation {
+    env.storage()
         .persistent()
         .get(&DataKey::Reputation(cooperative_id.clone()))
         .unwrap_or(Reputation {
             cooperative_id: cooperative_id.clone(),
             successful_trades: 0,
+            failed_trades: 0,
             rating: 5, // Start with max rating
-        });
+            weighted_score: 0,
+            total_weight: 0,
+            ratings_received: 0,
+            last_updated: env.ledger().timestamp(),
+        })
+}
+
+fn save_reputation(env: &Env, reputation: &Reputation) {
+    env.storage().persistent().set(
+        &DataKey::Reputation(reputation.cooperative_id.clone()),
+        reputation,
+    );
+}
+
+/// Age existing rating weight so older trades count less
+fn apply_decay(env: &Env, reputation: &mut Reputation) {
+    let now = env.ledger().timestamp();
+    let periods = now.saturating_sub(reputation.last_updated) / DECAY_PERIOD;
+    if periods == 0 {
+        return;
+    }
+
+    if periods > MAX_DECAY_PERIODS {
+        reputation.weighted_score = 0;
+        reputation.total_weight = 0;
+    } else {
+        for _ in 0..periods {
+            reputation.weighted_score = reputation.weighted_score * DECAY_RETAINED_PERCENT / 100;
+            reputation.total_weight = reputation.total_weight * DECAY_RETAINED_PERCENT / 100;
+        }
+    }
+    reputation.last_updated += periods * DECAY_PERIOD;
+}
+
+/// Score on a 100-500 scale: the decayed weighted average of ratings received
+fn score(reputation: &Reputation) -> u32 {
+    if reputation.total_weight == 0 {
+        return reputation.rating * 100;
+    }
+    (reputation.weighted_score * 100 / reputation.total_weight) as u32
+}
+
+fn refresh_rating(reputation: &mut Reputation) {
+    if reputation.total_weight == 0 {
+        // Without ratings, fall back to a rating based on successful trades
+        reputation.rating = if reputation.successful_trades >= 10 {
+            5
+        } else if reputation.successful_trades >= 5 {
+            4
+        } else if reputation.successful_trades >= 2 {
+            3
+        } else if reputation.successful_trades >= 1 {
+            2
+        } else {
+            1
+        };
+    } else {
+        reputation.rating = ((score(reputation) + 50) / 100).clamp(1, 5);
+    }
+}
+
+fn record_rating(env: &Env, reputation: &mut Reputation, rating: u32, weight: u64) {
+    apply_decay(env, reputation);
+    reputation.weighted_score += rating as u64 * weight;
+    reputation.total_weight += weight;
+}
+
+pub fn update_reputation_after_trade(
+    env: &Env,
+    cooperative_id: &Address,
+    trade_successful: bool,
+) -> Result<(), TradeError> {
+    let mut reputation = load_reputation(env, cooperative_id);
 
-    // Update trade counts (simplified - just track successful trades)
+    // Update trade counts; a failed trade carries a weighted one-star penalty
     if trade_successful {
         reputation.successful_trades += 1;
+    } else {
+        reputation.failed_trades += 1;
+        record_rating(env, &mut reputation, 1, PENALTY_WEIGHT);
+    }
+
+    refresh_rating(&mut reputation);
+    save_reputation(env, &reputation);
+
+    Ok(())
+}
+
+pub fn rate_counterparty(
+    env: Env,
+    agreement_id: BytesN<32>,
+    rater: Address,
+    rating: u32,
+) -> Result<(), TradeError> {
+    rater.require_auth();
+
+    if !(1..=5).contains(&rating) {
+        return Err(TradeError::InvalidRating);
     }
 
-    // Simple rating calculation based on successful trades
-    reputation.rating = if reputation.successful_trades >= 10 {
-        5
-    } else if reputation.successful_trades >= 5 {
-        4
-    } else if reputation.successful_trades >= 2 {
-        3
-    } else if reputation.successful_trades >= 1 {
-        2
+    let agreement = crate::barter::get_barter_agreement(env.clone(), agreement_id.clone())?;
+    let ratee = if rater == agreement.offering_cooperative {
+        agreement.accepting_cooperative.clone()
+    } else if rater == agreement.accepting_cooperative {
+        agreement.offering_cooperative.clone()
     } else {
-        1
+        return Err(TradeError::UnauthorizedAccess);
     };
 
-    // Store updated reputation
-    env.storage()
-        .persistent()
-        .set(&DataKey::Reputation(cooperative_id.clone()), &reputation);
+    if ratee == rater {
+        return Err(TradeError::CannotRateSelf);
+    }
+
+    // Only settled agreements can be rated
+    let trade_offer = crate::trade::get_trade_details(env.clone(), agreement.trade_offer_id)?;
+    if trade_offer.status != String::from_str(&env, "Completed")
+        && trade_offer.status != String::from_str(&env, "Settled")
+    {
+        return Err(TradeError::InvalidTradeStatus);
+    }
+
+    // One rating per side of each agreement
+    let rating_key = DataKey::AgreementRating(agreement_id, rater);
+    if env.storage().persistent().has(&rating_key) {
+        return Err(TradeError::AlreadyRated);
+    }
+    env.storage().persistent().set(&rating_key, &rating);
+
+    let mut reputation = load_reputation(&env, &ratee);
+    record_rating(&env, &mut reputation, rating, RATING_WEIGHT);
+    reputation.ratings_received += 1;
+    refresh_rating(&mut reputation);
+    save_reputation(&env, &reputation);
 
     Ok(())
 }
+
+/// Current reputation with decay applied as of the current ledger time
+pub fn get_reputation(env: Env, cooperative_id: Address) -> Reputation {
+    let mut reputation = load_reputation(&env, &cooperative_id);
+    apply_decay(&env, &mut reputation);
+    refresh_rating(&mut reputation);
+    reputation
+}
+
+/// Reputation score on a 100-500 scale for use by other contracts
+pub fn get_reputation_score(env: Env, cooperative_id: Address) -> u32 {
+    score(&get_reputation(env, cooperative_id))
+}
@@ -322,7 +322,7 @@ fn test_complete_trade_success() {
         .expect("Trade offer creation should succeed");
 
     // Accept the trade offer
-    let _agreement_id = client
+    let agreement_id = client
         .try_accept_trade(&offer_id, &accepting_cooperative)
         .unwrap()
         .expect("Accept trade should succeed");
@@ -337,6 +337,10 @@ fn test_complete_trade_success() {
                 .unwrap()
                 .expect("Trade offer should exist");
             assert_eq!(trade_offer.status, String::from_str(&env, "Completed"));
+
+            // The agreement is closed along with the offer
+            let agreement = client.get_barter_agreement(&agreement_id);
+            assert_eq!(agreement.status, String::from_str(&env, "Completed"));
         }
         Ok(Err(trade_error)) => panic!("Complete trade failed with error: {:?}", trade_error),
         Err(call_error) => panic!("Contract call failed with error: {:?}", call_error),
@@ -521,7 +525,7 @@ fn test_list_active_offers_empty() {
     let _ = client.try_initialize(&admin);
 
     // Test listing active offers when there are none
-    let result = client.try_list_active_offers();
+    let result = client.try_list_active_offers(&0, &MAX_PAGE_SIZE);
     match result {
         Ok(Ok(offers)) => {
             assert_eq!(offers.len(), 0, "Should have no active offers initially");
@@ -577,7 +581,7 @@ fn test_list_active_offers_with_offers() {
         .expect("Trade offer creation should succeed");
 
     // Test listing active offers - should have 2 offers
-    let result = client.try_list_active_offers();
+    let result = client.try_list_active_offers(&0, &MAX_PAGE_SIZE);
     match result {
         Ok(Ok(offers)) => {
             assert_eq!(offers.len(), 2, "Should have 2 active offers");
@@ -595,7 +599,7 @@ fn test_list_active_offers_with_offers() {
         .expect("Accept trade should succeed");
 
     // Test listing active offers after acceptance - should have 1 offer
-    let result_after_accept = client.try_list_active_offers();
+    let result_after_accept = client.try_list_active_offers(&0, &MAX_PAGE_SIZE);
     match result_after_accept {
         Ok(Ok(offers)) => {
             assert_eq!(
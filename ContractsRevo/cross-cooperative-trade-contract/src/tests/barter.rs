@@ -327,7 +327,7 @@ mod barter_agreement_validation {
 
         assert_eq!(barter_agreement.agreement_id, agreement_id);
         assert_eq!(barter_agreement.trade_offer_id, offer_id);
-        assert_eq!(barter_agreement.status, String::from_str(&env, "Active"));
+        assert_eq!(barter_agreement.status, String::from_str(&env, "Completed"));
     }
 }
 
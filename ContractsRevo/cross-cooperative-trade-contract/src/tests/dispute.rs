@@ -0,0 +1,341 @@
+#![cfg(test)]
+
+use super::*;
+use crate::tests::utils::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+struct DisputeSetup<'a> {
+    client: CrossCooperativeTradeContractClient<'a>,
+    admin: Address,
+    offering_coop: Address,
+    accepting_coop: Address,
+    corn_token: Address,
+    wheat_token: Address,
+    offer_id: BytesN<32>,
+    agreement_id: BytesN<32>,
+}
+
+/// Create an accepted escrowed trade of 100 corn for 50 wheat
+fn setup_accepted_trade(env: &Env) -> DisputeSetup<'_> {
+    let (admin, client) = setup_contract_with_admin(env);
+    let offering_coop = Address::generate(env);
+    let accepting_coop = Address::generate(env);
+    let corn_token = create_test_token(env, &[&offering_coop], 1_000);
+    let wheat_token = create_test_token(env, &[&accepting_coop], 1_000);
+
+    let offer_id = client.create_escrowed_trade_offer(
+        &offering_coop,
+        &create_test_product(env, "corn"),
+        &create_test_product(env, "wheat"),
+        &TradeAsset {
+            token: corn_token.clone(),
+            amount: 100,
+        },
+        &TradeAsset {
+            token: wheat_token.clone(),
+            amount: 50,
+        },
+    );
+    let agreement_id = client.accept_trade(&offer_id, &accepting_coop);
+
+    DisputeSetup {
+        client,
+        admin,
+        offering_coop,
+        accepting_coop,
+        corn_token,
+        wheat_token,
+        offer_id,
+        agreement_id,
+    }
+}
+
+mod dispute_raising {
+    use super::*;
+
+    #[test]
+    fn test_raise_dispute_freezes_trade() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        let evidence = create_test_product(&env, "missing_delivery");
+
+        let dispute =
+            setup
+                .client
+                .raise_dispute(&setup.agreement_id, &setup.accepting_coop, &evidence);
+
+        assert_eq!(dispute.raised_by, setup.accepting_coop);
+        assert_eq!(dispute.evidence.len(), 1);
+        assert_eq!(dispute.ruling, DisputeRuling::Pending);
+
+        let agreement = setup.client.get_barter_agreement(&setup.agreement_id);
+        assert_eq!(agreement.status, String::from_str(&env, "Disputed"));
+
+        let result = setup
+            .client
+            .try_complete_trade(&setup.offer_id, &setup.offering_coop);
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+
+    #[test]
+    fn test_raise_dispute_by_outsider_rejected() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        let outsider = Address::generate(&env);
+
+        let result = setup.client.try_raise_dispute(
+            &setup.agreement_id,
+            &outsider,
+            &create_test_product(&env, "evidence"),
+        );
+        assert_error_contains(result, Ok(TradeError::UnauthorizedAccess));
+    }
+
+    #[test]
+    fn test_completed_trade_cannot_be_disputed() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        setup
+            .client
+            .complete_trade(&setup.offer_id, &setup.offering_coop);
+
+        let evidence = create_test_product(&env, "late_claim");
+        let result =
+            setup
+                .client
+                .try_raise_dispute(&setup.agreement_id, &setup.accepting_coop, &evidence);
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+
+    #[test]
+    fn test_raise_dispute_twice_rejected() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        let evidence = create_test_product(&env, "evidence");
+        setup
+            .client
+            .raise_dispute(&setup.agreement_id, &setup.offering_coop, &evidence);
+
+        let result =
+            setup
+                .client
+                .try_raise_dispute(&setup.agreement_id, &setup.accepting_coop, &evidence);
+        assert_error_contains(result, Ok(TradeError::DisputeAlreadyExists));
+    }
+
+    #[test]
+    fn test_submit_additional_evidence() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        setup.client.raise_dispute(
+            &setup.agreement_id,
+            &setup.offering_coop,
+            &create_test_product(&env, "evidence_a"),
+        );
+
+        setup.client.submit_evidence(
+            &setup.agreement_id,
+            &setup.accepting_coop,
+            &create_test_product(&env, "evidence_b"),
+        );
+
+        let dispute = setup.client.get_dispute(&setup.agreement_id);
+        assert_eq!(dispute.evidence.len(), 2);
+    }
+
+    #[test]
+    fn test_arbiter_assigned_from_panel() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        let arbiter = Address::generate(&env);
+        setup.client.register_arbiter(&setup.admin, &arbiter);
+
+        let dispute = setup.client.raise_dispute(
+            &setup.agreement_id,
+            &setup.offering_coop,
+            &create_test_product(&env, "evidence"),
+        );
+
+        assert_eq!(dispute.arbiter, Some(arbiter));
+    }
+
+    #[test]
+    fn test_register_arbiter_requires_admin() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let impostor = Address::generate(&env);
+
+        let result = client.try_register_arbiter(&impostor, &Address::generate(&env));
+        assert_error_contains(result, Ok(AdminError::UnauthorizedAccess));
+    }
+}
+
+mod dispute_resolution {
+    use super::*;
+
+    fn raise(env: &Env, setup: &DisputeSetup) {
+        setup.client.raise_dispute(
+            &setup.agreement_id,
+            &setup.accepting_coop,
+            &create_test_product(env, "evidence"),
+        );
+    }
+
+    #[test]
+    fn test_full_completion_swaps_escrow() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        raise(&env, &setup);
+
+        setup.client.resolve_dispute(
+            &setup.agreement_id,
+            &setup.admin,
+            &DisputeRuling::FullCompletion,
+            &None,
+        );
+
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.accepting_coop),
+            100
+        );
+        assert_eq!(
+            token_balance(&env, &setup.wheat_token, &setup.offering_coop),
+            50
+        );
+
+        let agreement = setup.client.get_barter_agreement(&setup.agreement_id);
+        assert_eq!(agreement.status, String::from_str(&env, "Completed"));
+    }
+
+    #[test]
+    fn test_partial_settlement_splits_escrow() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        raise(&env, &setup);
+
+        setup.client.resolve_dispute(
+            &setup.agreement_id,
+            &setup.admin,
+            &DisputeRuling::PartialSettlement(40),
+            &None,
+        );
+
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.accepting_coop),
+            40
+        );
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.offering_coop),
+            960
+        );
+        assert_eq!(
+            token_balance(&env, &setup.wheat_token, &setup.offering_coop),
+            20
+        );
+        assert_eq!(
+            token_balance(&env, &setup.wheat_token, &setup.accepting_coop),
+            980
+        );
+    }
+
+    #[test]
+    fn test_reversal_refunds_and_penalizes() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        raise(&env, &setup);
+
+        setup.client.resolve_dispute(
+            &setup.agreement_id,
+            &setup.admin,
+            &DisputeRuling::Reversal,
+            &Some(setup.offering_coop.clone()),
+        );
+
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.offering_coop),
+            1_000
+        );
+        assert_eq!(
+            token_balance(&env, &setup.wheat_token, &setup.accepting_coop),
+            1_000
+        );
+
+        let dispute = setup.client.get_dispute(&setup.agreement_id);
+        assert_eq!(dispute.ruling, DisputeRuling::Reversal);
+        assert_eq!(dispute.at_fault, Some(setup.offering_coop.clone()));
+        assert!(dispute.resolved_at.is_some());
+
+        let offer = setup.client.get_trade_details(&setup.offer_id);
+        assert_eq!(offer.status, String::from_str(&env, "Reversed"));
+    }
+
+    #[test]
+    fn test_assigned_arbiter_can_rule() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        let arbiter = Address::generate(&env);
+        setup.client.register_arbiter(&setup.admin, &arbiter);
+        raise(&env, &setup);
+
+        setup.client.resolve_dispute(
+            &setup.agreement_id,
+            &arbiter,
+            &DisputeRuling::FullCompletion,
+            &None,
+        );
+
+        let dispute = setup.client.get_dispute(&setup.agreement_id);
+        assert_eq!(dispute.ruling, DisputeRuling::FullCompletion);
+    }
+
+    #[test]
+    fn test_unassigned_party_cannot_rule() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        raise(&env, &setup);
+
+        let result = setup.client.try_resolve_dispute(
+            &setup.agreement_id,
+            &setup.accepting_coop,
+            &DisputeRuling::Reversal,
+            &None,
+        );
+        assert_error_contains(result, Ok(TradeError::UnauthorizedAccess));
+    }
+
+    #[test]
+    fn test_invalid_partial_percentage_rejected() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        raise(&env, &setup);
+
+        let result = setup.client.try_resolve_dispute(
+            &setup.agreement_id,
+            &setup.admin,
+            &DisputeRuling::PartialSettlement(100),
+            &None,
+        );
+        assert_error_contains(result, Ok(TradeError::InvalidRuling));
+    }
+
+    #[test]
+    fn test_resolve_twice_rejected() {
+        let env = Env::default();
+        let setup = setup_accepted_trade(&env);
+        raise(&env, &setup);
+        setup.client.resolve_dispute(
+            &setup.agreement_id,
+            &setup.admin,
+            &DisputeRuling::Reversal,
+            &None,
+        );
+
+        let result = setup.client.try_resolve_dispute(
+            &setup.agreement_id,
+            &setup.admin,
+            &DisputeRuling::FullCompletion,
+            &None,
+        );
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+}
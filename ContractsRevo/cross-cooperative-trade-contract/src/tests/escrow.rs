@@ -0,0 +1,294 @@
+#![cfg(test)]
+
+use super::*;
+use crate::tests::utils::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env, String,
+};
+
+struct EscrowSetup<'a> {
+    client: CrossCooperativeTradeContractClient<'a>,
+    contract: Address,
+    offering_coop: Address,
+    accepting_coop: Address,
+    offered_asset: TradeAsset,
+    requested_asset: TradeAsset,
+}
+
+fn setup_escrow(env: &Env) -> EscrowSetup<'_> {
+    let (_, client) = setup_contract_with_admin(env);
+    let offering_coop = Address::generate(env);
+    let accepting_coop = Address::generate(env);
+    let corn_token = create_test_token(env, &[&offering_coop], 1_000);
+    let wheat_token = create_test_token(env, &[&accepting_coop], 1_000);
+
+    EscrowSetup {
+        contract: client.address.clone(),
+        client,
+        offering_coop,
+        accepting_coop,
+        offered_asset: TradeAsset {
+            token: corn_token,
+            amount: 300,
+        },
+        requested_asset: TradeAsset {
+            token: wheat_token,
+            amount: 200,
+        },
+    }
+}
+
+fn create_offer(env: &Env, setup: &EscrowSetup) -> BytesN<32> {
+    setup
+        .client
+        .try_create_escrowed_trade_offer(
+            &setup.offering_coop,
+            &create_test_product(env, "corn"),
+            &create_test_product(env, "wheat"),
+            &setup.offered_asset,
+            &setup.requested_asset,
+        )
+        .unwrap()
+        .expect("Escrowed offer creation should succeed")
+}
+
+mod escrow_locking {
+    use super::*;
+
+    #[test]
+    fn test_offered_asset_escrowed_on_creation() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+
+        let offer_id = create_offer(&env, &setup);
+
+        let offer = setup.client.get_trade_details(&offer_id);
+        assert_eq!(offer.offered_token, Some(setup.offered_asset.token.clone()));
+        assert_eq!(offer.offered_amount, 300);
+        assert_eq!(
+            offer.requested_token,
+            Some(setup.requested_asset.token.clone())
+        );
+        assert_eq!(offer.requested_amount, 200);
+        assert_eq!(offer.accepting_cooperative, None);
+
+        let corn = &setup.offered_asset.token;
+        assert_eq!(token_balance(&env, corn, &setup.offering_coop), 700);
+        assert_eq!(token_balance(&env, corn, &setup.contract), 300);
+    }
+
+    #[test]
+    fn test_requested_asset_escrowed_on_acceptance() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+
+        setup.client.accept_trade(&offer_id, &setup.accepting_coop);
+
+        let offer = setup.client.get_trade_details(&offer_id);
+        assert_eq!(
+            offer.accepting_cooperative,
+            Some(setup.accepting_coop.clone())
+        );
+
+        let wheat = &setup.requested_asset.token;
+        assert_eq!(token_balance(&env, wheat, &setup.accepting_coop), 800);
+        assert_eq!(token_balance(&env, wheat, &setup.contract), 200);
+    }
+
+    #[test]
+    fn test_invalid_amount_rejected() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let zero_asset = TradeAsset {
+            token: setup.offered_asset.token.clone(),
+            amount: 0,
+        };
+
+        let result = setup.client.try_create_escrowed_trade_offer(
+            &setup.offering_coop,
+            &create_test_product(&env, "corn"),
+            &create_test_product(&env, "wheat"),
+            &zero_asset,
+            &setup.requested_asset,
+        );
+
+        assert_error_contains(result, Ok(TradeError::InvalidAmount));
+    }
+
+    #[test]
+    fn test_acceptance_fails_without_requested_balance() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+        let unfunded_coop = Address::generate(&env);
+
+        let result = setup.client.try_accept_trade(&offer_id, &unfunded_coop);
+        assert_is_error(result);
+
+        let offer = setup.client.get_trade_details(&offer_id);
+        assert_eq!(offer.status, String::from_str(&env, "Pending"));
+    }
+}
+
+mod escrow_settlement {
+    use super::*;
+
+    #[test]
+    fn test_complete_trade_swaps_assets() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+        let agreement_id = setup.client.accept_trade(&offer_id, &setup.accepting_coop);
+
+        setup.client.complete_trade(&offer_id, &setup.offering_coop);
+        assert_eq!(
+            setup.client.get_barter_agreement(&agreement_id).status,
+            String::from_str(&env, "Completed")
+        );
+
+        let corn = &setup.offered_asset.token;
+        let wheat = &setup.requested_asset.token;
+        assert_eq!(token_balance(&env, corn, &setup.accepting_coop), 300);
+        assert_eq!(token_balance(&env, wheat, &setup.offering_coop), 200);
+        assert_eq!(token_balance(&env, corn, &setup.contract), 0);
+        assert_eq!(token_balance(&env, wheat, &setup.contract), 0);
+    }
+
+    #[test]
+    fn test_accepting_coop_can_complete_trade() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+        setup.client.accept_trade(&offer_id, &setup.accepting_coop);
+
+        setup
+            .client
+            .complete_trade(&offer_id, &setup.accepting_coop);
+
+        let corn = &setup.offered_asset.token;
+        let wheat = &setup.requested_asset.token;
+        assert_eq!(token_balance(&env, corn, &setup.accepting_coop), 300);
+        assert_eq!(token_balance(&env, wheat, &setup.offering_coop), 200);
+
+        // Both sides are credited with the trade
+        assert_eq!(
+            setup
+                .client
+                .get_reputation(&setup.offering_coop)
+                .successful_trades,
+            1
+        );
+        assert_eq!(
+            setup
+                .client
+                .get_reputation(&setup.accepting_coop)
+                .successful_trades,
+            1
+        );
+    }
+
+    #[test]
+    fn test_expire_unsettled_trade_refunds_both_sides() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+        let agreement_id = setup.client.accept_trade(&offer_id, &setup.accepting_coop);
+
+        let result = setup
+            .client
+            .try_expire_trade(&offer_id, &setup.accepting_coop);
+        assert_error_contains(result, Ok(TradeError::TradeNotExpired));
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += crate::trade::SETTLEMENT_WINDOW);
+        let outsider = Address::generate(&env);
+        let result = setup.client.try_expire_trade(&offer_id, &outsider);
+        assert_error_contains(result, Ok(TradeError::UnauthorizedAccess));
+
+        setup.client.expire_trade(&offer_id, &setup.accepting_coop);
+
+        let offer = setup.client.get_trade_details(&offer_id);
+        assert_eq!(offer.status, String::from_str(&env, "Expired"));
+        assert_eq!(
+            setup.client.get_barter_agreement(&agreement_id).status,
+            String::from_str(&env, "Expired")
+        );
+
+        let corn = &setup.offered_asset.token;
+        let wheat = &setup.requested_asset.token;
+        assert_eq!(token_balance(&env, corn, &setup.offering_coop), 1_000);
+        assert_eq!(token_balance(&env, wheat, &setup.accepting_coop), 1_000);
+        assert_eq!(token_balance(&env, corn, &setup.contract), 0);
+        assert_eq!(token_balance(&env, wheat, &setup.contract), 0);
+
+        // An unwound trade can no longer be completed
+        let result = setup
+            .client
+            .try_complete_trade(&offer_id, &setup.offering_coop);
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+
+    #[test]
+    fn test_cancel_pending_offer_refunds_offerer() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+
+        setup.client.cancel_trade(&offer_id, &setup.offering_coop);
+
+        let offer = setup.client.get_trade_details(&offer_id);
+        assert_eq!(offer.status, String::from_str(&env, "Cancelled"));
+        assert!(!setup
+            .client
+            .list_active_offers(&0, &MAX_PAGE_SIZE)
+            .contains(&offer_id));
+
+        let corn = &setup.offered_asset.token;
+        assert_eq!(token_balance(&env, corn, &setup.offering_coop), 1_000);
+        assert_eq!(token_balance(&env, corn, &setup.contract), 0);
+    }
+
+    #[test]
+    fn test_cancel_by_non_party_rejected() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+
+        let result = setup
+            .client
+            .try_cancel_trade(&offer_id, &setup.accepting_coop);
+        assert_error_contains(result, Ok(TradeError::UnauthorizedAccess));
+    }
+
+    #[test]
+    fn test_cancel_after_acceptance_rejected() {
+        let env = Env::default();
+        let setup = setup_escrow(&env);
+        let offer_id = create_offer(&env, &setup);
+        setup.client.accept_trade(&offer_id, &setup.accepting_coop);
+
+        let result = setup
+            .client
+            .try_cancel_trade(&offer_id, &setup.offering_coop);
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+
+    #[test]
+    fn test_cancel_unescrowed_offer() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let cooperative = Address::generate(&env);
+        let offer_id = client.create_trade_offer(
+            &cooperative,
+            &create_test_product(&env, "corn"),
+            &create_test_product(&env, "wheat"),
+        );
+
+        client.cancel_trade(&offer_id, &cooperative);
+
+        let offer = client.get_trade_details(&offer_id);
+        assert_eq!(offer.status, String::from_str(&env, "Cancelled"));
+    }
+}
@@ -86,7 +86,7 @@ mod scalability_tests {
                 .try_get_barter_agreement(&agreement_id)
                 .unwrap()
                 .expect("Barter agreement should exist");
-            assert_eq!(barter_agreement.status, String::from_str(&env, "Active"));
+            assert_eq!(barter_agreement.status, String::from_str(&env, "Completed"));
         }
     }
 
@@ -132,7 +132,7 @@ mod scalability_tests {
 
         // Verify all offers are in active offers list
         let active_offers = client
-            .try_list_active_offers()
+            .try_list_active_offers(&0, &MAX_PAGE_SIZE)
             .unwrap()
             .expect("Should be able to list active offers");
 
@@ -517,7 +517,7 @@ mod complex_workflows {
                 .try_get_barter_agreement(&agreement_id)
                 .unwrap()
                 .expect("Barter agreement should exist");
-            assert_eq!(barter_agreement.status, String::from_str(&env, "Active"));
+            assert_eq!(barter_agreement.status, String::from_str(&env, "Completed"));
         }
 
         // Update reputation for all cooperatives
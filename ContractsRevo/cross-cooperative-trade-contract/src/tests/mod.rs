@@ -2,7 +2,10 @@
 
 // Import all test modules
 mod barter;
+mod dispute;
+mod escrow;
 mod integration;
+mod orderbook;
 mod reputation;
 mod trade;
 mod utils;
@@ -0,0 +1,463 @@
+#![cfg(test)]
+
+use super::*;
+use crate::tests::utils::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+struct BookSetup<'a> {
+    client: CrossCooperativeTradeContractClient<'a>,
+    corn: BytesN<32>,
+    wheat: BytesN<32>,
+    corn_token: Address,
+    wheat_token: Address,
+    corn_seller: Address,
+    wheat_seller: Address,
+}
+
+fn setup_book(env: &Env) -> BookSetup<'_> {
+    let (_, client) = setup_contract_with_admin(env);
+    let corn_seller = Address::generate(env);
+    let wheat_seller = Address::generate(env);
+
+    BookSetup {
+        client,
+        corn: create_test_product(env, "corn"),
+        wheat: create_test_product(env, "wheat"),
+        corn_token: create_test_token(env, &[&corn_seller], 1_000),
+        wheat_token: create_test_token(env, &[&wheat_seller], 1_000),
+        corn_seller,
+        wheat_seller,
+    }
+}
+
+fn sell_corn(setup: &BookSetup, quantity: i128, wheat_wanted: i128, expires_at: u64) -> BytesN<32> {
+    setup.client.place_order(
+        &setup.corn_seller,
+        &setup.corn,
+        &setup.wheat,
+        &TradeAsset {
+            token: setup.corn_token.clone(),
+            amount: quantity,
+        },
+        &TradeAsset {
+            token: setup.wheat_token.clone(),
+            amount: wheat_wanted,
+        },
+        &expires_at,
+    )
+}
+
+fn sell_wheat(setup: &BookSetup, quantity: i128, corn_wanted: i128) -> BytesN<32> {
+    setup.client.place_order(
+        &setup.wheat_seller,
+        &setup.wheat,
+        &setup.corn,
+        &TradeAsset {
+            token: setup.wheat_token.clone(),
+            amount: quantity,
+        },
+        &TradeAsset {
+            token: setup.corn_token.clone(),
+            amount: corn_wanted,
+        },
+        &0,
+    )
+}
+
+mod order_placement {
+    use super::*;
+
+    #[test]
+    fn test_order_rests_in_book_without_match() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+
+        let order_id = sell_corn(&setup, 100, 200, 0);
+
+        let order = setup.client.get_order(&order_id);
+        assert_eq!(order.remaining, 100);
+        assert_eq!(order.status, String::from_str(&env, "Open"));
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.corn_seller),
+            900
+        );
+
+        let book = setup.client.list_orders(&setup.corn, &setup.wheat, &0, &10);
+        assert_eq!(book.len(), 1);
+        assert_eq!(book.get(0).unwrap(), order_id);
+    }
+
+    #[test]
+    fn test_price_below_precision_rejected() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+
+        let result = setup.client.try_place_order(
+            &setup.corn_seller,
+            &setup.corn,
+            &setup.wheat,
+            &TradeAsset {
+                token: setup.corn_token.clone(),
+                amount: PRICE_SCALE * 10,
+            },
+            &TradeAsset {
+                token: setup.wheat_token.clone(),
+                amount: 1,
+            },
+            &0,
+        );
+        assert_error_contains(result, Ok(TradeError::InvalidPrice));
+    }
+
+    #[test]
+    fn test_past_expiry_rejected() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let setup = setup_book(&env);
+
+        let result = setup.client.try_place_order(
+            &setup.corn_seller,
+            &setup.corn,
+            &setup.wheat,
+            &TradeAsset {
+                token: setup.corn_token.clone(),
+                amount: 100,
+            },
+            &TradeAsset {
+                token: setup.wheat_token.clone(),
+                amount: 200,
+            },
+            &500,
+        );
+        assert_error_contains(result, Ok(TradeError::TradeExpired));
+    }
+}
+
+mod order_matching {
+    use super::*;
+
+    #[test]
+    fn test_compatible_orders_fill_completely() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        // 100 corn at 2 wheat each; taker sells 200 wheat at 0.5 corn each
+        let maker_id = sell_corn(&setup, 100, 200, 0);
+        let taker_id = sell_wheat(&setup, 200, 100);
+
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.wheat_seller),
+            100
+        );
+        assert_eq!(
+            token_balance(&env, &setup.wheat_token, &setup.corn_seller),
+            200
+        );
+
+        let maker = setup.client.get_order(&maker_id);
+        let taker = setup.client.get_order(&taker_id);
+        assert_eq!(maker.status, String::from_str(&env, "Filled"));
+        assert_eq!(taker.status, String::from_str(&env, "Filled"));
+        assert_eq!(
+            setup
+                .client
+                .list_orders(&setup.corn, &setup.wheat, &0, &10)
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_partial_fill_leaves_remainder_resting() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let maker_id = sell_corn(&setup, 100, 200, 0);
+
+        // Only 60 wheat offered, buying 30 corn
+        sell_wheat(&setup, 60, 30);
+
+        let maker = setup.client.get_order(&maker_id);
+        assert_eq!(maker.remaining, 70);
+        assert_eq!(maker.status, String::from_str(&env, "PartiallyFilled"));
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.wheat_seller),
+            30
+        );
+        assert_eq!(
+            setup
+                .client
+                .list_orders(&setup.corn, &setup.wheat, &0, &10)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_incompatible_prices_do_not_match() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        // Corn seller wants 2 wheat per corn, wheat seller wants 1 corn per wheat
+        let maker_id = sell_corn(&setup, 100, 200, 0);
+        let taker_id = sell_wheat(&setup, 100, 100);
+
+        assert_eq!(setup.client.get_order(&maker_id).remaining, 100);
+        assert_eq!(setup.client.get_order(&taker_id).remaining, 100);
+        assert_eq!(
+            setup
+                .client
+                .list_orders(&setup.wheat, &setup.corn, &0, &10)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_best_price_filled_first() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let expensive = sell_corn(&setup, 50, 150, 0);
+        let cheap = sell_corn(&setup, 50, 100, 0);
+
+        // 100 wheat for at least 40 corn can only afford the cheaper corn
+        sell_wheat(&setup, 100, 40);
+
+        assert_eq!(setup.client.get_order(&cheap).remaining, 0);
+        assert_eq!(setup.client.get_order(&expensive).remaining, 50);
+    }
+
+    #[test]
+    fn test_expired_orders_skipped_and_refunded() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let maker_id = sell_corn(&setup, 100, 200, 100);
+
+        env.ledger().with_mut(|li| li.timestamp = 200);
+        let taker_id = sell_wheat(&setup, 200, 100);
+        assert_eq!(setup.client.get_order(&taker_id).remaining, 200);
+
+        // The expired maker is pruned and refunded while matching
+        let maker = setup.client.get_order(&maker_id);
+        assert_eq!(maker.status, String::from_str(&env, "Expired"));
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.corn_seller),
+            1_000
+        );
+        assert_eq!(
+            setup
+                .client
+                .list_orders(&setup.corn, &setup.wheat, &0, &10)
+                .len(),
+            0
+        );
+
+        let result = setup.client.try_expire_order(&maker_id);
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+
+    #[test]
+    fn test_best_price_found_beyond_scan_limit() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        for _ in 0..MAX_MATCH_SCAN + 5 {
+            sell_corn(&setup, 10, 30, 0);
+        }
+        let cheap = sell_corn(&setup, 50, 100, 0);
+
+        sell_wheat(&setup, 100, 40);
+
+        assert_eq!(setup.client.get_order(&cheap).remaining, 0);
+    }
+
+    #[test]
+    fn test_stale_makers_do_not_block_matching() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let mut stale = Vec::new(&env);
+        for _ in 0..MAX_MATCH_SCAN + 5 {
+            stale.push_back(sell_corn(&setup, 10, 10, 100));
+        }
+        let live = sell_corn(&setup, 50, 100, 0);
+
+        env.ledger().with_mut(|li| li.timestamp = 200);
+        sell_wheat(&setup, 100, 40);
+
+        assert_eq!(setup.client.get_order(&live).remaining, 0);
+        for order_id in stale.iter() {
+            let order = setup.client.get_order(&order_id);
+            assert_eq!(order.status, String::from_str(&env, "Expired"));
+        }
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.corn_seller),
+            950
+        );
+    }
+
+    #[test]
+    fn test_price_overflow_rejected() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+
+        let result = setup.client.try_place_order(
+            &setup.corn_seller,
+            &setup.corn,
+            &setup.wheat,
+            &TradeAsset {
+                token: setup.corn_token.clone(),
+                amount: 100,
+            },
+            &TradeAsset {
+                token: setup.wheat_token.clone(),
+                amount: i128::MAX / 2,
+            },
+            &0,
+        );
+        assert_error_contains(result, Ok(TradeError::AmountOverflow));
+    }
+
+    #[test]
+    fn test_expire_before_deadline_rejected() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let order_id = sell_corn(&setup, 100, 200, 100);
+
+        let result = setup.client.try_expire_order(&order_id);
+        assert_error_contains(result, Ok(TradeError::OrderNotExpired));
+    }
+
+    #[test]
+    fn test_cancel_order_refunds_remainder() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let order_id = sell_corn(&setup, 100, 200, 0);
+        sell_wheat(&setup, 60, 30);
+
+        setup.client.cancel_order(&order_id, &setup.corn_seller);
+
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.corn_seller),
+            970
+        );
+        let order = setup.client.get_order(&order_id);
+        assert_eq!(order.status, String::from_str(&env, "Cancelled"));
+    }
+
+    #[test]
+    fn test_same_price_filled_oldest_first() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let first = sell_corn(&setup, 50, 100, 0);
+        let second = sell_corn(&setup, 50, 100, 0);
+
+        sell_wheat(&setup, 100, 50);
+
+        assert_eq!(setup.client.get_order(&first).remaining, 0);
+        assert_eq!(setup.client.get_order(&second).remaining, 50);
+    }
+
+    #[test]
+    fn test_cancelled_order_unlinked_from_price_level() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        let first = sell_corn(&setup, 50, 100, 0);
+        let middle = sell_corn(&setup, 50, 100, 0);
+        let last = sell_corn(&setup, 50, 100, 0);
+
+        setup.client.cancel_order(&middle, &setup.corn_seller);
+        sell_wheat(&setup, 200, 100);
+
+        assert_eq!(setup.client.get_order(&first).remaining, 0);
+        assert_eq!(setup.client.get_order(&last).remaining, 0);
+        assert_eq!(
+            token_balance(&env, &setup.corn_token, &setup.corn_seller),
+            900
+        );
+        assert_eq!(
+            setup
+                .client
+                .list_orders(&setup.corn, &setup.wheat, &0, &10)
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_price_levels_bounded() {
+        let env = Env::default();
+        let setup = setup_book(&env);
+        for step in 0..MAX_PRICE_LEVELS as i128 {
+            sell_corn(&setup, 10, 10 + step, 0);
+        }
+
+        let result = setup.client.try_place_order(
+            &setup.corn_seller,
+            &setup.corn,
+            &setup.wheat,
+            &TradeAsset {
+                token: setup.corn_token.clone(),
+                amount: 10,
+            },
+            &TradeAsset {
+                token: setup.wheat_token.clone(),
+                amount: 10 + MAX_PRICE_LEVELS as i128,
+            },
+            &0,
+        );
+        assert_error_contains(result, Ok(TradeError::TooManyPriceLevels));
+
+        // Existing price levels still take new orders
+        sell_corn(&setup, 10, 10, 0);
+    }
+}
+
+mod offer_pagination {
+    use super::*;
+
+    #[test]
+    fn test_list_offers_by_product() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let corn = create_test_product(&env, "corn");
+        let wheat = create_test_product(&env, "wheat");
+        let rice = create_test_product(&env, "rice");
+
+        for _ in 0..3 {
+            client.create_trade_offer(&Address::generate(&env), &corn, &wheat);
+        }
+        client.create_trade_offer(&Address::generate(&env), &rice, &wheat);
+
+        assert_eq!(client.list_offers_by_product(&corn, &0, &10).len(), 3);
+        assert_eq!(client.list_offers_by_product(&rice, &0, &10).len(), 1);
+    }
+
+    #[test]
+    fn test_active_offers_paginate() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        setup_multiple_trade_offers(&env, &client, 5);
+
+        let first_page = client.list_active_offers(&0, &2);
+        let second_page = client.list_active_offers(&2, &2);
+        let last_page = client.list_active_offers(&4, &2);
+
+        assert_eq!(first_page.len(), 2);
+        assert_eq!(second_page.len(), 2);
+        assert_eq!(last_page.len(), 1);
+        assert!(!first_page.contains(second_page.get(0).unwrap()));
+    }
+
+    #[test]
+    fn test_removed_offer_leaves_index_consistent() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offers = setup_multiple_trade_offers(&env, &client, 3);
+
+        let (first_offer_id, first_coop, _, _) = offers.get(0).unwrap();
+        client.cancel_trade(&first_offer_id, &first_coop);
+
+        let active = client.list_active_offers(&0, &MAX_PAGE_SIZE);
+        assert_eq!(active.len(), 2);
+        assert!(!active.contains(&first_offer_id));
+    }
+}
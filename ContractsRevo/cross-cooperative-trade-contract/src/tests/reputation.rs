@@ -339,3 +339,151 @@ mod reputation_edge_cases {
         assert_eq!(trade_offer.status, String::from_str(&env, "Pending"));
     }
 }
+
+mod counterparty_ratings {
+    use super::*;
+    use soroban_sdk::testutils::Ledger;
+
+    fn completed_trade(
+        env: &Env,
+        client: &CrossCooperativeTradeContractClient,
+        offering: &Address,
+        accepting: &Address,
+    ) -> BytesN<32> {
+        let (_, agreement_id) = create_complete_trade_flow(
+            env,
+            client,
+            offering,
+            accepting,
+            &create_test_product(env, "corn"),
+            &create_test_product(env, "wheat"),
+        );
+        agreement_id
+    }
+
+    #[test]
+    fn test_rate_counterparty_sets_score() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+        let agreement_id = completed_trade(&env, &client, &offering, &accepting);
+
+        client.rate_counterparty(&agreement_id, &offering, &4);
+        client.rate_counterparty(&agreement_id, &accepting, &2);
+
+        let accepting_rep = client.get_reputation(&accepting);
+        assert_eq!(accepting_rep.ratings_received, 1);
+        assert_eq!(accepting_rep.rating, 4);
+        assert_eq!(client.get_reputation_score(&accepting), 400);
+        assert_eq!(client.get_reputation(&offering).rating, 2);
+    }
+
+    #[test]
+    fn test_rating_averages_across_trades() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+
+        let first = completed_trade(&env, &client, &offering, &accepting);
+        let second = completed_trade(&env, &client, &offering, &accepting);
+        client.rate_counterparty(&first, &accepting, &5);
+        client.rate_counterparty(&second, &accepting, &2);
+
+        assert_eq!(client.get_reputation_score(&offering), 350);
+        assert_eq!(client.get_reputation(&offering).rating, 4);
+    }
+
+    #[test]
+    fn test_older_ratings_count_less() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+
+        let old_trade = completed_trade(&env, &client, &offering, &accepting);
+        client.rate_counterparty(&old_trade, &accepting, &1);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += 12 * DECAY_PERIOD);
+        let new_trade = completed_trade(&env, &client, &offering, &accepting);
+        client.rate_counterparty(&new_trade, &accepting, &5);
+
+        // The year-old one-star rating weighs far less than the fresh five-star one
+        assert!(client.get_reputation_score(&offering) > 400);
+    }
+
+    #[test]
+    fn test_rate_twice_rejected() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+        let agreement_id = completed_trade(&env, &client, &offering, &accepting);
+
+        client.rate_counterparty(&agreement_id, &offering, &5);
+        let result = client.try_rate_counterparty(&agreement_id, &offering, &5);
+        assert_error_contains(result, Ok(TradeError::AlreadyRated));
+    }
+
+    #[test]
+    fn test_rate_before_completion_rejected() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+        let offer_id = client.create_trade_offer(
+            &offering,
+            &create_test_product(&env, "corn"),
+            &create_test_product(&env, "wheat"),
+        );
+        let agreement_id = client.accept_trade(&offer_id, &accepting);
+
+        let result = client.try_rate_counterparty(&agreement_id, &offering, &5);
+        assert_error_contains(result, Ok(TradeError::InvalidTradeStatus));
+    }
+
+    #[test]
+    fn test_outsider_and_invalid_ratings_rejected() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+        let agreement_id = completed_trade(&env, &client, &offering, &accepting);
+
+        let outsider = client.try_rate_counterparty(&agreement_id, &Address::generate(&env), &5);
+        assert_error_contains(outsider, Ok(TradeError::UnauthorizedAccess));
+
+        let out_of_range = client.try_rate_counterparty(&agreement_id, &offering, &6);
+        assert_error_contains(out_of_range, Ok(TradeError::InvalidRating));
+    }
+
+    #[test]
+    fn test_failed_trade_carries_penalty() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let offering = Address::generate(&env);
+        let accepting = Address::generate(&env);
+        let agreement_id = completed_trade(&env, &client, &offering, &accepting);
+        client.rate_counterparty(&agreement_id, &accepting, &5);
+
+        client.update_reputation(&offering, &false);
+
+        let reputation = client.get_reputation(&offering);
+        assert_eq!(reputation.failed_trades, 1);
+        assert_eq!(client.get_reputation_score(&offering), 200);
+        assert_eq!(reputation.rating, 2);
+    }
+
+    #[test]
+    fn test_update_reputation_requires_admin() {
+        let env = Env::default();
+        let (_, client) = setup_contract_with_admin(&env);
+        let cooperative = Address::generate(&env);
+
+        env.mock_auths(&[]);
+        let result = client.try_update_reputation(&cooperative, &true);
+        assert_is_error(result);
+    }
+}
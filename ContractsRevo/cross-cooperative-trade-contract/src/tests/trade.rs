@@ -103,7 +103,7 @@ mod trade_offer_creation {
 
         // Verify all offers are in active offers list
         let active_offers = client
-            .try_list_active_offers()
+            .try_list_active_offers(&0, &MAX_PAGE_SIZE)
             .unwrap()
             .expect("Should be able to list active offers");
 
@@ -138,7 +138,7 @@ mod trade_offer_creation {
 
         // Verify both offers exist
         let active_offers = client
-            .try_list_active_offers()
+            .try_list_active_offers(&0, &MAX_PAGE_SIZE)
             .unwrap()
             .expect("Should be able to list active offers");
         assert_eq!(active_offers.len(), 2);
@@ -521,7 +521,7 @@ mod trade_queries {
         let (_, client) = setup_contract_with_admin(&env);
 
         let active_offers = client
-            .try_list_active_offers()
+            .try_list_active_offers(&0, &MAX_PAGE_SIZE)
             .unwrap()
             .expect("Should be able to list active offers");
 
@@ -541,7 +541,7 @@ mod trade_queries {
         let offers = setup_multiple_trade_offers(&env, &client, 3);
 
         let active_offers = client
-            .try_list_active_offers()
+            .try_list_active_offers(&0, &MAX_PAGE_SIZE)
             .unwrap()
             .expect("Should be able to list active offers");
 
@@ -569,7 +569,7 @@ mod trade_queries {
             .expect("Accept trade should succeed");
 
         let active_offers = client
-            .try_list_active_offers()
+            .try_list_active_offers(&0, &MAX_PAGE_SIZE)
             .unwrap()
             .expect("Should be able to list active offers");
 
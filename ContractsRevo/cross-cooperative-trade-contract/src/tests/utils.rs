@@ -1,7 +1,11 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, String};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, BytesN, Env, String,
+};
 
 /// Test helper to create a new contract instance with admin
 pub fn setup_contract_with_admin(env: &Env) -> (Address, CrossCooperativeTradeContractClient) {
@@ -32,6 +36,22 @@ pub fn create_test_product(env: &Env, product_name: &str) -> BytesN<32> {
         .into()
 }
 
+/// Register a SEP-41 test token and mint a balance to each holder
+pub fn create_test_token(env: &Env, holders: &[&Address], amount: i128) -> Address {
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    let asset_client = StellarAssetClient::new(env, &token.address());
+    for holder in holders {
+        asset_client.mint(holder, &amount);
+    }
+    token.address()
+}
+
+/// Get the token balance held by an address
+pub fn token_balance(env: &Env, token: &Address, holder: &Address) -> i128 {
+    TokenClient::new(env, token).balance(holder)
+}
+
 /// Create multiple test cooperatives
 pub fn create_test_cooperatives(env: &Env, count: usize) -> Vec<Address> {
     let mut cooperatives = Vec::new(env);
@@ -1,6 +1,13 @@
-use crate::{utils::generate_id, DataKey, TradeError, TradeOffer};
+use crate::{
+    escrow,
+    utils::{generate_id, index_add, index_page, index_remove},
+    DataKey, OfferIndex, TradeAsset, TradeError, TradeOffer,
+};
 use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
+/// Time the cooperatives have to settle an accepted trade before either can unwind it (14 days)
+pub const SETTLEMENT_WINDOW: u64 = 14 * 24 * 60 * 60;
+
 pub fn create_trade_offer(
     env: Env,
     cooperative_id: Address,
@@ -10,21 +17,81 @@ pub fn create_trade_offer(
     // Verify the caller is the cooperative
     cooperative_id.require_auth();
 
+    store_trade_offer(
+        &env,
+        cooperative_id,
+        offered_product,
+        requested_product,
+        None,
+        None,
+    )
+}
+
+pub fn create_escrowed_trade_offer(
+    env: Env,
+    cooperative_id: Address,
+    offered_product: BytesN<32>,
+    requested_product: BytesN<32>,
+    offered_asset: TradeAsset,
+    requested_asset: TradeAsset,
+) -> Result<BytesN<32>, TradeError> {
+    // Verify the caller is the cooperative
+    cooperative_id.require_auth();
+
+    escrow::validate_asset(&offered_asset)?;
+    escrow::validate_asset(&requested_asset)?;
+
+    let offer_id = store_trade_offer(
+        &env,
+        cooperative_id.clone(),
+        offered_product,
+        requested_product,
+        Some(&offered_asset),
+        Some(&requested_asset),
+    )?;
+
+    // Lock the offered side until the trade completes or is cancelled
+    escrow::lock_asset(&env, &cooperative_id, &offered_asset);
+
+    Ok(offer_id)
+}
+
+fn store_trade_offer(
+    env: &Env,
+    cooperative_id: Address,
+    offered_product: BytesN<32>,
+    requested_product: BytesN<32>,
+    offered_asset: Option<&TradeAsset>,
+    requested_asset: Option<&TradeAsset>,
+) -> Result<BytesN<32>, TradeError> {
     // Basic validation: offered and requested products should be different
     if offered_product == requested_product {
         return Err(TradeError::InvalidQuantity);
     }
 
     // Generate unique offer ID
-    let offer_id = generate_id(&env);
+    let offer_id = generate_id(env);
+
+    // Index the offer for paginated discovery
+    index_add(env, &OfferIndex::Active, &offer_id);
+    index_add(
+        env,
+        &OfferIndex::Product(offered_product.clone()),
+        &offer_id,
+    );
 
-    // Create the simplified trade offer
     let trade_offer = TradeOffer {
         offer_id: offer_id.clone(),
-        cooperative_id: cooperative_id.clone(),
+        cooperative_id,
         offered_product,
         requested_product,
-        status: String::from_str(&env, "Pending"),
+        offered_token: offered_asset.map(|asset| asset.token.clone()),
+        offered_amount: offered_asset.map_or(0, |asset| asset.amount),
+        requested_token: requested_asset.map(|asset| asset.token.clone()),
+        requested_amount: requested_asset.map_or(0, |asset| asset.amount),
+        accepting_cooperative: None,
+        accepted_at: 0,
+        status: String::from_str(env, "Pending"),
     };
 
     // Store the trade offer
@@ -32,17 +99,6 @@ pub fn create_trade_offer(
         .persistent()
         .set(&DataKey::TradeOffer(offer_id.clone()), &trade_offer);
 
-    // Add to active offers list
-    let mut active_offers: Vec<BytesN<32>> = env
-        .storage()
-        .instance()
-        .get(&DataKey::ActiveOffers)
-        .unwrap_or(Vec::new(&env));
-    active_offers.push_back(offer_id.clone());
-    env.storage()
-        .instance()
-        .set(&DataKey::ActiveOffers, &active_offers);
-
     Ok(offer_id)
 }
 
@@ -70,8 +126,15 @@ pub fn accept_trade(
         return Err(TradeError::InvalidTradeStatus);
     }
 
+    // Lock the requested side so neither cooperative can walk away
+    if let Some(requested_asset) = escrow::requested_asset(&trade_offer) {
+        escrow::lock_asset(&env, &accepting_cooperative, &requested_asset);
+    }
+
     // Update trade offer status
     trade_offer.status = String::from_str(&env, "Accepted");
+    trade_offer.accepting_cooperative = Some(accepting_cooperative.clone());
+    trade_offer.accepted_at = env.ledger().timestamp();
     env.storage()
         .persistent()
         .set(&DataKey::TradeOffer(offer_id.clone()), &trade_offer);
@@ -80,26 +143,11 @@ pub fn accept_trade(
     let agreement_id = crate::barter::create_barter_agreement(
         env.clone(),
         offer_id.clone(),
-        trade_offer.cooperative_id,
+        trade_offer.cooperative_id.clone(),
         accepting_cooperative,
     );
 
-    // Remove from active offers
-    let active_offers: Vec<BytesN<32>> = env
-        .storage()
-        .instance()
-        .get(&DataKey::ActiveOffers)
-        .unwrap_or(Vec::new(&env));
-
-    let mut new_active_offers = Vec::new(&env);
-    for i in 0..active_offers.len() {
-        if active_offers.get(i).unwrap() != offer_id.clone() {
-            new_active_offers.push_back(active_offers.get(i).unwrap());
-        }
-    }
-    env.storage()
-        .instance()
-        .set(&DataKey::ActiveOffers, &new_active_offers);
+    remove_from_active_offers(&env, &trade_offer);
 
     Ok(agreement_id)
 }
@@ -108,34 +156,126 @@ pub fn complete_trade(env: Env, offer_id: BytesN<32>, caller: Address) -> Result
     // Verify caller authorization
     caller.require_auth();
 
-    // Get the trade offer
+    let (mut trade_offer, accepting_cooperative) = get_accepted_trade(&env, &offer_id, &caller)?;
+
+    // Swap both escrowed sides atomically
+    if let Some(offered_asset) = escrow::offered_asset(&trade_offer) {
+        escrow::release_asset(&env, &accepting_cooperative, &offered_asset);
+    }
+    if let Some(requested_asset) = escrow::requested_asset(&trade_offer) {
+        escrow::release_asset(&env, &trade_offer.cooperative_id, &requested_asset);
+    }
+
+    // Update trade offer status
+    trade_offer.status = String::from_str(&env, "Completed");
+    env.storage()
+        .persistent()
+        .set(&DataKey::TradeOffer(offer_id.clone()), &trade_offer);
+    crate::barter::set_offer_agreement_status(&env, &offer_id, "Completed");
+
+    // Update reputations for both cooperatives
+    crate::reputation::update_reputation_after_trade(&env, &trade_offer.cooperative_id, true)?;
+    crate::reputation::update_reputation_after_trade(&env, &accepting_cooperative, true)?;
+
+    Ok(())
+}
+
+pub fn expire_trade(env: Env, offer_id: BytesN<32>, caller: Address) -> Result<(), TradeError> {
+    // Verify caller authorization
+    caller.require_auth();
+
+    let (mut trade_offer, accepting_cooperative) = get_accepted_trade(&env, &offer_id, &caller)?;
+
+    if env.ledger().timestamp() < trade_offer.accepted_at + SETTLEMENT_WINDOW {
+        return Err(TradeError::TradeNotExpired);
+    }
+
+    // Return each escrowed side to the cooperative that locked it
+    if let Some(offered_asset) = escrow::offered_asset(&trade_offer) {
+        escrow::release_asset(&env, &trade_offer.cooperative_id, &offered_asset);
+    }
+    if let Some(requested_asset) = escrow::requested_asset(&trade_offer) {
+        escrow::release_asset(&env, &accepting_cooperative, &requested_asset);
+    }
+
+    trade_offer.status = String::from_str(&env, "Expired");
+    env.storage()
+        .persistent()
+        .set(&DataKey::TradeOffer(offer_id.clone()), &trade_offer);
+    crate::barter::set_offer_agreement_status(&env, &offer_id, "Expired");
+
+    Ok(())
+}
+
+/// Load an accepted trade that `caller` is a party to, with its accepting cooperative
+fn get_accepted_trade(
+    env: &Env,
+    offer_id: &BytesN<32>,
+    caller: &Address,
+) -> Result<(TradeOffer, Address), TradeError> {
+    let trade_offer: TradeOffer = env
+        .storage()
+        .persistent()
+        .get(&DataKey::TradeOffer(offer_id.clone()))
+        .ok_or(TradeError::TradeOfferNotFound)?;
+
+    // Either side of the trade may settle or unwind it
+    let accepting_cooperative = trade_offer.accepting_cooperative.clone();
+    if trade_offer.cooperative_id != *caller && accepting_cooperative.as_ref() != Some(caller) {
+        return Err(TradeError::UnauthorizedAccess);
+    }
+
+    match accepting_cooperative {
+        Some(accepting_cooperative) if trade_offer.status == String::from_str(env, "Accepted") => {
+            Ok((trade_offer, accepting_cooperative))
+        }
+        _ => Err(TradeError::InvalidTradeStatus),
+    }
+}
+
+pub fn cancel_trade(env: Env, offer_id: BytesN<32>, caller: Address) -> Result<(), TradeError> {
+    // Verify caller authorization
+    caller.require_auth();
+
     let mut trade_offer: TradeOffer = env
         .storage()
         .persistent()
         .get(&DataKey::TradeOffer(offer_id.clone()))
         .ok_or(TradeError::TradeOfferNotFound)?;
 
-    // Validate that caller is involved in the trade
+    // Only the offering cooperative is party to a pending offer
     if trade_offer.cooperative_id != caller {
         return Err(TradeError::UnauthorizedAccess);
     }
 
-    if trade_offer.status != String::from_str(&env, "Accepted") {
+    if trade_offer.status != String::from_str(&env, "Pending") {
         return Err(TradeError::InvalidTradeStatus);
     }
 
-    // Update trade offer status
-    trade_offer.status = String::from_str(&env, "Completed");
+    // Refund the escrowed offered side
+    if let Some(offered_asset) = escrow::offered_asset(&trade_offer) {
+        escrow::release_asset(&env, &trade_offer.cooperative_id, &offered_asset);
+    }
+
+    trade_offer.status = String::from_str(&env, "Cancelled");
     env.storage()
         .persistent()
         .set(&DataKey::TradeOffer(offer_id.clone()), &trade_offer);
 
-    // Update reputations for both cooperatives
-    crate::reputation::update_reputation_after_trade(&env, &trade_offer.cooperative_id, true)?;
+    remove_from_active_offers(&env, &trade_offer);
 
     Ok(())
 }
 
+fn remove_from_active_offers(env: &Env, trade_offer: &TradeOffer) {
+    index_remove(env, &OfferIndex::Active, &trade_offer.offer_id);
+    index_remove(
+        env,
+        &OfferIndex::Product(trade_offer.offered_product.clone()),
+        &trade_offer.offer_id,
+    );
+}
+
 pub fn get_trade_details(env: Env, offer_id: BytesN<32>) -> Result<TradeOffer, TradeError> {
     env.storage()
         .persistent()
@@ -143,12 +283,20 @@ pub fn get_trade_details(env: Env, offer_id: BytesN<32>) -> Result<TradeOffer, T
         .ok_or(TradeError::TradeOfferNotFound)
 }
 
-pub fn list_active_offers(env: Env) -> Result<Vec<BytesN<32>>, TradeError> {
-    let active_offers = env
-        .storage()
-        .instance()
-        .get(&DataKey::ActiveOffers)
-        .unwrap_or(Vec::new(&env));
+pub fn list_active_offers(env: Env, start: u32, limit: u32) -> Result<Vec<BytesN<32>>, TradeError> {
+    Ok(index_page(&env, &OfferIndex::Active, start, limit))
+}
 
-    Ok(active_offers)
+pub fn list_offers_by_product(
+    env: Env,
+    product: BytesN<32>,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<BytesN<32>>, TradeError> {
+    Ok(index_page(
+        &env,
+        &OfferIndex::Product(product),
+        start,
+        limit,
+    ))
 }
@@ -1,5 +1,8 @@
-use crate::DataKey;
-use soroban_sdk::{BytesN, Env};
+use crate::{DataKey, OfferIndex};
+use soroban_sdk::{BytesN, Env, Vec};
+
+/// Largest page returned by paginated queries
+pub const MAX_PAGE_SIZE: u32 = 100;
 
 /// Generate a unique ID for trade offers and barter agreements
 pub fn generate_id(env: &Env) -> BytesN<32> {
@@ -31,3 +34,56 @@ pub fn generate_id(env: &Env) -> BytesN<32> {
 
     BytesN::from_array(env, &id_bytes)
 }
+
+/// Append an ID to a paginated index
+pub fn index_add(env: &Env, index: &OfferIndex, id: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    let len: u32 = storage.get(&DataKey::IndexLen(index.clone())).unwrap_or(0);
+
+    storage.set(&DataKey::IndexEntry(index.clone(), len), id);
+    storage.set(&DataKey::IndexPosition(index.clone(), id.clone()), &len);
+    storage.set(&DataKey::IndexLen(index.clone()), &(len + 1));
+}
+
+/// Remove an ID from a paginated index by swapping in the last entry
+pub fn index_remove(env: &Env, index: &OfferIndex, id: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    let position_key = DataKey::IndexPosition(index.clone(), id.clone());
+    let position: u32 = match storage.get(&position_key) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let last = storage
+        .get::<_, u32>(&DataKey::IndexLen(index.clone()))
+        .unwrap_or(1)
+        - 1;
+    if position != last {
+        let last_id: BytesN<32> = storage
+            .get(&DataKey::IndexEntry(index.clone(), last))
+            .unwrap();
+        storage.set(&DataKey::IndexEntry(index.clone(), position), &last_id);
+        storage.set(&DataKey::IndexPosition(index.clone(), last_id), &position);
+    }
+
+    storage.remove(&DataKey::IndexEntry(index.clone(), last));
+    storage.remove(&position_key);
+    storage.set(&DataKey::IndexLen(index.clone()), &last);
+}
+
+/// Read one page of a paginated index
+pub fn index_page(env: &Env, index: &OfferIndex, start: u32, limit: u32) -> Vec<BytesN<32>> {
+    let storage = env.storage().persistent();
+    let len: u32 = storage.get(&DataKey::IndexLen(index.clone())).unwrap_or(0);
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+
+    let mut page = Vec::new(env);
+    for position in start..end {
+        page.push_back(
+            storage
+                .get(&DataKey::IndexEntry(index.clone(), position))
+                .unwrap(),
+        );
+    }
+    page
+}
@@ -1,4 +1,4 @@
-use crate::{CSAMembership, Error};
+use crate::{CSAMembership, DataKey, Error};
 use soroban_sdk::{Address, BytesN, Env, Symbol};
 
 pub fn cancel_membership(env: Env, token_id: BytesN<32>, member: Address) -> Result<(), Error> {
@@ -9,7 +9,7 @@ pub fn cancel_membership(env: Env, token_id: BytesN<32>, member: Address) -> Res
     let membership: CSAMembership = env
         .storage()
         .persistent()
-        .get(&token_id)
+        .get(&DataKey::Membership(token_id.clone()))
         .ok_or(Error::NotFound)?;
     env.logs().add("After getting membership", &[]);
 
@@ -18,13 +18,24 @@ pub fn cancel_membership(env: Env, token_id: BytesN<32>, member: Address) -> Res
     }
     env.logs().add("After member check", &[]);
 
-    env.storage().persistent().remove(&token_id);
+    let refund = crate::payment::settle_cancellation(&env, &token_id, &membership)?;
+    env.logs().add("After settling payments", &[]);
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Membership(token_id.clone()));
+    crate::inventory::release_share(
+        &env,
+        &membership.farm_id,
+        &membership.season,
+        membership.share_size,
+    );
     env.logs().add("After removing membership", &[]);
 
     // Evento modificado con Symbol
     env.events().publish(
         (Symbol::new(&env, "membership_cancelled"), member.clone()),
-        token_id,
+        (token_id, refund),
     );
     env.logs().add("After event publish", &[]);
 
@@ -1,24 +1,32 @@
-use crate::{CSAMembership, Error, ShareSize};
-use soroban_sdk::{Address, BytesN, Env, String, Symbol};
+use crate::{CSAMembership, DataKey, EnrollmentRequest, Error, PaymentPlan};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env, Symbol};
 
 pub fn enroll_membership(
     env: Env,
-    farm_id: BytesN<32>,
-    season: String,
-    share_size: ShareSize,
-    pickup_location: String,
-    start_date: u64,
-    end_date: u64,
-    member: Address,
+    request: EnrollmentRequest,
+    plan: PaymentPlan,
 ) -> Result<BytesN<32>, Error> {
     env.logs().add("Starting enroll_membership", &[]);
+    let EnrollmentRequest {
+        farm_id,
+        season,
+        share_size,
+        pickup_location,
+        start_date,
+        end_date,
+        member,
+    } = request;
     member.require_auth();
     env.logs().add("After require_auth", &[]);
 
     crate::validate::validate_season(&env, farm_id.clone(), season.clone(), start_date, end_date)?;
     env.logs().add("After validate_season", &[]);
 
-    let token_id = BytesN::from_array(&env, &[0; 32]);
+    crate::inventory::reserve_share(&env, &farm_id, &season, share_size, &member)?;
+    env.logs().add("After reserve_share", &[]);
+
+    let token_id = generate_token_id(&env, &member);
     env.logs().add("After generating token_id", &[]);
 
     let membership = CSAMembership {
@@ -30,7 +38,12 @@ pub fn enroll_membership(
         end_date,
         member: member.clone(),
     };
-    env.storage().persistent().set(&token_id, &membership);
+    crate::payment::open_payment(&env, &token_id, &membership, plan)?;
+    env.logs().add("After open_payment", &[]);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Membership(token_id.clone()), &membership);
     env.logs().add("After storage set", &[]);
 
     env.events().publish(
@@ -41,3 +54,23 @@ pub fn enroll_membership(
 
     Ok(token_id)
 }
+
+/// Derive a membership ID that is never reused, even for identical enrollments
+fn generate_token_id(env: &Env, member: &Address) -> BytesN<32> {
+    let counter: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MembershipCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::MembershipCounter, &counter);
+
+    let mut data = Bytes::new(env);
+    data.append(&env.current_contract_address().to_xdr(env));
+    data.append(&Bytes::from_array(env, &counter.to_be_bytes()));
+    data.append(&member.clone().to_xdr(env));
+
+    env.crypto().sha256(&data).into()
+}
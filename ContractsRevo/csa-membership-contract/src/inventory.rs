@@ -0,0 +1,242 @@
+use crate::{DataKey, Error, ShareInventory, ShareSize, MAX_WAITLIST_LEN};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+pub fn register_farm(env: Env, farm_id: BytesN<32>, operator: Address) -> Result<(), Error> {
+    operator.require_auth();
+
+    if farm_id == BytesN::from_array(&env, &[0; 32]) {
+        return Err(Error::InvalidFarm);
+    }
+
+    let key = DataKey::FarmOperator(farm_id.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(Error::FarmAlreadyRegistered);
+    }
+    env.storage().persistent().set(&key, &operator);
+
+    env.events()
+        .publish((Symbol::new(&env, "farm_registered"), operator), farm_id);
+
+    Ok(())
+}
+
+pub fn get_farm_operator(env: &Env, farm_id: &BytesN<32>) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FarmOperator(farm_id.clone()))
+}
+
+/// Check that `operator` runs the farm and has signed the call
+pub fn require_farm_operator(
+    env: &Env,
+    farm_id: &BytesN<32>,
+    operator: &Address,
+) -> Result<(), Error> {
+    operator.require_auth();
+
+    let registered = get_farm_operator(env, farm_id).ok_or(Error::FarmNotRegistered)?;
+    if registered != *operator {
+        return Err(Error::NotAuthorized);
+    }
+
+    Ok(())
+}
+
+pub fn set_share_capacity(
+    env: Env,
+    farm_id: BytesN<32>,
+    season: String,
+    share_size: ShareSize,
+    capacity: u32,
+    operator: Address,
+) -> Result<(), Error> {
+    require_farm_operator(&env, &farm_id, &operator)?;
+
+    if season.is_empty() {
+        return Err(Error::InvalidSeason);
+    }
+
+    // A farm cannot shrink below the shares it has already sold
+    if capacity < enrolled_shares(&env, &farm_id, &season, share_size) {
+        return Err(Error::InvalidCapacity);
+    }
+
+    env.storage().persistent().set(
+        &DataKey::ShareCapacity(farm_id.clone(), season.clone(), share_size),
+        &capacity,
+    );
+
+    env.events().publish(
+        (Symbol::new(&env, "share_capacity_set"), farm_id),
+        (season, share_size, capacity),
+    );
+
+    Ok(())
+}
+
+pub fn get_share_inventory(
+    env: &Env,
+    farm_id: &BytesN<32>,
+    season: &String,
+    share_size: ShareSize,
+) -> ShareInventory {
+    ShareInventory {
+        capacity: env.storage().persistent().get(&DataKey::ShareCapacity(
+            farm_id.clone(),
+            season.clone(),
+            share_size,
+        )),
+        enrolled: enrolled_shares(env, farm_id, season, share_size),
+        waitlisted: get_waitlist(env, farm_id, season, share_size).len(),
+    }
+}
+
+pub fn get_waitlist(
+    env: &Env,
+    farm_id: &BytesN<32>,
+    season: &String,
+    share_size: ShareSize,
+) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Waitlist(
+            farm_id.clone(),
+            season.clone(),
+            share_size,
+        ))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Join the queue for a sold-out share. Returns the member's position (0 is next).
+pub fn join_waitlist(
+    env: Env,
+    farm_id: BytesN<32>,
+    season: String,
+    share_size: ShareSize,
+    member: Address,
+) -> Result<u32, Error> {
+    member.require_auth();
+
+    let inventory = get_share_inventory(&env, &farm_id, &season, share_size);
+    let mut waitlist = get_waitlist(&env, &farm_id, &season, share_size);
+    if waitlist.contains(&member) {
+        return Err(Error::AlreadyWaitlisted);
+    }
+    if open_slots(&inventory) > waitlist.len() {
+        return Err(Error::SharesAvailable);
+    }
+    if waitlist.len() >= MAX_WAITLIST_LEN {
+        return Err(Error::WaitlistFull);
+    }
+
+    waitlist.push_back(member.clone());
+    env.storage().persistent().set(
+        &DataKey::Waitlist(farm_id.clone(), season.clone(), share_size),
+        &waitlist,
+    );
+
+    env.events().publish(
+        (Symbol::new(&env, "waitlist_joined"), member),
+        (farm_id, season, share_size),
+    );
+
+    Ok(waitlist.len() - 1)
+}
+
+pub fn leave_waitlist(
+    env: Env,
+    farm_id: BytesN<32>,
+    season: String,
+    share_size: ShareSize,
+    member: Address,
+) -> Result<(), Error> {
+    member.require_auth();
+
+    let mut waitlist = get_waitlist(&env, &farm_id, &season, share_size);
+    let index = waitlist
+        .first_index_of(&member)
+        .ok_or(Error::NotWaitlisted)?;
+    waitlist.remove(index);
+    env.storage().persistent().set(
+        &DataKey::Waitlist(farm_id.clone(), season.clone(), share_size),
+        &waitlist,
+    );
+
+    env.events().publish(
+        (Symbol::new(&env, "waitlist_left"), member),
+        (farm_id, season, share_size),
+    );
+
+    Ok(())
+}
+
+/// Take one share from the season's inventory for `member`.
+/// Open slots go to waitlisted members first, in the order they joined.
+pub fn reserve_share(
+    env: &Env,
+    farm_id: &BytesN<32>,
+    season: &String,
+    share_size: ShareSize,
+    member: &Address,
+) -> Result<(), Error> {
+    let inventory = get_share_inventory(env, farm_id, season, share_size);
+    let mut waitlist = get_waitlist(env, farm_id, season, share_size);
+
+    if inventory.capacity.is_some() {
+        let slots = open_slots(&inventory);
+        match waitlist.first_index_of(member) {
+            Some(position) if position < slots => {
+                waitlist.remove(position);
+                env.storage().persistent().set(
+                    &DataKey::Waitlist(farm_id.clone(), season.clone(), share_size),
+                    &waitlist,
+                );
+            }
+            Some(_) => return Err(Error::SoldOut),
+            None if slots <= waitlist.len() => return Err(Error::SoldOut),
+            None => {}
+        }
+    }
+
+    env.storage().persistent().set(
+        &DataKey::EnrolledShares(farm_id.clone(), season.clone(), share_size),
+        &(inventory.enrolled + 1),
+    );
+
+    Ok(())
+}
+
+/// Return a cancelled member's share to the season's inventory
+pub fn release_share(env: &Env, farm_id: &BytesN<32>, season: &String, share_size: ShareSize) {
+    let enrolled = enrolled_shares(env, farm_id, season, share_size);
+    env.storage().persistent().set(
+        &DataKey::EnrolledShares(farm_id.clone(), season.clone(), share_size),
+        &enrolled.saturating_sub(1),
+    );
+
+    if !get_waitlist(env, farm_id, season, share_size).is_empty() {
+        env.events().publish(
+            (Symbol::new(env, "waitlist_slot_opened"), farm_id.clone()),
+            (season.clone(), share_size),
+        );
+    }
+}
+
+fn enrolled_shares(env: &Env, farm_id: &BytesN<32>, season: &String, share_size: ShareSize) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EnrolledShares(
+            farm_id.clone(),
+            season.clone(),
+            share_size,
+        ))
+        .unwrap_or(0)
+}
+
+/// Unsold shares, or unlimited when the farm has not set a capacity
+fn open_slots(inventory: &ShareInventory) -> u32 {
+    match inventory.capacity {
+        Some(capacity) => capacity.saturating_sub(inventory.enrolled),
+        None => u32::MAX,
+    }
+}
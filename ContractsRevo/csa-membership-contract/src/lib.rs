@@ -1,8 +1,32 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String,
+    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Vec,
 };
 
+/// Maximum number of members waiting for a single farm/season/share size
+pub const MAX_WAITLIST_LEN: u32 = 100;
+
+/// Maximum number of pickups a farm can schedule in one season
+pub const MAX_PICKUPS_PER_SEASON: u32 = 60;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Membership(BytesN<32>),
+    MembershipCounter,
+    FarmOperator(BytesN<32>),
+    ShareCapacity(BytesN<32>, String, ShareSize),
+    EnrolledShares(BytesN<32>, String, ShareSize),
+    Waitlist(BytesN<32>, String, ShareSize),
+    SharePrice(BytesN<32>, String, ShareSize),
+    Payment(BytesN<32>),
+    PickupSchedule(BytesN<32>, String),
+    SiteOperator(BytesN<32>, Address),
+    Distribution(BytesN<32>),
+    TransferPolicy(BytesN<32>),
+    PendingTransfer(BytesN<32>),
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct CSAMembership {
@@ -23,6 +47,102 @@ pub enum ShareSize {
     Large,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EnrollmentRequest {
+    pub farm_id: BytesN<32>,
+    pub season: String,
+    pub share_size: ShareSize,
+    pub pickup_location: String,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub member: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaymentPlan {
+    Upfront,
+    Weekly,
+    Monthly,
+}
+
+/// Season price for a share size, in a SEP-41 token
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareOffer {
+    pub token: Address,
+    pub price: i128,
+}
+
+/// Escrowed payments for one membership. `released` has been paid out to the farm;
+/// `next_due` is 0 once every installment is paid.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentRecord {
+    pub plan: PaymentPlan,
+    pub token: Address,
+    pub total_price: i128,
+    pub paid: i128,
+    pub released: i128,
+    pub installments_total: u32,
+    pub installments_paid: u32,
+    pub next_due: u64,
+}
+
+/// Pickup dates for a farm's season, indexed by week, and how many a member may skip
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PickupSchedule {
+    pub pickup_dates: Vec<u64>,
+    pub max_skips: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PickupStatus {
+    Collected,
+    Skipped,
+    Donated(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistributionRecord {
+    pub week: u32,
+    pub pickup_date: u64,
+    pub status: PickupStatus,
+    pub confirmed_by: Option<Address>,
+    pub recorded_at: u64,
+}
+
+/// How a farm lets members hand their share to someone else.
+/// The fee is paid by the recipient to the farm operator.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferPolicy {
+    pub requires_approval: bool,
+    pub fee: i128,
+    pub fee_token: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingTransfer {
+    pub from: Address,
+    pub to: Address,
+    pub approved: bool,
+    pub offered_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareInventory {
+    pub capacity: Option<u32>,
+    pub enrolled: u32,
+    pub waitlisted: u32,
+}
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum Error {
@@ -32,6 +152,31 @@ pub enum Error {
     InvalidFarm = 4,
     InvalidSeason = 5,
     AlreadyCancelled = 6,
+    SoldOut = 7,
+    FarmAlreadyRegistered = 8,
+    FarmNotRegistered = 9,
+    InvalidCapacity = 10,
+    AlreadyWaitlisted = 11,
+    NotWaitlisted = 12,
+    WaitlistFull = 13,
+    SharesAvailable = 14,
+    InvalidPrice = 15,
+    PriceNotSet = 16,
+    NoPaymentPlan = 17,
+    FullyPaid = 18,
+    ScheduleNotSet = 19,
+    InvalidSchedule = 20,
+    InvalidWeek = 21,
+    PickupNotOpen = 22,
+    AlreadyRecorded = 23,
+    SkipLimitReached = 24,
+    SkipDeadlinePassed = 25,
+    MembershipSuspended = 26,
+    TransferNotFound = 27,
+    TransferPending = 28,
+    TransferNotApproved = 29,
+    SeasonEnded = 30,
+    InvalidTransfer = 31,
 }
 
 #[contract]
@@ -49,8 +194,7 @@ impl CSAMembershipContract {
         end_date: u64,
         member: Address,
     ) -> Result<BytesN<32>, Error> {
-        enroll::enroll_membership(
-            env,
+        let request = EnrollmentRequest {
             farm_id,
             season,
             share_size,
@@ -58,7 +202,16 @@ impl CSAMembershipContract {
             start_date,
             end_date,
             member,
-        )
+        };
+        enroll::enroll_membership(env, request, PaymentPlan::Upfront)
+    }
+
+    pub fn enroll_with_plan(
+        env: Env,
+        request: EnrollmentRequest,
+        plan: PaymentPlan,
+    ) -> Result<BytesN<32>, Error> {
+        enroll::enroll_membership(env, request, plan)
     }
 
     pub fn update_pickup_location(
@@ -77,6 +230,202 @@ impl CSAMembershipContract {
     pub fn cancel_membership(env: Env, token_id: BytesN<32>, member: Address) -> Result<(), Error> {
         crate::cancel::cancel_membership(env, token_id, member)
     }
+
+    pub fn register_farm(env: Env, farm_id: BytesN<32>, operator: Address) -> Result<(), Error> {
+        crate::inventory::register_farm(env, farm_id, operator)
+    }
+
+    pub fn get_farm_operator(env: Env, farm_id: BytesN<32>) -> Option<Address> {
+        crate::inventory::get_farm_operator(&env, &farm_id)
+    }
+
+    pub fn set_share_capacity(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+        capacity: u32,
+        operator: Address,
+    ) -> Result<(), Error> {
+        crate::inventory::set_share_capacity(env, farm_id, season, share_size, capacity, operator)
+    }
+
+    pub fn get_share_inventory(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+    ) -> ShareInventory {
+        crate::inventory::get_share_inventory(&env, &farm_id, &season, share_size)
+    }
+
+    pub fn join_waitlist(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+        member: Address,
+    ) -> Result<u32, Error> {
+        crate::inventory::join_waitlist(env, farm_id, season, share_size, member)
+    }
+
+    pub fn leave_waitlist(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+        member: Address,
+    ) -> Result<(), Error> {
+        crate::inventory::leave_waitlist(env, farm_id, season, share_size, member)
+    }
+
+    pub fn set_share_price(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+        offer: ShareOffer,
+        operator: Address,
+    ) -> Result<(), Error> {
+        crate::payment::set_share_price(env, farm_id, season, share_size, offer, operator)
+    }
+
+    pub fn get_share_price(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+    ) -> Option<ShareOffer> {
+        crate::payment::get_share_price(&env, &farm_id, &season, share_size)
+    }
+
+    pub fn pay_installment(env: Env, token_id: BytesN<32>, member: Address) -> Result<i128, Error> {
+        crate::payment::pay_installment(env, token_id, member)
+    }
+
+    pub fn get_payment(env: Env, token_id: BytesN<32>) -> Option<PaymentRecord> {
+        crate::payment::get_payment(&env, &token_id)
+    }
+
+    pub fn is_suspended(env: Env, token_id: BytesN<32>) -> bool {
+        crate::payment::is_suspended(&env, &token_id)
+    }
+
+    pub fn collect_payments(
+        env: Env,
+        farm_id: BytesN<32>,
+        operator: Address,
+        token_ids: Vec<BytesN<32>>,
+    ) -> Result<i128, Error> {
+        crate::payment::collect_payments(env, farm_id, operator, token_ids)
+    }
+
+    pub fn set_pickup_schedule(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        schedule: PickupSchedule,
+        operator: Address,
+    ) -> Result<(), Error> {
+        crate::pickup::set_pickup_schedule(env, farm_id, season, schedule, operator)
+    }
+
+    pub fn get_pickup_schedule(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+    ) -> Option<PickupSchedule> {
+        crate::pickup::get_pickup_schedule(&env, &farm_id, &season)
+    }
+
+    pub fn set_site_operator(
+        env: Env,
+        farm_id: BytesN<32>,
+        site_operator: Address,
+        allowed: bool,
+        operator: Address,
+    ) -> Result<(), Error> {
+        crate::pickup::set_site_operator(env, farm_id, site_operator, allowed, operator)
+    }
+
+    pub fn confirm_pickup(
+        env: Env,
+        token_id: BytesN<32>,
+        week: u32,
+        site_operator: Address,
+    ) -> Result<(), Error> {
+        crate::pickup::confirm_pickup(env, token_id, week, site_operator)
+    }
+
+    pub fn skip_pickup(
+        env: Env,
+        token_id: BytesN<32>,
+        week: u32,
+        donate_to: Option<Address>,
+        member: Address,
+    ) -> Result<(), Error> {
+        crate::pickup::skip_pickup(env, token_id, week, donate_to, member)
+    }
+
+    pub fn get_distribution_history(env: Env, token_id: BytesN<32>) -> Vec<DistributionRecord> {
+        crate::pickup::get_distribution_history(&env, &token_id)
+    }
+
+    pub fn set_transfer_policy(
+        env: Env,
+        farm_id: BytesN<32>,
+        policy: TransferPolicy,
+        operator: Address,
+    ) -> Result<(), Error> {
+        crate::transfer::set_transfer_policy(env, farm_id, policy, operator)
+    }
+
+    pub fn get_transfer_policy(env: Env, farm_id: BytesN<32>) -> TransferPolicy {
+        crate::transfer::get_transfer_policy(&env, &farm_id)
+    }
+
+    pub fn offer_transfer(
+        env: Env,
+        token_id: BytesN<32>,
+        member: Address,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        crate::transfer::offer_transfer(env, token_id, member, recipient)
+    }
+
+    pub fn review_transfer(
+        env: Env,
+        token_id: BytesN<32>,
+        approve: bool,
+        operator: Address,
+    ) -> Result<(), Error> {
+        crate::transfer::review_transfer(env, token_id, approve, operator)
+    }
+
+    pub fn cancel_transfer(env: Env, token_id: BytesN<32>, member: Address) -> Result<(), Error> {
+        crate::transfer::cancel_transfer(env, token_id, member)
+    }
+
+    pub fn accept_transfer(
+        env: Env,
+        token_id: BytesN<32>,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        crate::transfer::accept_transfer(env, token_id, recipient)
+    }
+
+    pub fn get_pending_transfer(env: Env, token_id: BytesN<32>) -> Option<PendingTransfer> {
+        crate::transfer::get_pending_transfer(&env, &token_id)
+    }
+
+    pub fn get_waitlist(
+        env: Env,
+        farm_id: BytesN<32>,
+        season: String,
+        share_size: ShareSize,
+    ) -> Vec<Address> {
+        crate::inventory::get_waitlist(&env, &farm_id, &season, share_size)
+    }
 }
 
 #[contracterror]
@@ -89,8 +438,12 @@ pub enum ContractError {
 pub mod cancel;
 pub mod enroll;
 pub mod errors;
+pub mod inventory;
 pub mod manage;
 pub mod metadata;
+pub mod payment;
+pub mod pickup;
+pub mod transfer;
 pub mod types;
 pub mod validate;
 
@@ -1,4 +1,4 @@
-use crate::{CSAMembership, Error};
+use crate::{CSAMembership, DataKey, Error};
 use soroban_sdk::{Address, BytesN, Env, String, Symbol};
 
 pub fn update_pickup_location(
@@ -14,7 +14,7 @@ pub fn update_pickup_location(
     let mut membership: CSAMembership = env
         .storage()
         .persistent()
-        .get(&token_id)
+        .get(&DataKey::Membership(token_id.clone()))
         .ok_or(Error::NotFound)?;
     env.logs().add("After getting membership", &[]);
 
@@ -24,7 +24,9 @@ pub fn update_pickup_location(
     env.logs().add("After member check", &[]);
 
     membership.pickup_location = new_location.clone();
-    env.storage().persistent().set(&token_id, &membership);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Membership(token_id.clone()), &membership);
     env.logs().add("After updating membership", &[]);
 
     env.events().publish(
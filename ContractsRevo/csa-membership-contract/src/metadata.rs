@@ -1,6 +1,8 @@
-use crate::CSAMembership;
+use crate::{CSAMembership, DataKey};
 use soroban_sdk::{BytesN, Env};
 
 pub fn get_membership_metadata(env: Env, token_id: BytesN<32>) -> Option<CSAMembership> {
-    env.storage().persistent().get(&token_id)
+    env.storage()
+        .persistent()
+        .get(&DataKey::Membership(token_id))
 }
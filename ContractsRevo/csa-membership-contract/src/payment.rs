@@ -0,0 +1,272 @@
+use crate::{CSAMembership, DataKey, Error, PaymentPlan, PaymentRecord, ShareOffer, ShareSize};
+use soroban_sdk::{token, Address, BytesN, Env, String, Symbol, Vec};
+
+pub const WEEK_IN_SECONDS: u64 = 7 * 24 * 60 * 60;
+pub const MONTH_IN_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+pub fn set_share_price(
+    env: Env,
+    farm_id: BytesN<32>,
+    season: String,
+    share_size: ShareSize,
+    offer: ShareOffer,
+    operator: Address,
+) -> Result<(), Error> {
+    crate::inventory::require_farm_operator(&env, &farm_id, &operator)?;
+
+    if season.is_empty() {
+        return Err(Error::InvalidSeason);
+    }
+    if offer.price <= 0 {
+        return Err(Error::InvalidPrice);
+    }
+
+    env.storage().persistent().set(
+        &DataKey::SharePrice(farm_id.clone(), season.clone(), share_size),
+        &offer,
+    );
+
+    env.events().publish(
+        (Symbol::new(&env, "share_price_set"), farm_id),
+        (season, share_size, offer.token, offer.price),
+    );
+
+    Ok(())
+}
+
+pub fn get_share_price(
+    env: &Env,
+    farm_id: &BytesN<32>,
+    season: &String,
+    share_size: ShareSize,
+) -> Option<ShareOffer> {
+    env.storage().persistent().get(&DataKey::SharePrice(
+        farm_id.clone(),
+        season.clone(),
+        share_size,
+    ))
+}
+
+pub fn get_payment(env: &Env, token_id: &BytesN<32>) -> Option<PaymentRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Payment(token_id.clone()))
+}
+
+/// Charge the first installment of a new membership. Shares without a published
+/// price stay free, but only when paid up front.
+pub fn open_payment(
+    env: &Env,
+    token_id: &BytesN<32>,
+    membership: &CSAMembership,
+    plan: PaymentPlan,
+) -> Result<(), Error> {
+    let Some(offer) = get_share_price(
+        env,
+        &membership.farm_id,
+        &membership.season,
+        membership.share_size,
+    ) else {
+        return match plan {
+            PaymentPlan::Upfront => Ok(()),
+            _ => Err(Error::PriceNotSet),
+        };
+    };
+
+    let mut record = PaymentRecord {
+        plan,
+        token: offer.token,
+        total_price: offer.price,
+        paid: 0,
+        released: 0,
+        installments_total: installment_count(plan, membership),
+        installments_paid: 0,
+        next_due: 0,
+    };
+    take_installment(env, &mut record, membership);
+    save_payment(env, token_id, &record);
+
+    Ok(())
+}
+
+/// Pay the member's next installment, returning the amount charged
+pub fn pay_installment(env: Env, token_id: BytesN<32>, member: Address) -> Result<i128, Error> {
+    member.require_auth();
+
+    let membership: CSAMembership = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Membership(token_id.clone()))
+        .ok_or(Error::NotFound)?;
+    if membership.member != member {
+        return Err(Error::NotAuthorized);
+    }
+
+    let mut record = get_payment(&env, &token_id).ok_or(Error::NoPaymentPlan)?;
+    if record.installments_paid >= record.installments_total {
+        return Err(Error::FullyPaid);
+    }
+
+    let amount = take_installment(&env, &mut record, &membership);
+    save_payment(&env, &token_id, &record);
+
+    env.events().publish(
+        (Symbol::new(&env, "installment_paid"), member),
+        (token_id, amount, record.installments_paid),
+    );
+
+    Ok(amount)
+}
+
+/// A membership is suspended while an installment is overdue
+pub fn is_suspended(env: &Env, token_id: &BytesN<32>) -> bool {
+    get_payment(env, token_id)
+        .is_some_and(|record| record.next_due != 0 && env.ledger().timestamp() > record.next_due)
+}
+
+/// Pay out the share revenue the farm has earned so far on the given memberships
+pub fn collect_payments(
+    env: Env,
+    farm_id: BytesN<32>,
+    operator: Address,
+    token_ids: Vec<BytesN<32>>,
+) -> Result<i128, Error> {
+    crate::inventory::require_farm_operator(&env, &farm_id, &operator)?;
+
+    let mut collected = 0;
+    for token_id in token_ids.iter() {
+        let membership: CSAMembership = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Membership(token_id.clone()))
+            .ok_or(Error::NotFound)?;
+        if membership.farm_id != farm_id {
+            return Err(Error::NotAuthorized);
+        }
+        let Some(mut record) = get_payment(&env, &token_id) else {
+            continue;
+        };
+
+        collected += release_earned(&env, &mut record, &membership, &operator);
+        save_payment(&env, &token_id, &record);
+    }
+
+    env.events().publish(
+        (Symbol::new(&env, "payments_collected"), farm_id),
+        collected,
+    );
+
+    Ok(collected)
+}
+
+/// Settle a cancelled membership: the farm keeps what it earned so far and the
+/// member is refunded the rest. Returns the refund.
+pub fn settle_cancellation(
+    env: &Env,
+    token_id: &BytesN<32>,
+    membership: &CSAMembership,
+) -> Result<i128, Error> {
+    let Some(mut record) = get_payment(env, token_id) else {
+        return Ok(0);
+    };
+    let operator = crate::inventory::get_farm_operator(env, &membership.farm_id)
+        .ok_or(Error::FarmNotRegistered)?;
+
+    release_earned(env, &mut record, membership, &operator);
+    let refund = record.paid - record.released;
+    if refund > 0 {
+        token::Client::new(env, &record.token).transfer(
+            &env.current_contract_address(),
+            &membership.member,
+            &refund,
+        );
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Payment(token_id.clone()));
+
+    Ok(refund)
+}
+
+/// Value of the share delivered by now, pro rata over the season and capped at what was paid
+pub fn earned_amount(env: &Env, record: &PaymentRecord, membership: &CSAMembership) -> i128 {
+    let now = env.ledger().timestamp();
+    if now <= membership.start_date {
+        return 0;
+    }
+
+    let duration = membership.end_date - membership.start_date;
+    let elapsed = now.min(membership.end_date) - membership.start_date;
+    let delivered = record.total_price * elapsed as i128 / duration as i128;
+
+    delivered.min(record.paid)
+}
+
+fn installment_count(plan: PaymentPlan, membership: &CSAMembership) -> u32 {
+    let period = match plan {
+        PaymentPlan::Upfront => return 1,
+        PaymentPlan::Weekly => WEEK_IN_SECONDS,
+        PaymentPlan::Monthly => MONTH_IN_SECONDS,
+    };
+
+    let duration = membership.end_date - membership.start_date;
+    duration.div_ceil(period).max(1) as u32
+}
+
+fn installment_period(plan: PaymentPlan) -> u64 {
+    match plan {
+        PaymentPlan::Upfront => 0,
+        PaymentPlan::Weekly => WEEK_IN_SECONDS,
+        PaymentPlan::Monthly => MONTH_IN_SECONDS,
+    }
+}
+
+/// Move the next installment from the member into escrow
+fn take_installment(env: &Env, record: &mut PaymentRecord, membership: &CSAMembership) -> i128 {
+    let base = record.total_price / record.installments_total as i128;
+    let amount = if record.installments_paid + 1 == record.installments_total {
+        record.total_price - record.paid
+    } else {
+        base
+    };
+
+    token::Client::new(env, &record.token).transfer(
+        &membership.member,
+        &env.current_contract_address(),
+        &amount,
+    );
+
+    record.paid += amount;
+    record.installments_paid += 1;
+    record.next_due = if record.installments_paid < record.installments_total {
+        membership.start_date + record.installments_paid as u64 * installment_period(record.plan)
+    } else {
+        0
+    };
+
+    amount
+}
+
+fn release_earned(
+    env: &Env,
+    record: &mut PaymentRecord,
+    membership: &CSAMembership,
+    operator: &Address,
+) -> i128 {
+    let due = earned_amount(env, record, membership) - record.released;
+    if due > 0 {
+        token::Client::new(env, &record.token).transfer(
+            &env.current_contract_address(),
+            operator,
+            &due,
+        );
+        record.released += due;
+    }
+    due.max(0)
+}
+
+fn save_payment(env: &Env, token_id: &BytesN<32>, record: &PaymentRecord) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Payment(token_id.clone()), record);
+}
@@ -0,0 +1,222 @@
+use crate::{
+    CSAMembership, DataKey, DistributionRecord, Error, PickupSchedule, PickupStatus,
+    MAX_PICKUPS_PER_SEASON,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+pub fn set_pickup_schedule(
+    env: Env,
+    farm_id: BytesN<32>,
+    season: String,
+    schedule: PickupSchedule,
+    operator: Address,
+) -> Result<(), Error> {
+    crate::inventory::require_farm_operator(&env, &farm_id, &operator)?;
+
+    if season.is_empty() {
+        return Err(Error::InvalidSeason);
+    }
+
+    // Dates must be strictly increasing and the skip allowance must fit the season
+    let dates = &schedule.pickup_dates;
+    if dates.is_empty() || dates.len() > MAX_PICKUPS_PER_SEASON || schedule.max_skips > dates.len()
+    {
+        return Err(Error::InvalidSchedule);
+    }
+    for i in 1..dates.len() {
+        if dates.get_unchecked(i) <= dates.get_unchecked(i - 1) {
+            return Err(Error::InvalidSchedule);
+        }
+    }
+
+    env.storage().persistent().set(
+        &DataKey::PickupSchedule(farm_id.clone(), season.clone()),
+        &schedule,
+    );
+
+    env.events().publish(
+        (Symbol::new(&env, "pickup_schedule_set"), farm_id),
+        (season, dates.len()),
+    );
+
+    Ok(())
+}
+
+pub fn get_pickup_schedule(
+    env: &Env,
+    farm_id: &BytesN<32>,
+    season: &String,
+) -> Option<PickupSchedule> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PickupSchedule(farm_id.clone(), season.clone()))
+}
+
+/// Allow or revoke a pickup-site operator's right to confirm collections for the farm
+pub fn set_site_operator(
+    env: Env,
+    farm_id: BytesN<32>,
+    site_operator: Address,
+    allowed: bool,
+    operator: Address,
+) -> Result<(), Error> {
+    crate::inventory::require_farm_operator(&env, &farm_id, &operator)?;
+
+    let key = DataKey::SiteOperator(farm_id.clone(), site_operator.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    env.events().publish(
+        (Symbol::new(&env, "site_operator_updated"), farm_id),
+        (site_operator, allowed),
+    );
+
+    Ok(())
+}
+
+pub fn is_site_operator(env: &Env, farm_id: &BytesN<32>, site_operator: &Address) -> bool {
+    env.storage().persistent().has(&DataKey::SiteOperator(
+        farm_id.clone(),
+        site_operator.clone(),
+    ))
+}
+
+/// Record that a member's share for a scheduled week was handed over.
+/// Donated weeks can also be confirmed once the food bank collects them.
+pub fn confirm_pickup(
+    env: Env,
+    token_id: BytesN<32>,
+    week: u32,
+    site_operator: Address,
+) -> Result<(), Error> {
+    site_operator.require_auth();
+
+    let membership = load_membership(&env, &token_id)?;
+    if !is_site_operator(&env, &membership.farm_id, &site_operator) {
+        return Err(Error::NotAuthorized);
+    }
+
+    let pickup_date = scheduled_date(&env, &membership, week)?;
+    if env.ledger().timestamp() < pickup_date {
+        return Err(Error::PickupNotOpen);
+    }
+
+    let mut history = get_distribution_history(&env, &token_id);
+    match history.iter().position(|record| record.week == week) {
+        Some(index) => {
+            // Only an unconfirmed donation can be confirmed after the fact
+            let mut record = history.get_unchecked(index as u32);
+            if !matches!(record.status, PickupStatus::Donated(_)) || record.confirmed_by.is_some() {
+                return Err(Error::AlreadyRecorded);
+            }
+            record.confirmed_by = Some(site_operator.clone());
+            record.recorded_at = env.ledger().timestamp();
+            history.set(index as u32, record);
+        }
+        None => {
+            if crate::payment::is_suspended(&env, &token_id) {
+                return Err(Error::MembershipSuspended);
+            }
+            history.push_back(DistributionRecord {
+                week,
+                pickup_date,
+                status: PickupStatus::Collected,
+                confirmed_by: Some(site_operator.clone()),
+                recorded_at: env.ledger().timestamp(),
+            });
+        }
+    }
+    save_history(&env, &token_id, &history);
+
+    env.events().publish(
+        (Symbol::new(&env, "pickup_confirmed"), site_operator),
+        (token_id, week),
+    );
+
+    Ok(())
+}
+
+/// Skip a scheduled week before its pickup date, optionally donating the share
+pub fn skip_pickup(
+    env: Env,
+    token_id: BytesN<32>,
+    week: u32,
+    donate_to: Option<Address>,
+    member: Address,
+) -> Result<(), Error> {
+    member.require_auth();
+
+    let membership = load_membership(&env, &token_id)?;
+    if membership.member != member {
+        return Err(Error::NotAuthorized);
+    }
+
+    let schedule = get_pickup_schedule(&env, &membership.farm_id, &membership.season)
+        .ok_or(Error::ScheduleNotSet)?;
+    let pickup_date = scheduled_date(&env, &membership, week)?;
+    if env.ledger().timestamp() >= pickup_date {
+        return Err(Error::SkipDeadlinePassed);
+    }
+
+    let mut history = get_distribution_history(&env, &token_id);
+    if history.iter().any(|record| record.week == week) {
+        return Err(Error::AlreadyRecorded);
+    }
+    let skipped = history
+        .iter()
+        .filter(|record| record.status != PickupStatus::Collected)
+        .count() as u32;
+    if skipped >= schedule.max_skips {
+        return Err(Error::SkipLimitReached);
+    }
+
+    let status = match &donate_to {
+        Some(food_bank) => PickupStatus::Donated(food_bank.clone()),
+        None => PickupStatus::Skipped,
+    };
+    history.push_back(DistributionRecord {
+        week,
+        pickup_date,
+        status,
+        confirmed_by: None,
+        recorded_at: env.ledger().timestamp(),
+    });
+    save_history(&env, &token_id, &history);
+
+    env.events().publish(
+        (Symbol::new(&env, "pickup_skipped"), member),
+        (token_id, week, donate_to),
+    );
+
+    Ok(())
+}
+
+/// Every collection, skip and donation recorded for a membership
+pub fn get_distribution_history(env: &Env, token_id: &BytesN<32>) -> Vec<DistributionRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Distribution(token_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn scheduled_date(env: &Env, membership: &CSAMembership, week: u32) -> Result<u64, Error> {
+    let schedule = get_pickup_schedule(env, &membership.farm_id, &membership.season)
+        .ok_or(Error::ScheduleNotSet)?;
+    schedule.pickup_dates.get(week).ok_or(Error::InvalidWeek)
+}
+
+fn load_membership(env: &Env, token_id: &BytesN<32>) -> Result<CSAMembership, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Membership(token_id.clone()))
+        .ok_or(Error::NotFound)
+}
+
+fn save_history(env: &Env, token_id: &BytesN<32>, history: &Vec<DistributionRecord>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Distribution(token_id.clone()), history);
+}
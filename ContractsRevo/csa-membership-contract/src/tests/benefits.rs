@@ -129,7 +129,7 @@ fn test_benefit_distribution_large_share() {
 }
 
 #[test]
-fn test_benefit_tracking_overwrites_by_season() {
+fn test_benefit_tracking_separate_by_season() {
     let test_env = setup_test();
     let client = create_client(&test_env);
 
@@ -158,8 +158,11 @@ fn test_benefit_tracking_overwrites_by_season() {
         &test_env.member2,
     );
 
-    // Token IDs are the same, last enrollment wins
-    assert_eq!(token_summer, token_fall);
+    // Each enrollment gets its own token
+    assert_ne!(token_summer, token_fall);
+    let summer = client.get_membership_metadata(&token_summer).unwrap();
+    assert_eq!(summer.season, summer_season);
+    assert_eq!(summer.member, test_env.member1);
 
     let membership = client.get_membership_metadata(&token_fall).unwrap();
     assert_eq!(membership.season, fall_season);
@@ -167,7 +170,7 @@ fn test_benefit_tracking_overwrites_by_season() {
 }
 
 #[test]
-fn test_benefit_tracking_overwrites_by_farm() {
+fn test_benefit_tracking_separate_by_farm() {
     let test_env = setup_test();
     let client = create_client(&test_env);
 
@@ -196,8 +199,12 @@ fn test_benefit_tracking_overwrites_by_farm() {
         &test_env.member2,
     );
 
-    // Token IDs are the same, last enrollment wins
-    assert_eq!(token1, token2);
+    // Each enrollment gets its own token
+    assert_ne!(token1, token2);
+    assert_eq!(
+        client.get_membership_metadata(&token1).unwrap().farm_id,
+        farm1
+    );
 
     let membership = client.get_membership_metadata(&token2).unwrap();
     assert_eq!(membership.farm_id, farm2);
@@ -234,8 +241,15 @@ fn test_benefit_pickup_location_tracking() {
         &test_env.member2,
     );
 
-    // Token IDs are the same, last enrollment wins
-    assert_eq!(token1, token2);
+    // Each enrollment keeps its own pickup location
+    assert_ne!(token1, token2);
+    assert_eq!(
+        client
+            .get_membership_metadata(&token1)
+            .unwrap()
+            .pickup_location,
+        location1
+    );
 
     let membership = client.get_membership_metadata(&token2).unwrap();
     assert_eq!(membership.pickup_location, location2);
@@ -184,7 +184,7 @@ fn test_duplicate_enrollment_same_member() {
         &test_env.member1,
     );
 
-    // Second enrollment (will overwrite first due to same token_id)
+    // Second enrollment gets its own token
     let token_id2 = client.enroll_membership(
         &standard_farm_id(&test_env.env),
         &String::from_str(&test_env.env, "Fall 2025"),
@@ -195,10 +195,13 @@ fn test_duplicate_enrollment_same_member() {
         &test_env.member1,
     );
 
-    // Token IDs are the same (hardcoded to zeros in enroll.rs)
-    assert_eq!(token_id1, token_id2);
+    assert_ne!(token_id1, token_id2);
 
-    // Only the latest membership data should exist
+    // Both memberships are kept
+    assert_eq!(
+        client.get_membership_metadata(&token_id1).unwrap().season,
+        standard_season(&test_env.env)
+    );
     let membership = client.get_membership_metadata(&token_id2).unwrap();
     assert_eq!(
         membership.season,
@@ -234,10 +237,13 @@ fn test_membership_id_consistency() {
         &test_env.member2,
     );
 
-    // Token IDs are the same (hardcoded to zeros in enroll.rs)
-    assert_eq!(token_id1, token_id2);
+    // Token IDs are unique per enrollment
+    assert_ne!(token_id1, token_id2);
+    assert_eq!(
+        client.get_membership_metadata(&token_id1).unwrap().member,
+        test_env.member1
+    );
 
-    // Latest membership data should exist
     let membership = client.get_membership_metadata(&token_id2).unwrap();
     assert_eq!(membership.member, test_env.member2);
     assert_eq!(membership.share_size, ShareSize::Large);
@@ -270,7 +276,7 @@ fn test_high_volume_enrollment() {
 }
 
 #[test]
-fn test_enrollment_different_farms_overwrites() {
+fn test_enrollment_different_farms_kept_separate() {
     let test_env = setup_test();
     let client = create_client(&test_env);
 
@@ -300,8 +306,12 @@ fn test_enrollment_different_farms_overwrites() {
         &test_env.member1,
     );
 
-    // Token IDs are the same, so second enrollment overwrites first
-    assert_eq!(token_id1, token_id2);
+    // The second enrollment does not overwrite the first
+    assert_ne!(token_id1, token_id2);
+    assert_eq!(
+        client.get_membership_metadata(&token_id1).unwrap().farm_id,
+        farm1
+    );
 
     let membership = client.get_membership_metadata(&token_id2).unwrap();
     assert_eq!(membership.farm_id, farm2);
@@ -0,0 +1,223 @@
+use crate::{tests::utils::*, CSAMembershipContractClient, Error, ShareInventory, ShareSize};
+use soroban_sdk::{testutils::Address as _, Address, BytesN};
+
+fn enroll(
+    test_env: &TestEnv,
+    client: &CSAMembershipContractClient,
+    member: &Address,
+) -> BytesN<32> {
+    client.enroll_membership(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &standard_pickup_location(&test_env.env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        member,
+    )
+}
+
+fn setup_capacity(test_env: &TestEnv, client: &CSAMembershipContractClient, capacity: u32) {
+    let farm_id = standard_farm_id(&test_env.env);
+    client.register_farm(&farm_id, &test_env.admin);
+    client.set_share_capacity(
+        &farm_id,
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &capacity,
+        &test_env.admin,
+    );
+}
+
+#[test]
+fn test_capacity_limits_enrollment() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    setup_capacity(&test_env, &client, 2);
+    enroll(&test_env, &client, &test_env.member1);
+    enroll(&test_env, &client, &test_env.member2);
+
+    let result = client.try_enroll_membership(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &standard_pickup_location(&test_env.env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &test_env.member3,
+    );
+    assert_eq!(result, Err(Ok(Error::SoldOut)));
+
+    assert_eq!(
+        client.get_share_inventory(
+            &standard_farm_id(&test_env.env),
+            &standard_season(&test_env.env),
+            &ShareSize::Medium
+        ),
+        ShareInventory {
+            capacity: Some(2),
+            enrolled: 2,
+            waitlisted: 0,
+        }
+    );
+}
+
+#[test]
+fn test_capacity_is_per_share_size() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    setup_capacity(&test_env, &client, 1);
+    enroll(&test_env, &client, &test_env.member1);
+
+    // Large shares have no capacity set for this season
+    client.enroll_membership(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Large,
+        &standard_pickup_location(&test_env.env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &test_env.member2,
+    );
+}
+
+#[test]
+fn test_capacity_requires_farm_operator() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    let farm_id = standard_farm_id(&test_env.env);
+    let season = standard_season(&test_env.env);
+
+    let result =
+        client.try_set_share_capacity(&farm_id, &season, &ShareSize::Medium, &5, &test_env.admin);
+    assert_eq!(result, Err(Ok(Error::FarmNotRegistered)));
+
+    client.register_farm(&farm_id, &test_env.admin);
+    let result =
+        client.try_set_share_capacity(&farm_id, &season, &ShareSize::Medium, &5, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_register_farm(&farm_id, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::FarmAlreadyRegistered)));
+}
+
+#[test]
+fn test_capacity_cannot_drop_below_enrolled() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    setup_capacity(&test_env, &client, 3);
+    enroll(&test_env, &client, &test_env.member1);
+    enroll(&test_env, &client, &test_env.member2);
+
+    let result = client.try_set_share_capacity(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &1,
+        &test_env.admin,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidCapacity)));
+}
+
+#[test]
+fn test_waitlist_only_when_sold_out() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    let farm_id = standard_farm_id(&test_env.env);
+    let season = standard_season(&test_env.env);
+    setup_capacity(&test_env, &client, 1);
+
+    let result = client.try_join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::SharesAvailable)));
+
+    enroll(&test_env, &client, &test_env.member1);
+    assert_eq!(
+        client.join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2),
+        0
+    );
+    assert_eq!(
+        client.join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member3),
+        1
+    );
+
+    let result = client.try_join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::AlreadyWaitlisted)));
+}
+
+#[test]
+fn test_cancelled_share_goes_to_waitlist_in_order() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    let farm_id = standard_farm_id(&test_env.env);
+    let season = standard_season(&test_env.env);
+    setup_capacity(&test_env, &client, 1);
+
+    let token_id = enroll(&test_env, &client, &test_env.member1);
+    client.join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2);
+    client.join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member3);
+
+    client.cancel_membership(&token_id, &test_env.member1);
+
+    // The freed share is held for the head of the queue
+    let outsider = Address::generate(&test_env.env);
+    let result = client.try_enroll_membership(
+        &farm_id,
+        &season,
+        &ShareSize::Medium,
+        &standard_pickup_location(&test_env.env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &outsider,
+    );
+    assert_eq!(result, Err(Ok(Error::SoldOut)));
+    let result = client.try_enroll_membership(
+        &farm_id,
+        &season,
+        &ShareSize::Medium,
+        &standard_pickup_location(&test_env.env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &test_env.member3,
+    );
+    assert_eq!(result, Err(Ok(Error::SoldOut)));
+
+    enroll(&test_env, &client, &test_env.member2);
+
+    let waitlist = client.get_waitlist(&farm_id, &season, &ShareSize::Medium);
+    assert_eq!(waitlist.len(), 1);
+    assert_eq!(waitlist.get(0), Some(test_env.member3.clone()));
+}
+
+#[test]
+fn test_leave_waitlist() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    let farm_id = standard_farm_id(&test_env.env);
+    let season = standard_season(&test_env.env);
+    setup_capacity(&test_env, &client, 1);
+    enroll(&test_env, &client, &test_env.member1);
+
+    client.join_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2);
+    client.leave_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2);
+    assert!(client
+        .get_waitlist(&farm_id, &season, &ShareSize::Medium)
+        .is_empty());
+
+    let result =
+        client.try_leave_waitlist(&farm_id, &season, &ShareSize::Medium, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::NotWaitlisted)));
+}
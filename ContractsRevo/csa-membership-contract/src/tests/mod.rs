@@ -1,4 +1,8 @@
 pub mod benefits;
 pub mod enrollment;
+pub mod inventory;
+pub mod payment;
+pub mod pickup;
 pub mod subscription;
+pub mod transfer;
 pub mod utils;
@@ -0,0 +1,298 @@
+use crate::{
+    tests::utils::*, CSAMembershipContractClient, EnrollmentRequest, Error, PaymentPlan,
+    ShareOffer, ShareSize,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN,
+};
+
+const SEASON_LENGTH: u64 = FUTURE_END_DATE - FUTURE_START_DATE;
+
+fn setup_priced_share(
+    test_env: &TestEnv,
+    client: &CSAMembershipContractClient,
+) -> TokenClient<'static> {
+    let env = &test_env.env;
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    let minter = StellarAssetClient::new(env, &token.address());
+    for member in [&test_env.member1, &test_env.member2, &test_env.member3] {
+        minter.mint(member, &(SUBSCRIPTION_AMOUNT * 10));
+    }
+
+    let farm_id = standard_farm_id(env);
+    client.register_farm(&farm_id, &test_env.admin);
+    client.set_share_price(
+        &farm_id,
+        &standard_season(env),
+        &ShareSize::Medium,
+        &ShareOffer {
+            token: token.address(),
+            price: SUBSCRIPTION_AMOUNT,
+        },
+        &test_env.admin,
+    );
+
+    TokenClient::new(env, &token.address())
+}
+
+fn enroll_request(test_env: &TestEnv, member: &Address) -> EnrollmentRequest {
+    EnrollmentRequest {
+        farm_id: standard_farm_id(&test_env.env),
+        season: standard_season(&test_env.env),
+        share_size: ShareSize::Medium,
+        pickup_location: standard_pickup_location(&test_env.env),
+        start_date: FUTURE_START_DATE,
+        end_date: FUTURE_END_DATE,
+        member: member.clone(),
+    }
+}
+
+fn set_time(test_env: &TestEnv, timestamp: u64) {
+    test_env
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp = timestamp);
+}
+
+#[test]
+fn test_upfront_payment_on_enrollment() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token = setup_priced_share(&test_env, &client);
+
+    let token_id = client.enroll_membership(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &standard_pickup_location(&test_env.env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &test_env.member1,
+    );
+
+    assert_eq!(token.balance(&test_env.member1), SUBSCRIPTION_AMOUNT * 9);
+    assert_eq!(token.balance(&test_env.contract_id), SUBSCRIPTION_AMOUNT);
+
+    let record = client.get_payment(&token_id).unwrap();
+    assert_eq!(record.plan, PaymentPlan::Upfront);
+    assert_eq!(record.paid, SUBSCRIPTION_AMOUNT);
+    assert_eq!(record.installments_total, 1);
+    assert_eq!(record.next_due, 0);
+    assert!(!client.is_suspended(&token_id));
+}
+
+#[test]
+fn test_unpriced_share_is_free_upfront_only() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+
+    let token_id = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Upfront,
+    );
+    assert!(client.get_payment(&token_id).is_none());
+
+    let result = client.try_enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member2),
+        &PaymentPlan::Monthly,
+    );
+    assert_eq!(result, Err(Ok(Error::PriceNotSet)));
+}
+
+#[test]
+fn test_monthly_installments() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token = setup_priced_share(&test_env, &client);
+
+    let token_id = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Monthly,
+    );
+
+    // A 90-day season is billed in three monthly installments
+    let record = client.get_payment(&token_id).unwrap();
+    assert_eq!(record.installments_total, 3);
+    assert_eq!(record.installments_paid, 1);
+    assert_eq!(record.paid, SUBSCRIPTION_AMOUNT / 3);
+    assert_eq!(record.next_due, FUTURE_START_DATE + 30 * 24 * 60 * 60);
+
+    client.pay_installment(&token_id, &test_env.member1);
+    let last = client.pay_installment(&token_id, &test_env.member1);
+    assert_eq!(last, SUBSCRIPTION_AMOUNT - 2 * (SUBSCRIPTION_AMOUNT / 3));
+
+    let record = client.get_payment(&token_id).unwrap();
+    assert_eq!(record.paid, SUBSCRIPTION_AMOUNT);
+    assert_eq!(record.next_due, 0);
+    assert_eq!(token.balance(&test_env.contract_id), SUBSCRIPTION_AMOUNT);
+
+    let result = client.try_pay_installment(&token_id, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::FullyPaid)));
+}
+
+#[test]
+fn test_missed_installment_suspends_membership() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    setup_priced_share(&test_env, &client);
+
+    let token_id = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Weekly,
+    );
+    assert_eq!(
+        client.get_payment(&token_id).unwrap().installments_total,
+        13
+    );
+
+    set_time(&test_env, FUTURE_START_DATE + 7 * 24 * 60 * 60);
+    assert!(!client.is_suspended(&token_id));
+
+    set_time(&test_env, FUTURE_START_DATE + 7 * 24 * 60 * 60 + 1);
+    assert!(client.is_suspended(&token_id));
+
+    // Catching up lifts the suspension
+    client.pay_installment(&token_id, &test_env.member1);
+    assert!(!client.is_suspended(&token_id));
+}
+
+#[test]
+fn test_cancel_before_season_refunds_everything() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token = setup_priced_share(&test_env, &client);
+
+    let token_id = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Upfront,
+    );
+    client.cancel_membership(&token_id, &test_env.member1);
+
+    assert_eq!(token.balance(&test_env.member1), SUBSCRIPTION_AMOUNT * 10);
+    assert_eq!(token.balance(&test_env.admin), 0);
+    assert!(client.get_payment(&token_id).is_none());
+}
+
+#[test]
+fn test_cancel_mid_season_refunds_pro_rata() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token = setup_priced_share(&test_env, &client);
+
+    let token_id = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Upfront,
+    );
+
+    // A third of the season has been delivered
+    set_time(&test_env, FUTURE_START_DATE + SEASON_LENGTH / 3);
+    client.cancel_membership(&token_id, &test_env.member1);
+
+    let earned = SUBSCRIPTION_AMOUNT / 3;
+    assert_eq!(token.balance(&test_env.admin), earned);
+    assert_eq!(
+        token.balance(&test_env.member1),
+        SUBSCRIPTION_AMOUNT * 9 + (SUBSCRIPTION_AMOUNT - earned)
+    );
+    assert_eq!(token.balance(&test_env.contract_id), 0);
+}
+
+#[test]
+fn test_farm_collects_earned_payments() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token = setup_priced_share(&test_env, &client);
+    let farm_id = standard_farm_id(&test_env.env);
+
+    let token1 = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Upfront,
+    );
+    let token2 = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member2),
+        &PaymentPlan::Upfront,
+    );
+    let tokens = vec![&test_env.env, token1.clone(), token2.clone()];
+
+    // Nothing is earned before the season starts
+    assert_eq!(
+        client.collect_payments(&farm_id, &test_env.admin, &tokens),
+        0
+    );
+
+    set_time(&test_env, FUTURE_START_DATE + SEASON_LENGTH / 2);
+    assert_eq!(
+        client.collect_payments(&farm_id, &test_env.admin, &tokens),
+        SUBSCRIPTION_AMOUNT
+    );
+    // Already released revenue is not paid twice
+    assert_eq!(
+        client.collect_payments(&farm_id, &test_env.admin, &tokens),
+        0
+    );
+
+    set_time(&test_env, FUTURE_END_DATE + 1);
+    client.collect_payments(&farm_id, &test_env.admin, &tokens);
+    assert_eq!(token.balance(&test_env.admin), SUBSCRIPTION_AMOUNT * 2);
+    assert_eq!(token.balance(&test_env.contract_id), 0);
+}
+
+#[test]
+fn test_share_price_requires_farm_operator() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    setup_priced_share(&test_env, &client);
+
+    let offer = ShareOffer {
+        token: Address::generate(&test_env.env),
+        price: SUBSCRIPTION_AMOUNT,
+    };
+    let result = client.try_set_share_price(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &offer,
+        &test_env.member1,
+    );
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_set_share_price(
+        &standard_farm_id(&test_env.env),
+        &standard_season(&test_env.env),
+        &ShareSize::Medium,
+        &ShareOffer { price: 0, ..offer },
+        &test_env.admin,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidPrice)));
+}
+
+#[test]
+fn test_collect_rejects_other_farms_memberships() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    setup_priced_share(&test_env, &client);
+
+    let token_id = client.enroll_with_plan(
+        &enroll_request(&test_env, &test_env.member1),
+        &PaymentPlan::Upfront,
+    );
+    let other_farm: BytesN<32> = create_farm_id(&test_env.env, 9);
+    let other_operator = Address::generate(&test_env.env);
+    client.register_farm(&other_farm, &other_operator);
+
+    let result =
+        client.try_collect_payments(&other_farm, &other_operator, &vec![&test_env.env, token_id]);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
@@ -0,0 +1,269 @@
+use crate::{
+    tests::utils::*, CSAMembershipContractClient, Error, PickupSchedule, PickupStatus, ShareSize,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN,
+};
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+struct PickupSetup {
+    token_id: BytesN<32>,
+    site_operator: Address,
+}
+
+fn setup_pickups(test_env: &TestEnv, client: &CSAMembershipContractClient) -> PickupSetup {
+    let env = &test_env.env;
+    let farm_id = standard_farm_id(env);
+    let site_operator = Address::generate(env);
+
+    client.register_farm(&farm_id, &test_env.admin);
+    client.set_pickup_schedule(
+        &farm_id,
+        &standard_season(env),
+        &PickupSchedule {
+            pickup_dates: vec![
+                env,
+                FUTURE_START_DATE,
+                FUTURE_START_DATE + WEEK,
+                FUTURE_START_DATE + 2 * WEEK,
+                FUTURE_START_DATE + 3 * WEEK,
+            ],
+            max_skips: 2,
+        },
+        &test_env.admin,
+    );
+    client.set_site_operator(&farm_id, &site_operator, &true, &test_env.admin);
+
+    let token_id = client.enroll_membership(
+        &farm_id,
+        &standard_season(env),
+        &ShareSize::Medium,
+        &standard_pickup_location(env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &test_env.member1,
+    );
+
+    PickupSetup {
+        token_id,
+        site_operator,
+    }
+}
+
+fn set_time(test_env: &TestEnv, timestamp: u64) {
+    test_env
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp = timestamp);
+}
+
+#[test]
+fn test_schedule_must_be_increasing() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let env = &test_env.env;
+
+    let farm_id = standard_farm_id(env);
+    client.register_farm(&farm_id, &test_env.admin);
+
+    let result = client.try_set_pickup_schedule(
+        &farm_id,
+        &standard_season(env),
+        &PickupSchedule {
+            pickup_dates: vec![env, FUTURE_START_DATE + WEEK, FUTURE_START_DATE],
+            max_skips: 0,
+        },
+        &test_env.admin,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidSchedule)));
+
+    let result = client.try_set_pickup_schedule(
+        &farm_id,
+        &standard_season(env),
+        &PickupSchedule {
+            pickup_dates: vec![env, FUTURE_START_DATE],
+            max_skips: 2,
+        },
+        &test_env.admin,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidSchedule)));
+}
+
+#[test]
+fn test_site_operator_confirms_pickup() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let setup = setup_pickups(&test_env, &client);
+
+    // Collections cannot be confirmed ahead of the pickup date
+    let result = client.try_confirm_pickup(&setup.token_id, &0, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::PickupNotOpen)));
+
+    set_time(&test_env, FUTURE_START_DATE);
+    client.confirm_pickup(&setup.token_id, &0, &setup.site_operator);
+
+    let history = client.get_distribution_history(&setup.token_id);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.week, 0);
+    assert_eq!(record.pickup_date, FUTURE_START_DATE);
+    assert_eq!(record.status, PickupStatus::Collected);
+    assert_eq!(record.confirmed_by, Some(setup.site_operator.clone()));
+
+    let result = client.try_confirm_pickup(&setup.token_id, &0, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::AlreadyRecorded)));
+}
+
+#[test]
+fn test_confirm_pickup_requires_site_operator() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let setup = setup_pickups(&test_env, &client);
+    set_time(&test_env, FUTURE_START_DATE);
+
+    let result = client.try_confirm_pickup(&setup.token_id, &0, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.set_site_operator(
+        &standard_farm_id(&test_env.env),
+        &setup.site_operator,
+        &false,
+        &test_env.admin,
+    );
+    let result = client.try_confirm_pickup(&setup.token_id, &0, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_confirm_pickup_invalid_week() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let setup = setup_pickups(&test_env, &client);
+
+    let result = client.try_confirm_pickup(&setup.token_id, &4, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::InvalidWeek)));
+}
+
+#[test]
+fn test_skip_and_donate_up_to_limit() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let setup = setup_pickups(&test_env, &client);
+    let food_bank = Address::generate(&test_env.env);
+
+    client.skip_pickup(&setup.token_id, &1, &None, &test_env.member1);
+    client.skip_pickup(
+        &setup.token_id,
+        &2,
+        &Some(food_bank.clone()),
+        &test_env.member1,
+    );
+
+    let result = client.try_skip_pickup(&setup.token_id, &3, &None, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::SkipLimitReached)));
+
+    let history = client.get_distribution_history(&setup.token_id);
+    assert_eq!(history.get(0).unwrap().status, PickupStatus::Skipped);
+    assert_eq!(
+        history.get(1).unwrap().status,
+        PickupStatus::Donated(food_bank)
+    );
+
+    // Skipped weeks cannot be collected
+    set_time(&test_env, FUTURE_START_DATE + WEEK);
+    let result = client.try_confirm_pickup(&setup.token_id, &1, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::AlreadyRecorded)));
+}
+
+#[test]
+fn test_donated_share_collection_is_confirmed() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let setup = setup_pickups(&test_env, &client);
+    let food_bank = Address::generate(&test_env.env);
+
+    client.skip_pickup(
+        &setup.token_id,
+        &1,
+        &Some(food_bank.clone()),
+        &test_env.member1,
+    );
+
+    set_time(&test_env, FUTURE_START_DATE + WEEK);
+    client.confirm_pickup(&setup.token_id, &1, &setup.site_operator);
+
+    let record = client
+        .get_distribution_history(&setup.token_id)
+        .get(0)
+        .unwrap();
+    assert_eq!(record.status, PickupStatus::Donated(food_bank));
+    assert_eq!(record.confirmed_by, Some(setup.site_operator.clone()));
+
+    let result = client.try_confirm_pickup(&setup.token_id, &1, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::AlreadyRecorded)));
+}
+
+#[test]
+fn test_skip_after_pickup_date_fails() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let setup = setup_pickups(&test_env, &client);
+
+    set_time(&test_env, FUTURE_START_DATE + WEEK);
+    let result = client.try_skip_pickup(&setup.token_id, &1, &None, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::SkipDeadlinePassed)));
+
+    let result = client.try_skip_pickup(&setup.token_id, &2, &None, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_suspended_member_cannot_collect() {
+    use crate::{EnrollmentRequest, PaymentPlan, ShareOffer};
+    use soroban_sdk::token::StellarAssetClient;
+
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let env = &test_env.env;
+    let setup = setup_pickups(&test_env, &client);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&test_env.member2, &SUBSCRIPTION_AMOUNT);
+    client.set_share_price(
+        &standard_farm_id(env),
+        &standard_season(env),
+        &ShareSize::Large,
+        &ShareOffer {
+            token: token.address(),
+            price: SUBSCRIPTION_AMOUNT,
+        },
+        &test_env.admin,
+    );
+    let token_id = client.enroll_with_plan(
+        &EnrollmentRequest {
+            farm_id: standard_farm_id(env),
+            season: standard_season(env),
+            share_size: ShareSize::Large,
+            pickup_location: standard_pickup_location(env),
+            start_date: FUTURE_START_DATE,
+            end_date: FUTURE_END_DATE,
+            member: test_env.member2.clone(),
+        },
+        &PaymentPlan::Weekly,
+    );
+
+    // The second weekly installment is overdue by the third pickup
+    set_time(&test_env, FUTURE_START_DATE + 2 * WEEK);
+    let result = client.try_confirm_pickup(&token_id, &2, &setup.site_operator);
+    assert_eq!(result, Err(Ok(Error::MembershipSuspended)));
+}
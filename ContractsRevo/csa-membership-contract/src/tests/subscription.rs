@@ -143,7 +143,7 @@ fn test_subscription_with_long_period() {
 }
 
 #[test]
-fn test_multiple_subscriptions_overwrites() {
+fn test_multiple_subscriptions_kept_separate() {
     let test_env = setup_test();
     let client = create_client(&test_env);
 
@@ -160,7 +160,7 @@ fn test_multiple_subscriptions_overwrites() {
         &test_env.member1,
     );
 
-    // Second subscription for different season (will overwrite)
+    // Second subscription for a different season
     let token_id2 = client.enroll_membership(
         &standard_farm_id(&test_env.env),
         &String::from_str(&test_env.env, "Fall 2025"),
@@ -171,10 +171,16 @@ fn test_multiple_subscriptions_overwrites() {
         &test_env.member1,
     );
 
-    // Token IDs are the same
-    assert_eq!(token_id1, token_id2);
+    assert_ne!(token_id1, token_id2);
 
-    // Only latest subscription data exists
+    // Both subscriptions exist
+    assert_eq!(
+        client
+            .get_membership_metadata(&token_id1)
+            .unwrap()
+            .share_size,
+        ShareSize::Small
+    );
     let membership = client.get_membership_metadata(&token_id2).unwrap();
     assert_eq!(
         membership.season,
@@ -268,8 +274,9 @@ fn test_subscription_renewal_workflow() {
         &test_env.member1,
     );
 
-    // Token IDs are the same
-    assert_eq!(token_id1, token_id2);
+    // Renewal issues a new token; the cancelled one stays gone
+    assert_ne!(token_id1, token_id2);
+    assert!(client.get_membership_metadata(&token_id1).is_none());
     // New subscription should exist with updated data
     let membership = client.get_membership_metadata(&token_id2).unwrap();
     assert_eq!(
@@ -345,11 +352,24 @@ fn test_subscription_multiple_share_sizes() {
         &test_env.member3,
     );
 
-    // All token IDs are the same, last enrollment wins
-    assert_eq!(token_small, token_medium);
-    assert_eq!(token_medium, token_large);
+    // Every enrollment has its own token
+    assert_ne!(token_small, token_medium);
+    assert_ne!(token_medium, token_large);
+    assert_eq!(
+        client
+            .get_membership_metadata(&token_small)
+            .unwrap()
+            .share_size,
+        ShareSize::Small
+    );
+    assert_eq!(
+        client
+            .get_membership_metadata(&token_medium)
+            .unwrap()
+            .share_size,
+        ShareSize::Medium
+    );
 
-    // Only the last membership exists
     let membership_large = client.get_membership_metadata(&token_large).unwrap();
     assert_eq!(membership_large.share_size, ShareSize::Large);
     assert_eq!(membership_large.member, test_env.member3);
@@ -0,0 +1,202 @@
+use crate::{
+    tests::utils::*, CSAMembershipContractClient, Error, PickupSchedule, PickupStatus, ShareSize,
+    TransferPolicy,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN,
+};
+
+const TRANSFER_FEE: i128 = 50;
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+fn enroll_member1(test_env: &TestEnv, client: &CSAMembershipContractClient) -> BytesN<32> {
+    let env = &test_env.env;
+    client.register_farm(&standard_farm_id(env), &test_env.admin);
+    client.enroll_membership(
+        &standard_farm_id(env),
+        &standard_season(env),
+        &ShareSize::Medium,
+        &standard_pickup_location(env),
+        &FUTURE_START_DATE,
+        &FUTURE_END_DATE,
+        &test_env.member1,
+    )
+}
+
+fn set_fee_policy(
+    test_env: &TestEnv,
+    client: &CSAMembershipContractClient,
+    requires_approval: bool,
+) -> TokenClient<'static> {
+    let env = &test_env.env;
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&test_env.member2, &(TRANSFER_FEE * 2));
+
+    client.set_transfer_policy(
+        &standard_farm_id(env),
+        &TransferPolicy {
+            requires_approval,
+            fee: TRANSFER_FEE,
+            fee_token: Some(token.address()),
+        },
+        &test_env.admin,
+    );
+
+    TokenClient::new(env, &token.address())
+}
+
+#[test]
+fn test_transfer_without_policy() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token_id = enroll_member1(&test_env, &client);
+
+    client.offer_transfer(&token_id, &test_env.member1, &test_env.member2);
+    let pending = client.get_pending_transfer(&token_id).unwrap();
+    assert!(pending.approved);
+
+    client.accept_transfer(&token_id, &test_env.member2);
+
+    let membership = client.get_membership_metadata(&token_id).unwrap();
+    assert_eq!(membership.member, test_env.member2);
+    assert!(client.get_pending_transfer(&token_id).is_none());
+
+    // The previous member no longer controls the share
+    let result = client.try_cancel_membership(&token_id, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_transfer_requires_farm_approval() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token_id = enroll_member1(&test_env, &client);
+    let fee_token = set_fee_policy(&test_env, &client, true);
+
+    client.offer_transfer(&token_id, &test_env.member1, &test_env.member2);
+    let result = client.try_accept_transfer(&token_id, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::TransferNotApproved)));
+
+    let result = client.try_review_transfer(&token_id, &true, &test_env.member3);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.review_transfer(&token_id, &true, &test_env.admin);
+    client.accept_transfer(&token_id, &test_env.member2);
+
+    assert_eq!(fee_token.balance(&test_env.admin), TRANSFER_FEE);
+    assert_eq!(fee_token.balance(&test_env.member2), TRANSFER_FEE);
+    let membership = client.get_membership_metadata(&token_id).unwrap();
+    assert_eq!(membership.member, test_env.member2);
+}
+
+#[test]
+fn test_rejected_transfer_is_discarded() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token_id = enroll_member1(&test_env, &client);
+    set_fee_policy(&test_env, &client, true);
+
+    client.offer_transfer(&token_id, &test_env.member1, &test_env.member2);
+    client.review_transfer(&token_id, &false, &test_env.admin);
+
+    assert!(client.get_pending_transfer(&token_id).is_none());
+    let result = client.try_accept_transfer(&token_id, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::TransferNotFound)));
+}
+
+#[test]
+fn test_only_recipient_can_accept() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token_id = enroll_member1(&test_env, &client);
+
+    client.offer_transfer(&token_id, &test_env.member1, &test_env.member2);
+    let result = client.try_accept_transfer(&token_id, &test_env.member3);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_offer_transfer(&token_id, &test_env.member1, &test_env.member3);
+    assert_eq!(result, Err(Ok(Error::TransferPending)));
+
+    client.cancel_transfer(&token_id, &test_env.member1);
+    client.offer_transfer(&token_id, &test_env.member1, &test_env.member3);
+    client.accept_transfer(&token_id, &test_env.member3);
+
+    let membership = client.get_membership_metadata(&token_id).unwrap();
+    assert_eq!(membership.member, test_env.member3);
+}
+
+#[test]
+fn test_invalid_transfer_offers() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let token_id = enroll_member1(&test_env, &client);
+
+    let result = client.try_offer_transfer(&token_id, &test_env.member2, &test_env.member3);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_offer_transfer(&token_id, &test_env.member1, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::InvalidTransfer)));
+
+    test_env
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp = FUTURE_END_DATE);
+    let result = client.try_offer_transfer(&token_id, &test_env.member1, &test_env.member2);
+    assert_eq!(result, Err(Ok(Error::SeasonEnded)));
+}
+
+#[test]
+fn test_fee_policy_requires_token() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    enroll_member1(&test_env, &client);
+
+    let result = client.try_set_transfer_policy(
+        &standard_farm_id(&test_env.env),
+        &TransferPolicy {
+            requires_approval: false,
+            fee: TRANSFER_FEE,
+            fee_token: None,
+        },
+        &test_env.admin,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidTransfer)));
+}
+
+#[test]
+fn test_pickup_history_carries_forward() {
+    let test_env = setup_test();
+    let client = create_client(&test_env);
+    test_env.env.mock_all_auths();
+    let env = &test_env.env;
+    let token_id = enroll_member1(&test_env, &client);
+    client.set_pickup_schedule(
+        &standard_farm_id(env),
+        &standard_season(env),
+        &PickupSchedule {
+            pickup_dates: vec![env, FUTURE_START_DATE, FUTURE_START_DATE + WEEK],
+            max_skips: 2,
+        },
+        &test_env.admin,
+    );
+
+    client.skip_pickup(&token_id, &0, &None, &test_env.member1);
+    client.offer_transfer(&token_id, &test_env.member1, &test_env.member2);
+    client.accept_transfer(&token_id, &test_env.member2);
+
+    let result = client.try_skip_pickup(&token_id, &1, &None, &test_env.member1);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    client.skip_pickup(&token_id, &1, &None, &test_env.member2);
+
+    let history = client.get_distribution_history(&token_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().status, PickupStatus::Skipped);
+}
@@ -0,0 +1,195 @@
+use crate::{CSAMembership, DataKey, Error, PendingTransfer, TransferPolicy};
+use soroban_sdk::{token, Address, BytesN, Env, Symbol};
+
+pub fn set_transfer_policy(
+    env: Env,
+    farm_id: BytesN<32>,
+    policy: TransferPolicy,
+    operator: Address,
+) -> Result<(), Error> {
+    crate::inventory::require_farm_operator(&env, &farm_id, &operator)?;
+
+    // A fee needs a token to be paid in
+    if policy.fee < 0 || (policy.fee > 0 && policy.fee_token.is_none()) {
+        return Err(Error::InvalidTransfer);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::TransferPolicy(farm_id.clone()), &policy);
+
+    env.events().publish(
+        (Symbol::new(&env, "transfer_policy_set"), farm_id),
+        (policy.requires_approval, policy.fee),
+    );
+
+    Ok(())
+}
+
+/// The farm's transfer rules; farms without a policy allow free, unapproved transfers
+pub fn get_transfer_policy(env: &Env, farm_id: &BytesN<32>) -> TransferPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TransferPolicy(farm_id.clone()))
+        .unwrap_or(TransferPolicy {
+            requires_approval: false,
+            fee: 0,
+            fee_token: None,
+        })
+}
+
+pub fn get_pending_transfer(env: &Env, token_id: &BytesN<32>) -> Option<PendingTransfer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingTransfer(token_id.clone()))
+}
+
+/// Offer the rest of the season's share to another address
+pub fn offer_transfer(
+    env: Env,
+    token_id: BytesN<32>,
+    member: Address,
+    recipient: Address,
+) -> Result<(), Error> {
+    member.require_auth();
+
+    let membership = load_membership(&env, &token_id)?;
+    if membership.member != member {
+        return Err(Error::NotAuthorized);
+    }
+    if recipient == member {
+        return Err(Error::InvalidTransfer);
+    }
+    if get_pending_transfer(&env, &token_id).is_some() {
+        return Err(Error::TransferPending);
+    }
+    ensure_transferable(&env, &token_id, &membership)?;
+
+    let policy = get_transfer_policy(&env, &membership.farm_id);
+    let pending = PendingTransfer {
+        from: member.clone(),
+        to: recipient.clone(),
+        approved: !policy.requires_approval,
+        offered_at: env.ledger().timestamp(),
+    };
+    save_pending(&env, &token_id, &pending);
+
+    env.events().publish(
+        (Symbol::new(&env, "transfer_offered"), member),
+        (token_id, recipient),
+    );
+
+    Ok(())
+}
+
+/// Farm operator approves or rejects a pending transfer
+pub fn review_transfer(
+    env: Env,
+    token_id: BytesN<32>,
+    approve: bool,
+    operator: Address,
+) -> Result<(), Error> {
+    let membership = load_membership(&env, &token_id)?;
+    crate::inventory::require_farm_operator(&env, &membership.farm_id, &operator)?;
+
+    let mut pending = get_pending_transfer(&env, &token_id).ok_or(Error::TransferNotFound)?;
+    if approve {
+        pending.approved = true;
+        save_pending(&env, &token_id, &pending);
+    } else {
+        remove_pending(&env, &token_id);
+    }
+
+    env.events().publish(
+        (Symbol::new(&env, "transfer_reviewed"), operator),
+        (token_id, approve),
+    );
+
+    Ok(())
+}
+
+/// The current member withdraws an offer that has not been accepted yet
+pub fn cancel_transfer(env: Env, token_id: BytesN<32>, member: Address) -> Result<(), Error> {
+    member.require_auth();
+
+    let pending = get_pending_transfer(&env, &token_id).ok_or(Error::TransferNotFound)?;
+    if pending.from != member {
+        return Err(Error::NotAuthorized);
+    }
+    remove_pending(&env, &token_id);
+
+    env.events()
+        .publish((Symbol::new(&env, "transfer_cancelled"), member), token_id);
+
+    Ok(())
+}
+
+/// The recipient takes over the membership, paying the farm's transfer fee.
+/// Escrowed payments, remaining installments and the distribution history stay with the share.
+pub fn accept_transfer(env: Env, token_id: BytesN<32>, recipient: Address) -> Result<(), Error> {
+    recipient.require_auth();
+
+    let mut membership = load_membership(&env, &token_id)?;
+    let pending = get_pending_transfer(&env, &token_id).ok_or(Error::TransferNotFound)?;
+    if pending.to != recipient {
+        return Err(Error::NotAuthorized);
+    }
+    if !pending.approved {
+        return Err(Error::TransferNotApproved);
+    }
+    ensure_transferable(&env, &token_id, &membership)?;
+
+    let policy = get_transfer_policy(&env, &membership.farm_id);
+    if let (true, Some(fee_token)) = (policy.fee > 0, &policy.fee_token) {
+        let operator = crate::inventory::get_farm_operator(&env, &membership.farm_id)
+            .ok_or(Error::FarmNotRegistered)?;
+        token::Client::new(&env, fee_token).transfer(&recipient, &operator, &policy.fee);
+    }
+
+    membership.member = recipient.clone();
+    env.storage()
+        .persistent()
+        .set(&DataKey::Membership(token_id.clone()), &membership);
+    remove_pending(&env, &token_id);
+
+    env.events().publish(
+        (Symbol::new(&env, "membership_transferred"), pending.from),
+        (token_id, recipient, policy.fee),
+    );
+
+    Ok(())
+}
+
+/// Shares can change hands until the season ends, but not while payments are overdue
+fn ensure_transferable(
+    env: &Env,
+    token_id: &BytesN<32>,
+    membership: &CSAMembership,
+) -> Result<(), Error> {
+    if env.ledger().timestamp() >= membership.end_date {
+        return Err(Error::SeasonEnded);
+    }
+    if crate::payment::is_suspended(env, token_id) {
+        return Err(Error::MembershipSuspended);
+    }
+    Ok(())
+}
+
+fn load_membership(env: &Env, token_id: &BytesN<32>) -> Result<CSAMembership, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Membership(token_id.clone()))
+        .ok_or(Error::NotFound)
+}
+
+fn save_pending(env: &Env, token_id: &BytesN<32>, pending: &PendingTransfer) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingTransfer(token_id.clone()), pending);
+}
+
+fn remove_pending(env: &Env, token_id: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingTransfer(token_id.clone()));
+}
@@ -1,44 +1,46 @@
-use crate::LoyaltyProgram;
+use crate::{
+    points::credit_points,
+    program::require_issuer,
+    tiers::{get_member_tier, record_earning, BASE_MULTIPLIER},
+};
 use soroban_sdk::{Address, BytesN, Env, Symbol};
 
 pub fn award_points(
     env: &Env,
+    issuer: Address,
     program_id: BytesN<32>,
     user_address: Address,
     transaction_amount: u32,
 ) {
-    let program_key = (Symbol::new(env, "program"), program_id.clone());
+    let program = require_issuer(env, &program_id, &issuer);
 
-    let program: LoyaltyProgram = env
-        .storage()
-        .persistent()
-        .get::<(Symbol, BytesN<32>), LoyaltyProgram>(&program_key)
-        .expect("Program not found");
-
-    let points_to_award = (program.points_per_transaction as u64) * (transaction_amount as u64);
-
-    let points_key = (
-        Symbol::new(env, "points"),
-        program_id.clone(),
-        user_address.clone(),
-    );
-
-    let current_points: u64 = env
-        .storage()
-        .persistent()
-        .get::<(Symbol, BytesN<32>, Address), u64>(&points_key)
-        .unwrap_or(0);
-
-    let new_points = current_points + points_to_award;
+    let base_points = (program.points_per_transaction as u64) * (transaction_amount as u64);
+    grant_points(env, &program_id, &user_address, base_points);
+}
 
-    env.storage().persistent().set(&points_key, &new_points);
+/// Scales base points by the member's tier, then credits and records them
+pub fn grant_points(
+    env: &Env,
+    program_id: &BytesN<32>,
+    user_address: &Address,
+    base_points: u64,
+) -> u64 {
+    let tier = get_member_tier(env, program_id.clone(), user_address.clone());
+    // Uncapped rules can hand in u64::MAX base points, so scale in u128 and saturate
+    let scaled = base_points as u128 * tier.multiplier as u128 / BASE_MULTIPLIER as u128;
+    let points_to_award = u64::try_from(scaled).unwrap_or(u64::MAX);
+
+    credit_points(env, program_id, user_address, points_to_award);
+    record_earning(env, program_id, user_address, points_to_award);
 
     env.events().publish(
         (
             Symbol::new(env, "points_awarded"),
             program_id.clone(),
-            user_address,
+            user_address.clone(),
         ),
         points_to_award,
     );
+
+    points_to_award
 }
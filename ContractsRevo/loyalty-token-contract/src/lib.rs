@@ -2,9 +2,13 @@
 use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, String, Vec};
 
 mod earn;
+mod points;
 mod program;
 mod redeem;
 mod rewards;
+mod settlement;
+mod tiers;
+mod voucher;
 
 #[cfg(test)]
 mod tests;
@@ -12,6 +16,7 @@ mod tests;
 #[contracttype]
 pub struct LoyaltyProgram {
     pub program_id: BytesN<32>,
+    pub owner: Address,
     pub points_per_transaction: u32,
     pub redemption_options: Vec<RedemptionOption>,
 }
@@ -25,6 +30,83 @@ pub struct RedemptionOption {
     pub available_quantity: u32,
 }
 
+/// Points earned together, spent oldest first
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointLot {
+    pub amount: u64,
+    pub earned_at: u64,
+    pub expires_at: u64, // 0 if the points never expire
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tier {
+    Bronze = 0,
+    Silver = 1,
+    Gold = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TierRule {
+    pub tier: Tier,
+    pub min_points: u64, // Points earned over the last 12 months
+    pub multiplier: u32, // Earning multiplier in basis points (10_000 = 1x)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgramSettings {
+    pub point_validity: u64, // Seconds before earned points expire, 0 to never expire
+    pub transfers_enabled: bool,
+    pub tiers: Vec<TierRule>,
+    pub voucher_validity: u64, // Seconds a voucher can be collected, 0 for no limit
+    pub refund_expired_vouchers: bool, // Return the spent points when an expired voucher is closed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EarningBucket {
+    pub month: u64,
+    pub points: u64,
+}
+
+/// How settlements reported by one source contract turn into points
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EarningRule {
+    pub points_per_unit: u32,
+    pub unit_amount: u64,  // Settlement amount that earns `points_per_unit`
+    pub bonus_points: u64, // Flat points per settlement, e.g. for an enrollment
+    pub max_points: u64,   // Cap per settlement before tier scaling, 0 for no cap
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VoucherStatus {
+    Issued,
+    Fulfilled,
+    Cancelled,
+    Expired,
+}
+
+/// Proof of a redemption that the merchant still has to honour
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Voucher {
+    pub code_hash: BytesN<32>,
+    pub program_id: BytesN<32>,
+    pub user: Address,
+    pub redemption_option_id: u32,
+    pub points_spent: u64,
+    pub spent_lots: Vec<PointLot>, // Refunded with their original expiry on cancellation
+    pub status: VoucherStatus,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub settled_by: Option<Address>,
+}
+
 #[contract]
 pub struct LoyaltyContract;
 
@@ -32,25 +114,152 @@ pub struct LoyaltyContract;
 impl LoyaltyContract {
     pub fn create_loyalty_program(
         env: Env,
+        owner: Address,
         program_id: BytesN<32>,
         points_per_transaction: u32,
         redemption_options: Vec<RedemptionOption>,
     ) {
         program::create_loyalty_program(
             &env,
+            owner,
             program_id,
             points_per_transaction,
             redemption_options,
         );
     }
 
+    pub fn set_issuer(
+        env: Env,
+        owner: Address,
+        program_id: BytesN<32>,
+        issuer: Address,
+        authorized: bool,
+    ) {
+        program::set_issuer(&env, owner, program_id, issuer, authorized);
+    }
+
+    pub fn is_issuer(env: Env, program_id: BytesN<32>, issuer: Address) -> bool {
+        program::is_issuer(&env, program_id, issuer)
+    }
+
+    pub fn transfer_program_ownership(
+        env: Env,
+        owner: Address,
+        program_id: BytesN<32>,
+        new_owner: Address,
+    ) {
+        program::transfer_ownership(&env, owner, program_id, new_owner);
+    }
+
+    pub fn update_points_rate(
+        env: Env,
+        owner: Address,
+        program_id: BytesN<32>,
+        points_per_transaction: u32,
+    ) {
+        program::update_points_rate(&env, owner, program_id, points_per_transaction);
+    }
+
+    pub fn set_redemption_option(
+        env: Env,
+        owner: Address,
+        program_id: BytesN<32>,
+        option: RedemptionOption,
+    ) {
+        program::set_redemption_option(&env, owner, program_id, option);
+    }
+
+    pub fn restock_reward(
+        env: Env,
+        issuer: Address,
+        program_id: BytesN<32>,
+        redemption_option_id: u32,
+        quantity: u32,
+    ) {
+        program::restock_reward(&env, issuer, program_id, redemption_option_id, quantity);
+    }
+
     pub fn award_points(
         env: Env,
+        issuer: Address,
         program_id: BytesN<32>,
         user_address: Address,
         transaction_amount: u32,
     ) {
-        earn::award_points(&env, program_id, user_address, transaction_amount);
+        earn::award_points(&env, issuer, program_id, user_address, transaction_amount);
+    }
+
+    pub fn set_earning_rule(
+        env: Env,
+        owner: Address,
+        program_id: BytesN<32>,
+        source: Address,
+        rule: EarningRule,
+    ) {
+        settlement::set_earning_rule(&env, owner, program_id, source, rule);
+    }
+
+    pub fn remove_earning_rule(env: Env, owner: Address, program_id: BytesN<32>, source: Address) {
+        settlement::remove_earning_rule(&env, owner, program_id, source);
+    }
+
+    pub fn get_earning_rule(env: Env, program_id: BytesN<32>, source: Address) -> EarningRule {
+        settlement::get_earning_rule(&env, program_id, source)
+    }
+
+    /// Settlement hook for marketplace contracts such as auctions, NFT sales or CSA enrollment
+    pub fn on_settlement(
+        env: Env,
+        source: Address,
+        program_id: BytesN<32>,
+        user_address: Address,
+        amount: i128,
+        reference: BytesN<32>,
+    ) -> u64 {
+        settlement::on_settlement(&env, source, program_id, user_address, amount, reference)
+    }
+
+    pub fn set_program_settings(
+        env: Env,
+        owner: Address,
+        program_id: BytesN<32>,
+        settings: ProgramSettings,
+    ) {
+        program::set_program_settings(&env, owner, program_id, settings);
+    }
+
+    pub fn get_program_settings(env: Env, program_id: BytesN<32>) -> ProgramSettings {
+        points::get_settings(&env, program_id)
+    }
+
+    pub fn get_points_balance(env: Env, program_id: BytesN<32>, user_address: Address) -> u64 {
+        points::get_points_balance(&env, program_id, user_address)
+    }
+
+    pub fn get_point_lots(
+        env: Env,
+        program_id: BytesN<32>,
+        user_address: Address,
+    ) -> Vec<PointLot> {
+        points::get_point_lots(&env, program_id, user_address)
+    }
+
+    pub fn get_member_tier(env: Env, program_id: BytesN<32>, user_address: Address) -> Tier {
+        tiers::get_member_tier(&env, program_id, user_address).tier
+    }
+
+    pub fn get_rolling_earnings(env: Env, program_id: BytesN<32>, user_address: Address) -> u64 {
+        tiers::get_rolling_earnings(&env, program_id, user_address)
+    }
+
+    pub fn transfer_points(
+        env: Env,
+        program_id: BytesN<32>,
+        from: Address,
+        to: Address,
+        amount: u64,
+    ) {
+        points::transfer_points(&env, program_id, from, to, amount);
     }
 
     pub fn redeem_reward(
@@ -58,8 +267,43 @@ impl LoyaltyContract {
         program_id: BytesN<32>,
         user_address: Address,
         redemption_option_id: u32,
-    ) {
-        redeem::redeem_reward(&env, program_id, user_address, redemption_option_id);
+    ) -> BytesN<32> {
+        redeem::redeem_reward(&env, program_id, user_address, redemption_option_id)
+    }
+
+    pub fn get_voucher(env: Env, code_hash: BytesN<32>) -> Voucher {
+        voucher::get_voucher(&env, code_hash)
+    }
+
+    pub fn get_user_vouchers(
+        env: Env,
+        program_id: BytesN<32>,
+        user_address: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Voucher> {
+        voucher::get_user_vouchers(&env, program_id, user_address, start, limit)
+    }
+
+    pub fn get_outstanding_vouchers(
+        env: Env,
+        program_id: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Voucher> {
+        voucher::get_outstanding_vouchers(&env, program_id, start, limit)
+    }
+
+    pub fn fulfill_voucher(env: Env, issuer: Address, code_hash: BytesN<32>) {
+        voucher::fulfill_voucher(&env, issuer, code_hash);
+    }
+
+    pub fn cancel_voucher(env: Env, caller: Address, code_hash: BytesN<32>) {
+        voucher::cancel_voucher(&env, caller, code_hash);
+    }
+
+    pub fn expire_voucher(env: Env, caller: Address, code_hash: BytesN<32>) {
+        voucher::expire_voucher(&env, caller, code_hash);
     }
 
     pub fn get_program_info(env: Env, program_id: BytesN<32>) -> LoyaltyProgram {
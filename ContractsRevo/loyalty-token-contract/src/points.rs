@@ -0,0 +1,195 @@
+use crate::{program::get_program_info, PointLot, ProgramSettings};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+pub fn get_settings(env: &Env, program_id: BytesN<32>) -> ProgramSettings {
+    let key = (Symbol::new(env, "settings"), program_id);
+    env.storage()
+        .persistent()
+        .get::<(Symbol, BytesN<32>), ProgramSettings>(&key)
+        .unwrap_or_else(|| ProgramSettings {
+            point_validity: 0,
+            transfers_enabled: false,
+            tiers: Vec::new(env),
+            voucher_validity: 0,
+            refund_expired_vouchers: true,
+        })
+}
+
+/// Unexpired point lots, oldest first
+pub fn get_point_lots(env: &Env, program_id: BytesN<32>, user_address: Address) -> Vec<PointLot> {
+    let now = env.ledger().timestamp();
+    let mut lots = Vec::new(env);
+    for lot in load_lots(env, &program_id, &user_address).iter() {
+        if !is_expired(&lot, now) {
+            lots.push_back(lot);
+        }
+    }
+    lots
+}
+
+pub fn get_points_balance(env: &Env, program_id: BytesN<32>, user_address: Address) -> u64 {
+    get_point_lots(env, program_id, user_address)
+        .iter()
+        .map(|lot| lot.amount)
+        .sum()
+}
+
+/// Credits points that expire after the program's validity period.
+/// Points earned on the same day share a lot.
+pub fn credit_points(env: &Env, program_id: &BytesN<32>, user_address: &Address, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+
+    let settings = get_settings(env, program_id.clone());
+    let now = env.ledger().timestamp();
+    let expires_at = if settings.point_validity == 0 {
+        0
+    } else {
+        // Round up to the end of the day so a day's earnings merge into one lot
+        let expiry = now + settings.point_validity;
+        expiry.div_ceil(SECONDS_PER_DAY) * SECONDS_PER_DAY
+    };
+
+    insert_lot(
+        env,
+        program_id,
+        user_address,
+        PointLot {
+            amount,
+            earned_at: now,
+            expires_at,
+        },
+    );
+}
+
+/// Removes `amount` points, oldest lots first, returning the consumed pieces
+pub fn debit_points(
+    env: &Env,
+    program_id: &BytesN<32>,
+    user_address: &Address,
+    amount: u64,
+) -> Vec<PointLot> {
+    let lots = get_point_lots(env, program_id.clone(), user_address.clone());
+    let balance: u64 = lots.iter().map(|lot| lot.amount).sum();
+    if balance < amount {
+        panic!("Insufficient points");
+    }
+
+    let mut remaining = amount;
+    let mut kept = Vec::new(env);
+    let mut consumed = Vec::new(env);
+    for mut lot in lots.iter() {
+        if remaining == 0 {
+            kept.push_back(lot);
+            continue;
+        }
+
+        let taken = lot.amount.min(remaining);
+        remaining -= taken;
+        consumed.push_back(PointLot {
+            amount: taken,
+            earned_at: lot.earned_at,
+            expires_at: lot.expires_at,
+        });
+
+        lot.amount -= taken;
+        if lot.amount > 0 {
+            kept.push_back(lot);
+        }
+    }
+
+    save_lots(env, program_id, user_address, &kept);
+    consumed
+}
+
+/// Moves points between users, keeping each lot's original expiry
+pub fn transfer_points(env: &Env, program_id: BytesN<32>, from: Address, to: Address, amount: u64) {
+    from.require_auth();
+
+    get_program_info(env, program_id.clone());
+    if !get_settings(env, program_id.clone()).transfers_enabled {
+        panic!("Point transfers are disabled");
+    }
+    if from == to || amount == 0 {
+        panic!("Invalid transfer");
+    }
+
+    let moved = debit_points(env, &program_id, &from, amount);
+    restore_lots(env, &program_id, &to, &moved);
+
+    env.events().publish(
+        (Symbol::new(env, "points_transferred"), program_id, from),
+        (to, amount),
+    );
+}
+
+/// Returns previously spent lots to a user, keeping their original expiry
+pub fn restore_lots(
+    env: &Env,
+    program_id: &BytesN<32>,
+    user_address: &Address,
+    lots: &Vec<PointLot>,
+) {
+    for lot in lots.iter() {
+        insert_lot(env, program_id, user_address, lot);
+    }
+}
+
+fn is_expired(lot: &PointLot, now: u64) -> bool {
+    lot.expires_at != 0 && lot.expires_at <= now
+}
+
+/// Inserts a lot keeping the list ordered by expiry, never-expiring lots last
+fn insert_lot(env: &Env, program_id: &BytesN<32>, user_address: &Address, lot: PointLot) {
+    let sort_key = |lot: &PointLot| {
+        if lot.expires_at == 0 {
+            u64::MAX
+        } else {
+            lot.expires_at
+        }
+    };
+
+    let mut lots = get_point_lots(env, program_id.clone(), user_address.clone());
+    let mut index = lots.len();
+    for (i, existing) in lots.iter().enumerate() {
+        if existing.expires_at == lot.expires_at {
+            let mut merged = existing.clone();
+            merged.amount = merged.amount.saturating_add(lot.amount);
+            merged.earned_at = merged.earned_at.min(lot.earned_at);
+            lots.set(i as u32, merged);
+            save_lots(env, program_id, user_address, &lots);
+            return;
+        }
+        if sort_key(&existing) > sort_key(&lot) {
+            index = i as u32;
+            break;
+        }
+    }
+
+    lots.insert(index, lot);
+    save_lots(env, program_id, user_address, &lots);
+}
+
+fn load_lots(env: &Env, program_id: &BytesN<32>, user_address: &Address) -> Vec<PointLot> {
+    let key = (
+        Symbol::new(env, "lots"),
+        program_id.clone(),
+        user_address.clone(),
+    );
+    env.storage()
+        .persistent()
+        .get::<(Symbol, BytesN<32>, Address), Vec<PointLot>>(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn save_lots(env: &Env, program_id: &BytesN<32>, user_address: &Address, lots: &Vec<PointLot>) {
+    let key = (
+        Symbol::new(env, "lots"),
+        program_id.clone(),
+        user_address.clone(),
+    );
+    env.storage().persistent().set(&key, lots);
+}
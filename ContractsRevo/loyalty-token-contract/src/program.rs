@@ -1,22 +1,29 @@
-use crate::{LoyaltyProgram, RedemptionOption};
-use soroban_sdk::{BytesN, Env, Symbol, Vec};
+use crate::{tiers::validate_tiers, LoyaltyProgram, ProgramSettings, RedemptionOption};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
 
 pub fn create_loyalty_program(
     env: &Env,
+    owner: Address,
     program_id: BytesN<32>,
     points_per_transaction: u32,
     redemption_options: Vec<RedemptionOption>,
 ) {
+    owner.require_auth();
+
     let key = (Symbol::new(env, "program"), program_id.clone());
     if env.storage().persistent().has(&key) {
         panic!("Program already exists");
     }
     let program = LoyaltyProgram {
-        program_id,
+        program_id: program_id.clone(),
+        owner: owner.clone(),
         points_per_transaction,
         redemption_options,
     };
     env.storage().persistent().set(&key, &program);
+
+    env.events()
+        .publish((Symbol::new(env, "program_created"), program_id), owner);
 }
 
 pub fn get_program_info(env: &Env, program_id: BytesN<32>) -> LoyaltyProgram {
@@ -26,3 +33,178 @@ pub fn get_program_info(env: &Env, program_id: BytesN<32>) -> LoyaltyProgram {
         .get::<(Symbol, BytesN<32>), LoyaltyProgram>(&key)
         .expect("Program not found")
 }
+
+pub fn save_program(env: &Env, program: &LoyaltyProgram) {
+    let key = (Symbol::new(env, "program"), program.program_id.clone());
+    env.storage().persistent().set(&key, program);
+}
+
+/// Loads a program and checks that `caller` is its owner
+pub fn require_owner(env: &Env, program_id: &BytesN<32>, caller: &Address) -> LoyaltyProgram {
+    caller.require_auth();
+
+    let program = get_program_info(env, program_id.clone());
+    if program.owner != *caller {
+        panic!("Not authorized");
+    }
+    program
+}
+
+/// Loads a program and checks that `caller` is its owner or an authorized issuer
+pub fn require_issuer(env: &Env, program_id: &BytesN<32>, caller: &Address) -> LoyaltyProgram {
+    caller.require_auth();
+
+    let program = get_program_info(env, program_id.clone());
+    if program.owner != *caller && !is_issuer(env, program_id.clone(), caller.clone()) {
+        panic!("Not authorized");
+    }
+    program
+}
+
+pub fn is_issuer(env: &Env, program_id: BytesN<32>, issuer: Address) -> bool {
+    let key = (Symbol::new(env, "issuer"), program_id, issuer);
+    env.storage()
+        .persistent()
+        .get::<(Symbol, BytesN<32>, Address), bool>(&key)
+        .unwrap_or(false)
+}
+
+pub fn set_issuer(
+    env: &Env,
+    owner: Address,
+    program_id: BytesN<32>,
+    issuer: Address,
+    authorized: bool,
+) {
+    require_owner(env, &program_id, &owner);
+
+    let key = (
+        Symbol::new(env, "issuer"),
+        program_id.clone(),
+        issuer.clone(),
+    );
+    if authorized {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "issuer_updated"), program_id),
+        (issuer, authorized),
+    );
+}
+
+pub fn transfer_ownership(env: &Env, owner: Address, program_id: BytesN<32>, new_owner: Address) {
+    let mut program = require_owner(env, &program_id, &owner);
+    new_owner.require_auth();
+
+    program.owner = new_owner.clone();
+    save_program(env, &program);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "program_ownership_transferred"),
+            program_id,
+        ),
+        (owner, new_owner),
+    );
+}
+
+pub fn update_points_rate(
+    env: &Env,
+    owner: Address,
+    program_id: BytesN<32>,
+    points_per_transaction: u32,
+) {
+    let mut program = require_owner(env, &program_id, &owner);
+
+    program.points_per_transaction = points_per_transaction;
+    save_program(env, &program);
+
+    env.events().publish(
+        (Symbol::new(env, "points_rate_updated"), program_id),
+        points_per_transaction,
+    );
+}
+
+/// Adds a redemption option, or replaces the one with the same ID
+pub fn set_redemption_option(
+    env: &Env,
+    owner: Address,
+    program_id: BytesN<32>,
+    option: RedemptionOption,
+) {
+    let mut program = require_owner(env, &program_id, &owner);
+
+    match program
+        .redemption_options
+        .iter()
+        .position(|opt| opt.id == option.id)
+    {
+        Some(index) => program
+            .redemption_options
+            .set(index.try_into().unwrap(), option.clone()),
+        None => program.redemption_options.push_back(option.clone()),
+    }
+    save_program(env, &program);
+
+    env.events().publish(
+        (Symbol::new(env, "redemption_option_set"), program_id),
+        option.id,
+    );
+}
+
+pub fn restock_reward(
+    env: &Env,
+    issuer: Address,
+    program_id: BytesN<32>,
+    redemption_option_id: u32,
+    quantity: u32,
+) {
+    let mut program = require_issuer(env, &program_id, &issuer);
+
+    let option_index = program
+        .redemption_options
+        .iter()
+        .position(|opt| opt.id == redemption_option_id)
+        .expect("Redemption option not found");
+
+    let mut option = program
+        .redemption_options
+        .get(option_index.try_into().unwrap())
+        .expect("Redemption option not found");
+
+    option.available_quantity = option
+        .available_quantity
+        .checked_add(quantity)
+        .expect("Quantity overflow");
+    program
+        .redemption_options
+        .set(option_index.try_into().unwrap(), option.clone());
+    save_program(env, &program);
+
+    env.events().publish(
+        (Symbol::new(env, "reward_restocked"), program_id, issuer),
+        (redemption_option_id, option.available_quantity),
+    );
+}
+
+/// Configures point expiry, transfers and membership tiers
+pub fn set_program_settings(
+    env: &Env,
+    owner: Address,
+    program_id: BytesN<32>,
+    settings: ProgramSettings,
+) {
+    require_owner(env, &program_id, &owner);
+    validate_tiers(&settings.tiers);
+
+    let key = (Symbol::new(env, "settings"), program_id.clone());
+    env.storage().persistent().set(&key, &settings);
+
+    env.events().publish(
+        (Symbol::new(env, "program_settings_updated"), program_id),
+        (settings.point_validity, settings.transfers_enabled),
+    );
+}
@@ -1,4 +1,4 @@
-use crate::LoyaltyProgram;
+use crate::{points::debit_points, voucher::issue_voucher, LoyaltyProgram};
 use soroban_sdk::{Address, BytesN, Env, Symbol};
 
 pub fn redeem_reward(
@@ -6,7 +6,9 @@ pub fn redeem_reward(
     program_id: BytesN<32>,
     user_address: Address,
     redemption_option_id: u32,
-) {
+) -> BytesN<32> {
+    user_address.require_auth();
+
     let program_key = (Symbol::new(env, "program"), program_id.clone());
 
     let mut program: LoyaltyProgram = env
@@ -15,18 +17,6 @@ pub fn redeem_reward(
         .get::<(Symbol, BytesN<32>), LoyaltyProgram>(&program_key)
         .expect("Program not found");
 
-    let points_key = (
-        Symbol::new(env, "points"),
-        program_id.clone(),
-        user_address.clone(),
-    );
-
-    let user_points: u64 = env
-        .storage()
-        .persistent()
-        .get::<(Symbol, BytesN<32>, Address), u64>(&points_key)
-        .unwrap_or(0);
-
     let option_index = program
         .redemption_options
         .iter()
@@ -41,12 +31,14 @@ pub fn redeem_reward(
     if option.available_quantity == 0 {
         panic!("Reward is out of stock");
     }
-    if user_points < option.points_required as u64 {
-        panic!("Insufficient points");
-    }
 
-    let new_points = user_points - option.points_required as u64;
-    env.storage().persistent().set(&points_key, &new_points);
+    // Spend the oldest points first
+    let spent_lots = debit_points(
+        env,
+        &program_id,
+        &user_address,
+        option.points_required as u64,
+    );
 
     option.available_quantity -= 1;
     program
@@ -55,12 +47,22 @@ pub fn redeem_reward(
 
     env.storage().persistent().set(&program_key, &program);
 
+    let code_hash = issue_voucher(
+        env,
+        &program_id,
+        &user_address,
+        redemption_option_id,
+        spent_lots,
+    );
+
     env.events().publish(
         (
             Symbol::new(env, "reward_redeemed"),
             program_id,
             user_address,
         ),
-        redemption_option_id,
+        (redemption_option_id, code_hash.clone()),
     );
+
+    code_hash
 }
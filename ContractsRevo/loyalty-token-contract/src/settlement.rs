@@ -0,0 +1,99 @@
+use crate::{earn::grant_points, program::require_owner, EarningRule};
+use soroban_sdk::{Address, BytesN, Env, Symbol};
+
+/// Lets a marketplace contract earn points for its settlements.
+/// Having a rule is what authorizes the source to call `on_settlement`.
+pub fn set_earning_rule(
+    env: &Env,
+    owner: Address,
+    program_id: BytesN<32>,
+    source: Address,
+    rule: EarningRule,
+) {
+    require_owner(env, &program_id, &owner);
+    if rule.unit_amount == 0 {
+        panic!("Invalid earning rule");
+    }
+
+    let key = (
+        Symbol::new(env, "earn_rule"),
+        program_id.clone(),
+        source.clone(),
+    );
+    env.storage().persistent().set(&key, &rule);
+
+    env.events().publish(
+        (Symbol::new(env, "earning_rule_set"), program_id, source),
+        rule,
+    );
+}
+
+pub fn remove_earning_rule(env: &Env, owner: Address, program_id: BytesN<32>, source: Address) {
+    require_owner(env, &program_id, &owner);
+
+    let key = (
+        Symbol::new(env, "earn_rule"),
+        program_id.clone(),
+        source.clone(),
+    );
+    env.storage().persistent().remove(&key);
+
+    env.events().publish(
+        (Symbol::new(env, "earning_rule_removed"), program_id),
+        source,
+    );
+}
+
+pub fn get_earning_rule(env: &Env, program_id: BytesN<32>, source: Address) -> EarningRule {
+    let key = (Symbol::new(env, "earn_rule"), program_id, source);
+    env.storage()
+        .persistent()
+        .get::<(Symbol, BytesN<32>, Address), EarningRule>(&key)
+        .expect("Earning rule not found")
+}
+
+/// Called by a source contract when a sale settles. The reference is the
+/// source's own settlement id, so each settlement only earns once.
+pub fn on_settlement(
+    env: &Env,
+    source: Address,
+    program_id: BytesN<32>,
+    user_address: Address,
+    amount: i128,
+    reference: BytesN<32>,
+) -> u64 {
+    source.require_auth();
+
+    if amount < 0 {
+        panic!("Invalid amount");
+    }
+    let rule = get_earning_rule(env, program_id.clone(), source.clone());
+
+    let settled_key = (
+        Symbol::new(env, "settled"),
+        program_id.clone(),
+        source.clone(),
+        reference.clone(),
+    );
+    if env.storage().persistent().has(&settled_key) {
+        panic!("Settlement already processed");
+    }
+    env.storage().persistent().set(&settled_key, &true);
+
+    let units = u64::try_from(amount / rule.unit_amount as i128).unwrap_or(u64::MAX);
+    let mut base_points = units
+        .saturating_mul(rule.points_per_unit as u64)
+        .saturating_add(rule.bonus_points);
+    if rule.max_points > 0 && base_points > rule.max_points {
+        base_points = rule.max_points;
+    }
+
+    let awarded = grant_points(env, &program_id, &user_address, base_points);
+
+    env.events().publish(
+        (Symbol::new(env, "settlement_earned"), program_id, source),
+        (user_address, reference, awarded),
+    );
+
+    awarded
+}
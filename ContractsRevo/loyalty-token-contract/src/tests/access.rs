@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+use super::utils::*;
+use crate::{LoyaltyContractClient, RedemptionOption};
+use soroban_sdk::{testutils::Address as _, Address, String};
+
+fn setup_client(
+    env: &soroban_sdk::Env,
+    contract_address: &Address,
+) -> LoyaltyContractClient<'static> {
+    LoyaltyContractClient::new(env, contract_address)
+}
+
+#[test]
+fn test_program_records_owner() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+
+    assert_eq!(client.get_program_info(&program_id).owner, owner);
+}
+
+#[test]
+fn test_authorized_issuer_awards_points() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let user = create_user(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &2, &create_basic_rewards(&env));
+    client.set_issuer(&owner, &program_id, &merchant, &true);
+    assert!(client.is_issuer(&program_id, &merchant));
+
+    client.award_points(&merchant, &program_id, &user, &50);
+
+    let points = env.as_contract(&contract_address, || {
+        get_user_points(&env, program_id.clone(), user.clone())
+    });
+    assert_eq!(points, 100);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_unauthorized_award_rejected() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let user = create_user(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+
+    // Users cannot mint points to themselves
+    client.award_points(&user, &program_id, &user, &1000);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_removed_issuer_rejected() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let user = create_user(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+    client.set_issuer(&owner, &program_id, &merchant, &true);
+    client.set_issuer(&owner, &program_id, &merchant, &false);
+    assert!(!client.is_issuer(&program_id, &merchant));
+
+    client.award_points(&merchant, &program_id, &user, &10);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_issuer_cannot_edit_program() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let merchant = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+    client.set_issuer(&owner, &program_id, &merchant, &true);
+
+    client.update_points_rate(&merchant, &program_id, &100);
+}
+
+#[test]
+fn test_owner_edits_program() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+    client.update_points_rate(&owner, &program_id, &5);
+
+    // Replace an existing option and add a new one
+    client.set_redemption_option(
+        &owner,
+        &program_id,
+        &RedemptionOption {
+            id: 2,
+            name: String::from_str(&env, "Bigger Discount"),
+            points_required: 150,
+            available_quantity: 5,
+        },
+    );
+    client.set_redemption_option(
+        &owner,
+        &program_id,
+        &RedemptionOption {
+            id: 4,
+            name: String::from_str(&env, "Farm Tour"),
+            points_required: 800,
+            available_quantity: 3,
+        },
+    );
+
+    let program = client.get_program_info(&program_id);
+    assert_eq!(program.points_per_transaction, 5);
+    assert_eq!(program.redemption_options.len(), 4);
+    let discount = program
+        .redemption_options
+        .iter()
+        .find(|opt| opt.id == 2)
+        .unwrap();
+    assert_eq!(discount.points_required, 150);
+}
+
+#[test]
+fn test_issuer_restocks_reward() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let merchant = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_rewards(&env));
+    client.set_issuer(&owner, &program_id, &merchant, &true);
+    client.restock_reward(&merchant, &program_id, &1, &4);
+
+    let rewards = client.list_available_rewards(&program_id);
+    assert_reward_quantity(&rewards, 1, 5);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_unauthorized_restock_rejected() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_rewards(&env));
+    client.restock_reward(&stranger, &program_id, &1, &100);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_ownership_transfer() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_rewards(&env));
+    client.transfer_program_ownership(&owner, &program_id, &new_owner);
+
+    client.update_points_rate(&new_owner, &program_id, &3);
+    assert_eq!(
+        client.get_program_info(&program_id).points_per_transaction,
+        3
+    );
+
+    // The previous owner has lost control
+    client.update_points_rate(&owner, &program_id, &1);
+}
+
+#[test]
+fn test_redeem_requires_user_auth() {
+    let (env, contract_address, program_id) = setup_test();
+    let client = setup_client(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let user = create_user(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_rewards(&env));
+    client.award_points(&owner, &program_id, &user, &200);
+    client.redeem_reward(&program_id, &user, &2);
+
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, user);
+}
@@ -9,8 +9,20 @@ fn test_award_points_after_transaction() {
     let user = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 50);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            50,
+        );
         // Points per transaction = 1, amount = 50, expect 50 points
 
         let points: u64 = get_user_points(&env, program_id, user.clone());
@@ -24,8 +36,20 @@ fn test_dynamic_point_rate_handling() {
     let user = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 2, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 50);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            2,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            50,
+        );
         // Points per transaction = 2, amount = 50, expect 100 points
         let points: u64 = get_user_points(&env, program_id, user.clone());
 
@@ -39,9 +63,27 @@ fn test_accurate_user_point_balances() {
     let user = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 100);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 50);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            100,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            50,
+        );
         // User should have 150 points
         let points: u64 = get_user_points(&env, program_id, user.clone());
 
@@ -58,8 +100,20 @@ fn test_basic_point_awarding() {
     let rewards = create_basic_rewards(&env);
 
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 50);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            50,
+        );
 
         let points = get_user_points(&env, program_id, user);
         assert_eq!(points, 50);
@@ -81,12 +135,14 @@ fn test_dynamic_point_rates() {
         env.as_contract(&contract_address, || {
             LoyaltyContract::create_loyalty_program(
                 env.clone(),
+                program_owner(&env),
                 test_program_id.clone(),
                 *rate,
                 rewards.clone(),
             );
             LoyaltyContract::award_points(
                 env.clone(),
+                program_owner(&env),
                 test_program_id.clone(),
                 user.clone(),
                 *amount,
@@ -105,12 +161,36 @@ fn test_cumulative_point_accumulation() {
     let rewards = create_basic_rewards(&env);
 
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
 
         // Multiple transactions
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 50);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 30);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 20);
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            50,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            30,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            20,
+        );
 
         let points = get_user_points(&env, program_id, user);
         assert_eq!(points, 100);
@@ -1,4 +1,8 @@
+pub mod access;
 pub mod earn;
+pub mod points;
 pub mod program;
 pub mod redeem;
+pub mod settlement;
 pub mod utils;
+pub mod voucher;
@@ -0,0 +1,229 @@
+#![cfg(test)]
+
+use super::utils::*;
+use crate::{LoyaltyContractClient, ProgramSettings, Tier, TierRule};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, Vec,
+};
+
+const DAY: u64 = 86400;
+const MONTH: u64 = 30 * DAY;
+
+struct ProgramSetup {
+    client: LoyaltyContractClient<'static>,
+    owner: Address,
+    program_id: BytesN<32>,
+}
+
+fn setup_program(settings: impl FnOnce(&Env) -> ProgramSettings) -> (Env, ProgramSetup) {
+    let (env, contract_address, program_id) = setup_test();
+    let client = LoyaltyContractClient::new(&env, &contract_address);
+    let owner = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+    client.set_program_settings(&owner, &program_id, &settings(&env));
+
+    (
+        env,
+        ProgramSetup {
+            client,
+            owner,
+            program_id,
+        },
+    )
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+fn expiring(env: &Env, validity: u64) -> ProgramSettings {
+    ProgramSettings {
+        point_validity: validity,
+        transfers_enabled: false,
+        tiers: Vec::new(env),
+        voucher_validity: 0,
+        refund_expired_vouchers: true,
+    }
+}
+
+fn tiered(env: &Env) -> ProgramSettings {
+    ProgramSettings {
+        point_validity: 0,
+        transfers_enabled: false,
+        tiers: vec![
+            env,
+            TierRule {
+                tier: Tier::Bronze,
+                min_points: 0,
+                multiplier: 10_000,
+            },
+            TierRule {
+                tier: Tier::Silver,
+                min_points: 500,
+                multiplier: 12_500,
+            },
+            TierRule {
+                tier: Tier::Gold,
+                min_points: 2000,
+                multiplier: 15_000,
+            },
+        ],
+        voucher_validity: 0,
+        refund_expired_vouchers: true,
+    }
+}
+
+#[test]
+fn test_points_expire_after_validity() {
+    let (env, p) = setup_program(|env| expiring(env, 90 * DAY));
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    set_time(&env, 60 * DAY);
+    p.client.award_points(&p.owner, &p.program_id, &user, &50);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 150);
+
+    let lots = p.client.get_point_lots(&p.program_id, &user);
+    assert_eq!(lots.len(), 2);
+    assert_eq!(lots.get(0).unwrap().expires_at, 90 * DAY);
+
+    set_time(&env, 90 * DAY);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 50);
+}
+
+#[test]
+fn test_redeem_consumes_oldest_points_first() {
+    let (env, p) = setup_program(|env| expiring(env, 90 * DAY));
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &80);
+    set_time(&env, 10 * DAY);
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+
+    // Free Shipping costs 50: taken from the first lot
+    p.client.redeem_reward(&p.program_id, &user, &3);
+
+    let lots = p.client.get_point_lots(&p.program_id, &user);
+    assert_eq!(lots.get(0).unwrap().amount, 30);
+    assert_eq!(lots.get(1).unwrap().amount, 100);
+
+    // Once the first lot expires, only the newer points remain
+    set_time(&env, 90 * DAY);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 100);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient points")]
+fn test_expired_points_cannot_be_redeemed() {
+    let (env, p) = setup_program(|env| expiring(env, 30 * DAY));
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &200);
+    set_time(&env, 31 * DAY);
+    p.client.redeem_reward(&p.program_id, &user, &1);
+}
+
+#[test]
+fn test_tier_multipliers() {
+    let (env, p) = setup_program(tiered);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &500);
+    assert_eq!(p.client.get_member_tier(&p.program_id, &user), Tier::Silver);
+
+    // Silver earns 1.25x
+    p.client.award_points(&p.owner, &p.program_id, &user, &1200);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 2000);
+    assert_eq!(p.client.get_member_tier(&p.program_id, &user), Tier::Gold);
+
+    // Gold earns 1.5x
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 2150);
+}
+
+#[test]
+fn test_tier_uses_rolling_twelve_months() {
+    let (env, p) = setup_program(tiered);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &600);
+    set_time(&env, 6 * MONTH);
+    assert_eq!(p.client.get_member_tier(&p.program_id, &user), Tier::Silver);
+
+    // Earnings older than 12 months no longer count, but unexpired points stay
+    set_time(&env, 12 * MONTH);
+    assert_eq!(p.client.get_rolling_earnings(&p.program_id, &user), 0);
+    assert_eq!(p.client.get_member_tier(&p.program_id, &user), Tier::Bronze);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 600);
+}
+
+#[test]
+#[should_panic(expected = "Invalid tiers")]
+fn test_tiers_must_increase() {
+    setup_program(|env| ProgramSettings {
+        point_validity: 0,
+        transfers_enabled: false,
+        tiers: vec![
+            env,
+            TierRule {
+                tier: Tier::Gold,
+                min_points: 0,
+                multiplier: 15_000,
+            },
+            TierRule {
+                tier: Tier::Silver,
+                min_points: 500,
+                multiplier: 12_500,
+            },
+        ],
+        voucher_validity: 0,
+        refund_expired_vouchers: true,
+    });
+}
+
+#[test]
+fn test_transfer_keeps_expiry() {
+    let (env, p) = setup_program(|env| ProgramSettings {
+        point_validity: 90 * DAY,
+        transfers_enabled: true,
+        tiers: Vec::new(env),
+        voucher_validity: 0,
+        refund_expired_vouchers: true,
+    });
+    let sender = create_user(&env);
+    let recipient = create_user(&env);
+
+    p.client
+        .award_points(&p.owner, &p.program_id, &sender, &100);
+    set_time(&env, 10 * DAY);
+    p.client
+        .award_points(&p.owner, &p.program_id, &sender, &100);
+
+    p.client
+        .transfer_points(&p.program_id, &sender, &recipient, &150);
+
+    assert_eq!(p.client.get_points_balance(&p.program_id, &sender), 50);
+    let received = p.client.get_point_lots(&p.program_id, &recipient);
+    assert_eq!(received.len(), 2);
+    assert_eq!(received.get(0).unwrap().amount, 100);
+    assert_eq!(received.get(0).unwrap().expires_at, 90 * DAY);
+    assert_eq!(received.get(1).unwrap().amount, 50);
+
+    // Transfers do not count toward the recipient's tier
+    assert_eq!(p.client.get_rolling_earnings(&p.program_id, &recipient), 0);
+}
+
+#[test]
+#[should_panic(expected = "Point transfers are disabled")]
+fn test_transfers_disabled_by_default() {
+    let (env, p) = setup_program(|env| expiring(env, 0));
+    let sender = create_user(&env);
+    let recipient = create_user(&env);
+
+    p.client
+        .award_points(&p.owner, &p.program_id, &sender, &100);
+    p.client
+        .transfer_points(&p.program_id, &sender, &recipient, &50);
+}
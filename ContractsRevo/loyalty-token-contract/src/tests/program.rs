@@ -382,18 +382,26 @@ fn test_cross_program_insufficient_points() {
     env.as_contract(&contract_address, || {
         LoyaltyContract::create_loyalty_program(
             env.clone(),
+            program_owner(&env),
             program1,
             1,
             create_basic_rewards(&env),
         );
         LoyaltyContract::create_loyalty_program(
             env.clone(),
+            program_owner(&env),
             program2.clone(),
             3,
             create_premium_rewards(&env),
         );
 
-        LoyaltyContract::award_points(env.clone(), program2.clone(), user.clone(), 200);
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program2.clone(),
+            user.clone(),
+            200,
+        );
 
         // Should fail - user has 600 points in program2 but premium reward needs 1000
         LoyaltyContract::redeem_reward(env.clone(), program2, user, 1);
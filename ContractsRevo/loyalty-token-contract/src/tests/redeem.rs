@@ -9,8 +9,20 @@ fn test_reward_redemption_with_sufficient_points() {
     let user = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 200);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            200,
+        );
         // User has 200 points, redeem Gift Card (id=1, requires 200)
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 1);
         let points: u64 = get_user_points(&env, program_id.clone(), user.clone());
@@ -34,8 +46,20 @@ fn test_redemption_with_insufficient_points() {
     let user = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 100);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            100,
+        );
         // User has 100 points, tries to redeem Gift Card (requires 200)
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 1);
     });
@@ -49,9 +73,27 @@ fn test_inventory_tracking_for_rewards() {
     let user2 = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user1.clone(), 200);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user2.clone(), 200);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user1.clone(),
+            200,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user2.clone(),
+            200,
+        );
         // User1 redeems Gift Card (id=1)
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user1.clone(), 1);
         // User2 tries to redeem same reward, should panic (out of stock)
@@ -66,9 +108,23 @@ fn test_double_redemption_attempt() {
     let user = create_user(&env);
     let rewards = create_rewards(&env);
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 200);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            200,
+        );
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 1);
+    });
+    env.as_contract(&contract_address, || {
         // Try to redeem again with 0 points, should panic
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 1);
     });
@@ -83,10 +139,22 @@ fn test_successful_reward_redemption() {
     let rewards = create_basic_rewards(&env);
 
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
 
         // Award enough points for redemption
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 200);
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            200,
+        );
 
         // Redeem Gift Card (id=1, requires 200 points)
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 1);
@@ -113,10 +181,22 @@ fn test_partial_point_redemption() {
     let rewards = create_basic_rewards(&env);
 
     env.as_contract(&contract_address, || {
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
 
         // Award more points than needed for redemption
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 300);
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            300,
+        );
 
         // Redeem Discount Coupon (id=2, requires 100 points)
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 2);
@@ -137,21 +217,44 @@ fn test_full_loyalty_program_lifecycle() {
 
     env.as_contract(&contract_address, || {
         // 1. Create loyalty program
-        LoyaltyContract::create_loyalty_program(env.clone(), program_id.clone(), 1, rewards);
+        LoyaltyContract::create_loyalty_program(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            1,
+            rewards,
+        );
 
         // 2. Verify program creation
         let program = LoyaltyContract::get_program_info(env.clone(), program_id.clone());
         assert_eq!(program.redemption_options.len(), 3);
 
         // 3. User makes purchases and earns points
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 100);
-        LoyaltyContract::award_points(env.clone(), program_id.clone(), user.clone(), 150);
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            100,
+        );
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(&env),
+            program_id.clone(),
+            user.clone(),
+            150,
+        );
 
         let total_points = get_user_points(&env, program_id.clone(), user.clone());
         assert_eq!(total_points, 250);
 
         // 4. User redeems rewards
-        LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 2); // 100 points
+        LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 2);
+        // 100 points
+    });
+
+    // Each redemption is authorized by the user in its own call
+    env.as_contract(&contract_address, || {
         LoyaltyContract::redeem_reward(env.clone(), program_id.clone(), user.clone(), 3); // 50 points
 
         // 5. Verify final state
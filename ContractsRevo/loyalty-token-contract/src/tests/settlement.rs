@@ -0,0 +1,306 @@
+#![cfg(test)]
+
+use super::utils::*;
+use crate::{EarningRule, LoyaltyContractClient, ProgramSettings, Tier, TierRule};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, vec, Address, BytesN, Env};
+
+/// Stands in for an auction or marketplace contract reporting its sales
+#[contract]
+pub struct MockMarketplace;
+
+#[contractimpl]
+impl MockMarketplace {
+    pub fn settle(
+        env: Env,
+        loyalty: Address,
+        program_id: BytesN<32>,
+        buyer: Address,
+        price: i128,
+        sale_id: BytesN<32>,
+    ) -> u64 {
+        LoyaltyContractClient::new(&env, &loyalty).on_settlement(
+            &env.current_contract_address(),
+            &program_id,
+            &buyer,
+            &price,
+            &sale_id,
+        )
+    }
+}
+
+struct ProgramSetup {
+    client: LoyaltyContractClient<'static>,
+    contract_address: Address,
+    owner: Address,
+    program_id: BytesN<32>,
+}
+
+fn setup_program() -> (Env, ProgramSetup) {
+    let (env, contract_address, program_id) = setup_test();
+    let client = LoyaltyContractClient::new(&env, &contract_address);
+    let owner = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+
+    (
+        env,
+        ProgramSetup {
+            client,
+            contract_address,
+            owner,
+            program_id,
+        },
+    )
+}
+
+fn sale_rule() -> EarningRule {
+    // 1 point per 100 units of sale value
+    EarningRule {
+        points_per_unit: 1,
+        unit_amount: 100,
+        bonus_points: 0,
+        max_points: 0,
+    }
+}
+
+fn reference(env: &Env, id: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[id; 32])
+}
+
+#[test]
+fn test_settlement_awards_points_by_rule() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &source, &sale_rule());
+    assert_eq!(
+        p.client.get_earning_rule(&p.program_id, &source),
+        sale_rule()
+    );
+
+    let awarded =
+        p.client
+            .on_settlement(&source, &p.program_id, &user, &2_550, &reference(&env, 1));
+
+    assert_eq!(awarded, 25);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 25);
+}
+
+#[test]
+fn test_marketplace_contract_calls_hook() {
+    let (env, p) = setup_program();
+    let marketplace = env.register(MockMarketplace, ());
+    let marketplace_client = MockMarketplaceClient::new(&env, &marketplace);
+    let buyer = create_user(&env);
+
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &marketplace, &sale_rule());
+
+    let awarded = marketplace_client.settle(
+        &p.contract_address,
+        &p.program_id,
+        &buyer,
+        &10_000,
+        &reference(&env, 1),
+    );
+
+    assert_eq!(awarded, 100);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &buyer), 100);
+}
+
+#[test]
+fn test_rules_are_per_source() {
+    let (env, p) = setup_program();
+    let auction = Address::generate(&env);
+    let csa = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &auction, &sale_rule());
+    // Enrollment earns a flat bonus whatever the share price
+    p.client.set_earning_rule(
+        &p.owner,
+        &p.program_id,
+        &csa,
+        &EarningRule {
+            points_per_unit: 0,
+            unit_amount: 1,
+            bonus_points: 500,
+            max_points: 0,
+        },
+    );
+
+    p.client
+        .on_settlement(&auction, &p.program_id, &user, &1_000, &reference(&env, 1));
+    p.client
+        .on_settlement(&csa, &p.program_id, &user, &0, &reference(&env, 1));
+
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 510);
+}
+
+#[test]
+fn test_settlement_points_are_capped() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client.set_earning_rule(
+        &p.owner,
+        &p.program_id,
+        &source,
+        &EarningRule {
+            max_points: 50,
+            ..sale_rule()
+        },
+    );
+
+    let awarded =
+        p.client
+            .on_settlement(&source, &p.program_id, &user, &100_000, &reference(&env, 1));
+    assert_eq!(awarded, 50);
+}
+
+#[test]
+fn test_settlement_applies_tier_multiplier() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client.set_program_settings(
+        &p.owner,
+        &p.program_id,
+        &ProgramSettings {
+            point_validity: 0,
+            transfers_enabled: false,
+            tiers: vec![
+                &env,
+                TierRule {
+                    tier: Tier::Silver,
+                    min_points: 0,
+                    multiplier: 20_000,
+                },
+            ],
+            voucher_validity: 0,
+            refund_expired_vouchers: true,
+        },
+    );
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &source, &sale_rule());
+
+    let awarded =
+        p.client
+            .on_settlement(&source, &p.program_id, &user, &1_000, &reference(&env, 1));
+    assert_eq!(awarded, 20);
+}
+
+#[test]
+fn test_uncapped_settlement_saturates() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client.set_program_settings(
+        &p.owner,
+        &p.program_id,
+        &ProgramSettings {
+            point_validity: 0,
+            transfers_enabled: false,
+            tiers: vec![
+                &env,
+                TierRule {
+                    tier: Tier::Silver,
+                    min_points: 0,
+                    multiplier: 20_000,
+                },
+            ],
+            voucher_validity: 0,
+            refund_expired_vouchers: true,
+        },
+    );
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &source, &sale_rule());
+
+    // Base points saturate without a cap, and the tier multiplier must not overflow them
+    let awarded = p.client.on_settlement(
+        &source,
+        &p.program_id,
+        &user,
+        &i128::MAX,
+        &reference(&env, 1),
+    );
+    assert_eq!(awarded, u64::MAX);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Settlement already processed")]
+fn test_settlement_only_earns_once() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &source, &sale_rule());
+
+    p.client
+        .on_settlement(&source, &p.program_id, &user, &1_000, &reference(&env, 1));
+    p.client
+        .on_settlement(&source, &p.program_id, &user, &1_000, &reference(&env, 1));
+}
+
+#[test]
+#[should_panic(expected = "Earning rule not found")]
+fn test_unknown_source_rejected() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client
+        .on_settlement(&source, &p.program_id, &user, &1_000, &reference(&env, 1));
+}
+
+#[test]
+#[should_panic(expected = "Earning rule not found")]
+fn test_removed_rule_revokes_source() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+    let user = create_user(&env);
+
+    p.client
+        .set_earning_rule(&p.owner, &p.program_id, &source, &sale_rule());
+    p.client
+        .remove_earning_rule(&p.owner, &p.program_id, &source);
+
+    p.client
+        .on_settlement(&source, &p.program_id, &user, &1_000, &reference(&env, 1));
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_only_owner_sets_rules() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+
+    // A source cannot grant itself a rule
+    p.client
+        .set_earning_rule(&source, &p.program_id, &source, &sale_rule());
+}
+
+#[test]
+#[should_panic(expected = "Invalid earning rule")]
+fn test_rule_requires_unit_amount() {
+    let (env, p) = setup_program();
+    let source = Address::generate(&env);
+
+    p.client.set_earning_rule(
+        &p.owner,
+        &p.program_id,
+        &source,
+        &EarningRule {
+            unit_amount: 0,
+            ..sale_rule()
+        },
+    );
+}
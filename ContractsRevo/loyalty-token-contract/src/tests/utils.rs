@@ -6,13 +6,21 @@ use crate::{LoyaltyContract, LoyaltyProgram, RedemptionOption};
 
 // ============ CORE SETUP FUNCTIONS ============
 
+/// Fixed owner for test programs, so helpers can act on its behalf
+pub const PROGRAM_OWNER: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
 pub fn setup_test() -> (Env, Address, BytesN<32>) {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_address = env.register(LoyaltyContract, ());
     let program_id = BytesN::from_array(&env, &[1u8; 32]);
     (env, contract_address, program_id)
 }
 
+pub fn program_owner(env: &Env) -> Address {
+    Address::from_str(env, PROGRAM_OWNER)
+}
+
 pub fn create_user(env: &Env) -> Address {
     Address::generate(env)
 }
@@ -104,15 +112,7 @@ pub fn create_single_reward(
 // ============ UTILITY FUNCTIONS ============
 
 pub fn get_user_points(env: &Env, program_id: BytesN<32>, user_address: Address) -> u64 {
-    let points_key = (
-        soroban_sdk::Symbol::new(env, "points"),
-        program_id,
-        user_address,
-    );
-    env.storage()
-        .persistent()
-        .get::<(soroban_sdk::Symbol, BytesN<32>, Address), u64>(&points_key)
-        .unwrap_or(0)
+    LoyaltyContract::get_points_balance(env.clone(), program_id, user_address)
 }
 
 pub fn create_program_with_id(env: &Env, id: u8) -> BytesN<32> {
@@ -161,6 +161,7 @@ pub fn setup_loyalty_program(
     env.as_contract(contract_address, || {
         LoyaltyContract::create_loyalty_program(
             env.clone(),
+            program_owner(env),
             program_id,
             points_per_transaction,
             rewards,
@@ -181,7 +182,13 @@ pub fn award_points_to_user(
     transaction_amount: u32,
 ) {
     env.as_contract(contract_address, || {
-        LoyaltyContract::award_points(env.clone(), program_id, user, transaction_amount);
+        LoyaltyContract::award_points(
+            env.clone(),
+            program_owner(env),
+            program_id,
+            user,
+            transaction_amount,
+        );
     });
 }
 
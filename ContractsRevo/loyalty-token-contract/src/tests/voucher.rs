@@ -0,0 +1,370 @@
+#![cfg(test)]
+
+use super::utils::*;
+use crate::{voucher::MAX_VOUCHER_PAGE, LoyaltyContractClient, ProgramSettings, VoucherStatus};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Vec,
+};
+
+const DAY: u64 = 86400;
+
+struct ProgramSetup {
+    client: LoyaltyContractClient<'static>,
+    owner: Address,
+    merchant: Address,
+    program_id: BytesN<32>,
+}
+
+fn setup_program(point_validity: u64, voucher_validity: u64) -> (Env, ProgramSetup) {
+    let (env, contract_address, program_id) = setup_test();
+    let client = LoyaltyContractClient::new(&env, &contract_address);
+    let owner = Address::generate(&env);
+    let merchant = Address::generate(&env);
+
+    client.create_loyalty_program(&owner, &program_id, &1, &create_basic_rewards(&env));
+    client.set_issuer(&owner, &program_id, &merchant, &true);
+    client.set_program_settings(
+        &owner,
+        &program_id,
+        &ProgramSettings {
+            point_validity,
+            transfers_enabled: false,
+            tiers: Vec::new(&env),
+            voucher_validity,
+            refund_expired_vouchers: true,
+        },
+    );
+
+    (
+        env,
+        ProgramSetup {
+            client,
+            owner,
+            merchant,
+            program_id,
+        },
+    )
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+fn stock(p: &ProgramSetup, option_id: u32) -> u32 {
+    p.client
+        .get_program_info(&p.program_id)
+        .redemption_options
+        .iter()
+        .find(|opt| opt.id == option_id)
+        .unwrap()
+        .available_quantity
+}
+
+#[test]
+fn test_redeem_issues_voucher() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &300);
+    let code = p.client.redeem_reward(&p.program_id, &user, &1);
+
+    let voucher = p.client.get_voucher(&code);
+    assert_eq!(voucher.user, user);
+    assert_eq!(voucher.redemption_option_id, 1);
+    assert_eq!(voucher.points_spent, 200);
+    assert_eq!(voucher.status, VoucherStatus::Issued);
+    assert_eq!(voucher.expires_at, 0);
+
+    assert_eq!(
+        p.client
+            .get_user_vouchers(&p.program_id, &user, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        1
+    );
+    assert_eq!(
+        p.client
+            .get_outstanding_vouchers(&p.program_id, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_voucher_codes_are_unique() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let first = p.client.redeem_reward(&p.program_id, &user, &3);
+    let second = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    assert_ne!(first, second);
+    assert_eq!(
+        p.client
+            .get_user_vouchers(&p.program_id, &user, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_merchant_fulfills_voucher() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+    let other = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    p.client.award_points(&p.owner, &p.program_id, &other, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+    p.client.redeem_reward(&p.program_id, &other, &3);
+
+    p.client.fulfill_voucher(&p.merchant, &code);
+
+    let voucher = p.client.get_voucher(&code);
+    assert_eq!(voucher.status, VoucherStatus::Fulfilled);
+    assert_eq!(voucher.settled_by, Some(p.merchant.clone()));
+    assert_eq!(
+        p.client
+            .get_user_vouchers(&p.program_id, &user, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        0
+    );
+
+    // Only the other member's voucher is still owed
+    let owed = p
+        .client
+        .get_outstanding_vouchers(&p.program_id, &0, &MAX_VOUCHER_PAGE);
+    assert_eq!(owed.len(), 1);
+    assert_eq!(owed.get(0).unwrap().user, other);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_member_cannot_fulfill_own_voucher() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    p.client.fulfill_voucher(&user, &code);
+}
+
+#[test]
+#[should_panic(expected = "Voucher already settled")]
+fn test_voucher_cannot_be_fulfilled_twice() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    p.client.fulfill_voucher(&p.merchant, &code);
+    p.client.fulfill_voucher(&p.merchant, &code);
+}
+
+#[test]
+fn test_cancel_refunds_points_and_stock() {
+    let (env, p) = setup_program(90 * DAY, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &250);
+    let code = p.client.redeem_reward(&p.program_id, &user, &1);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 50);
+    assert_eq!(stock(&p, 1), 9);
+
+    set_time(&env, 30 * DAY);
+    p.client.cancel_voucher(&user, &code);
+
+    assert_eq!(p.client.get_voucher(&code).status, VoucherStatus::Cancelled);
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 250);
+    assert_eq!(stock(&p, 1), 10);
+    assert_eq!(
+        p.client
+            .get_outstanding_vouchers(&p.program_id, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        0
+    );
+
+    // Refunded points keep their original expiry
+    let lots = p.client.get_point_lots(&p.program_id, &user);
+    assert_eq!(lots.len(), 1);
+    assert_eq!(lots.get(0).unwrap().expires_at, 90 * DAY);
+}
+
+#[test]
+fn test_merchant_can_cancel_voucher() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &2);
+    p.client.cancel_voucher(&p.merchant, &code);
+
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 100);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_stranger_cannot_cancel_voucher() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+    let stranger = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    p.client.cancel_voucher(&stranger, &code);
+}
+
+#[test]
+#[should_panic(expected = "Voucher already settled")]
+fn test_fulfilled_voucher_cannot_be_cancelled() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    p.client.fulfill_voucher(&p.merchant, &code);
+    p.client.cancel_voucher(&user, &code);
+}
+
+#[test]
+fn test_voucher_expires() {
+    let (env, p) = setup_program(0, 7 * DAY);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+    assert_eq!(p.client.get_voucher(&code).expires_at, 7 * DAY);
+
+    set_time(&env, 7 * DAY);
+    assert_eq!(p.client.get_voucher(&code).status, VoucherStatus::Expired);
+    assert_eq!(
+        p.client
+            .get_user_vouchers(&p.program_id, &user, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        0
+    );
+    assert_eq!(
+        p.client
+            .get_outstanding_vouchers(&p.program_id, &0, &MAX_VOUCHER_PAGE)
+            .len(),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "Voucher expired")]
+fn test_expired_voucher_cannot_be_fulfilled() {
+    let (env, p) = setup_program(0, 7 * DAY);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    set_time(&env, 8 * DAY);
+    p.client.fulfill_voucher(&p.merchant, &code);
+}
+
+#[test]
+fn test_expire_voucher_refunds_points_and_stock() {
+    let (env, p) = setup_program(0, 7 * DAY);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &250);
+    let code = p.client.redeem_reward(&p.program_id, &user, &1);
+    assert_eq!(stock(&p, 1), 9);
+
+    set_time(&env, 7 * DAY);
+    p.client.expire_voucher(&user, &code);
+
+    let voucher = p.client.get_voucher(&code);
+    assert_eq!(voucher.status, VoucherStatus::Expired);
+    assert_eq!(voucher.settled_by, Some(user.clone()));
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 250);
+    assert_eq!(stock(&p, 1), 10);
+}
+
+#[test]
+fn test_expire_voucher_without_refund_policy() {
+    let (env, p) = setup_program(0, 7 * DAY);
+    let user = create_user(&env);
+    let mut settings = p.client.get_program_settings(&p.program_id);
+    settings.refund_expired_vouchers = false;
+    p.client
+        .set_program_settings(&p.owner, &p.program_id, &settings);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &250);
+    let code = p.client.redeem_reward(&p.program_id, &user, &1);
+
+    set_time(&env, 8 * DAY);
+    p.client.expire_voucher(&p.merchant, &code);
+
+    // The points are forfeited but the reward is back in stock
+    assert_eq!(p.client.get_points_balance(&p.program_id, &user), 50);
+    assert_eq!(stock(&p, 1), 10);
+}
+
+#[test]
+#[should_panic(expected = "Voucher not expired")]
+fn test_voucher_cannot_be_expired_early() {
+    let (env, p) = setup_program(0, 7 * DAY);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    set_time(&env, 6 * DAY);
+    p.client.expire_voucher(&user, &code);
+}
+
+#[test]
+#[should_panic(expected = "Voucher already settled")]
+fn test_voucher_cannot_be_expired_twice() {
+    let (env, p) = setup_program(0, 7 * DAY);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &100);
+    let code = p.client.redeem_reward(&p.program_id, &user, &3);
+
+    set_time(&env, 7 * DAY);
+    p.client.expire_voucher(&user, &code);
+    p.client.expire_voucher(&user, &code);
+}
+
+#[test]
+fn test_vouchers_paginate() {
+    let (env, p) = setup_program(0, 0);
+    let user = create_user(&env);
+
+    p.client.award_points(&p.owner, &p.program_id, &user, &200);
+    let mut codes = Vec::new(&env);
+    for _ in 0..4 {
+        codes.push_back(p.client.redeem_reward(&p.program_id, &user, &3));
+    }
+
+    let first = p.client.get_user_vouchers(&p.program_id, &user, &0, &3);
+    let rest = p.client.get_user_vouchers(&p.program_id, &user, &3, &3);
+    assert_eq!(first.len(), 3);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().code_hash, codes.get(3).unwrap());
+
+    // Closing a voucher moves the last one into its place
+    p.client
+        .fulfill_voucher(&p.merchant, &codes.get(0).unwrap());
+    let owed = p
+        .client
+        .get_outstanding_vouchers(&p.program_id, &0, &MAX_VOUCHER_PAGE);
+    assert_eq!(owed.len(), 3);
+    assert_eq!(owed.get(0).unwrap().code_hash, codes.get(3).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Voucher not found")]
+fn test_unknown_voucher() {
+    let (env, p) = setup_program(0, 0);
+    p.client.get_voucher(&BytesN::from_array(&env, &[9u8; 32]));
+}
@@ -0,0 +1,99 @@
+use crate::{points::get_settings, EarningBucket, Tier, TierRule};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+/// Earnings are bucketed by 30-day month; tiers look at the last 12
+const SECONDS_PER_MONTH: u64 = 2_592_000;
+const ROLLING_MONTHS: u64 = 12;
+
+/// Multipliers are in basis points, 10_000 = 1x
+pub const BASE_MULTIPLIER: u32 = 10_000;
+
+/// Points earned in the last 12 months, excluding transfers
+pub fn get_rolling_earnings(env: &Env, program_id: BytesN<32>, user_address: Address) -> u64 {
+    let current_month = env.ledger().timestamp() / SECONDS_PER_MONTH;
+    load_buckets(env, &program_id, &user_address)
+        .iter()
+        .filter(|bucket| bucket.month + ROLLING_MONTHS > current_month)
+        .map(|bucket| bucket.points)
+        .sum()
+}
+
+/// Highest tier whose threshold the user's rolling earnings reach
+pub fn get_member_tier(env: &Env, program_id: BytesN<32>, user_address: Address) -> TierRule {
+    let settings = get_settings(env, program_id.clone());
+    let earnings = get_rolling_earnings(env, program_id, user_address);
+
+    let mut current = TierRule {
+        tier: Tier::Bronze,
+        min_points: 0,
+        multiplier: BASE_MULTIPLIER,
+    };
+    for rule in settings.tiers.iter() {
+        if earnings >= rule.min_points {
+            current = rule;
+        }
+    }
+    current
+}
+
+/// Rules must go Bronze, Silver, Gold with increasing thresholds
+pub fn validate_tiers(tiers: &Vec<TierRule>) {
+    if tiers.len() > 3 {
+        panic!("Invalid tiers");
+    }
+
+    let mut previous: Option<TierRule> = None;
+    for rule in tiers.iter() {
+        if rule.multiplier == 0 {
+            panic!("Invalid tiers");
+        }
+        if let Some(previous) = previous {
+            if rule.tier as u32 <= previous.tier as u32 || rule.min_points <= previous.min_points {
+                panic!("Invalid tiers");
+            }
+        }
+        previous = Some(rule);
+    }
+}
+
+pub fn record_earning(env: &Env, program_id: &BytesN<32>, user_address: &Address, points: u64) {
+    let current_month = env.ledger().timestamp() / SECONDS_PER_MONTH;
+
+    // Keep only the buckets that still count toward the rolling window
+    let mut buckets = Vec::new(env);
+    for bucket in load_buckets(env, program_id, user_address).iter() {
+        if bucket.month + ROLLING_MONTHS > current_month {
+            buckets.push_back(bucket);
+        }
+    }
+
+    match buckets.last() {
+        Some(mut last) if last.month == current_month => {
+            last.points = last.points.saturating_add(points);
+            buckets.set(buckets.len() - 1, last);
+        }
+        _ => buckets.push_back(EarningBucket {
+            month: current_month,
+            points,
+        }),
+    }
+
+    let key = (
+        Symbol::new(env, "earned"),
+        program_id.clone(),
+        user_address.clone(),
+    );
+    env.storage().persistent().set(&key, &buckets);
+}
+
+fn load_buckets(env: &Env, program_id: &BytesN<32>, user_address: &Address) -> Vec<EarningBucket> {
+    let key = (
+        Symbol::new(env, "earned"),
+        program_id.clone(),
+        user_address.clone(),
+    );
+    env.storage()
+        .persistent()
+        .get::<(Symbol, BytesN<32>, Address), Vec<EarningBucket>>(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
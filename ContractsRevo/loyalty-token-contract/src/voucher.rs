@@ -0,0 +1,310 @@
+use crate::{
+    points::{get_settings, restore_lots},
+    program::{get_program_info, require_issuer, save_program},
+    PointLot, Voucher, VoucherStatus,
+};
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+/// Largest page of vouchers returned by a listing
+pub const MAX_VOUCHER_PAGE: u32 = 50;
+
+/// Creates the voucher for a redemption, keeping the spent lots for a refund
+pub fn issue_voucher(
+    env: &Env,
+    program_id: &BytesN<32>,
+    user_address: &Address,
+    redemption_option_id: u32,
+    spent_lots: Vec<PointLot>,
+) -> BytesN<32> {
+    let counter_key = Symbol::new(env, "voucher_count");
+    let count: u64 = env.storage().instance().get(&counter_key).unwrap_or(0) + 1;
+    env.storage().instance().set(&counter_key, &count);
+
+    // Unique per contract and redemption
+    let mut preimage = Bytes::new(env);
+    preimage.append(&env.current_contract_address().to_xdr(env));
+    preimage.append(&Bytes::from_slice(env, &program_id.to_array()));
+    preimage.append(&user_address.clone().to_xdr(env));
+    preimage.append(&Bytes::from_slice(env, &count.to_be_bytes()));
+    let code_hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    let settings = get_settings(env, program_id.clone());
+    let issued_at = env.ledger().timestamp();
+    let voucher = Voucher {
+        code_hash: code_hash.clone(),
+        program_id: program_id.clone(),
+        user: user_address.clone(),
+        redemption_option_id,
+        points_spent: spent_lots.iter().map(|lot| lot.amount).sum(),
+        spent_lots,
+        status: VoucherStatus::Issued,
+        issued_at,
+        expires_at: if settings.voucher_validity == 0 {
+            0
+        } else {
+            issued_at + settings.voucher_validity
+        },
+        settled_by: None,
+    };
+    save_voucher(env, &voucher);
+
+    // Index the voucher for the member and for the merchants who owe it
+    let user_key = user_index_key(env, program_id, user_address);
+    let program_key = program_index_key(env, program_id);
+    add_to_index(env, &user_key, &code_hash);
+    add_to_index(env, &program_key, &code_hash);
+
+    code_hash
+}
+
+pub fn get_voucher(env: &Env, code_hash: BytesN<32>) -> Voucher {
+    let key = (Symbol::new(env, "voucher"), code_hash);
+    let mut voucher = env
+        .storage()
+        .persistent()
+        .get::<(Symbol, BytesN<32>), Voucher>(&key)
+        .expect("Voucher not found");
+
+    if voucher.status == VoucherStatus::Issued
+        && voucher.expires_at != 0
+        && env.ledger().timestamp() >= voucher.expires_at
+    {
+        voucher.status = VoucherStatus::Expired;
+    }
+    voucher
+}
+
+/// One page of the vouchers a user still has to collect. Expired vouchers are left out
+/// but keep their place in the index until `expire_voucher` closes them.
+pub fn get_user_vouchers(
+    env: &Env,
+    program_id: BytesN<32>,
+    user_address: Address,
+    start: u32,
+    limit: u32,
+) -> Vec<Voucher> {
+    load_outstanding(
+        env,
+        &user_index_key(env, &program_id, &user_address),
+        start,
+        limit,
+    )
+}
+
+/// One page of the redemptions the program's merchants still owe
+pub fn get_outstanding_vouchers(
+    env: &Env,
+    program_id: BytesN<32>,
+    start: u32,
+    limit: u32,
+) -> Vec<Voucher> {
+    load_outstanding(env, &program_index_key(env, &program_id), start, limit)
+}
+
+/// The merchant marks a voucher as handed over
+pub fn fulfill_voucher(env: &Env, issuer: Address, code_hash: BytesN<32>) {
+    let mut voucher = get_voucher(env, code_hash.clone());
+    require_issuer(env, &voucher.program_id, &issuer);
+    require_issued(&voucher);
+
+    voucher.status = VoucherStatus::Fulfilled;
+    voucher.settled_by = Some(issuer.clone());
+    close_voucher(env, &voucher);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "voucher_fulfilled"),
+            voucher.program_id,
+            issuer,
+        ),
+        code_hash,
+    );
+}
+
+/// Cancels an outstanding voucher, refunding the points and returning the reward to stock.
+/// Either the member or a program issuer can cancel.
+pub fn cancel_voucher(env: &Env, caller: Address, code_hash: BytesN<32>) {
+    let mut voucher = get_voucher(env, code_hash.clone());
+    require_party(env, &voucher, &caller);
+    require_issued(&voucher);
+
+    restore_lots(env, &voucher.program_id, &voucher.user, &voucher.spent_lots);
+    restock(env, &voucher);
+
+    voucher.status = VoucherStatus::Cancelled;
+    voucher.settled_by = Some(caller.clone());
+    close_voucher(env, &voucher);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "voucher_cancelled"),
+            voucher.program_id,
+            caller,
+        ),
+        (code_hash, voucher.points_spent),
+    );
+}
+
+/// Closes a voucher that was not collected in time. The reward goes back into stock and
+/// the spent points are refunded if the program's `refund_expired_vouchers` is set.
+/// Either the member or a program issuer can expire it.
+pub fn expire_voucher(env: &Env, caller: Address, code_hash: BytesN<32>) {
+    let mut voucher = get_voucher(env, code_hash.clone());
+    require_party(env, &voucher, &caller);
+    // Closed vouchers keep the Expired status but record who closed them
+    match voucher.status {
+        VoucherStatus::Expired if voucher.settled_by.is_none() => {}
+        VoucherStatus::Issued => panic!("Voucher not expired"),
+        _ => panic!("Voucher already settled"),
+    }
+
+    let refunded = if get_settings(env, voucher.program_id.clone()).refund_expired_vouchers {
+        restore_lots(env, &voucher.program_id, &voucher.user, &voucher.spent_lots);
+        voucher.points_spent
+    } else {
+        0
+    };
+    restock(env, &voucher);
+
+    voucher.settled_by = Some(caller.clone());
+    close_voucher(env, &voucher);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "voucher_expired"),
+            voucher.program_id,
+            caller,
+        ),
+        (code_hash, refunded),
+    );
+}
+
+/// The member or a program issuer
+fn require_party(env: &Env, voucher: &Voucher, caller: &Address) {
+    if *caller == voucher.user {
+        caller.require_auth();
+    } else {
+        require_issuer(env, &voucher.program_id, caller);
+    }
+}
+
+/// Puts the voucher's reward back into the redemption option's stock
+fn restock(env: &Env, voucher: &Voucher) {
+    let mut program = get_program_info(env, voucher.program_id.clone());
+    if let Some(index) = program
+        .redemption_options
+        .iter()
+        .position(|opt| opt.id == voucher.redemption_option_id)
+    {
+        let mut option = program
+            .redemption_options
+            .get(index.try_into().unwrap())
+            .unwrap();
+        option.available_quantity += 1;
+        program
+            .redemption_options
+            .set(index.try_into().unwrap(), option);
+        save_program(env, &program);
+    }
+}
+
+fn require_issued(voucher: &Voucher) {
+    match voucher.status {
+        VoucherStatus::Issued => {}
+        VoucherStatus::Expired => panic!("Voucher expired"),
+        _ => panic!("Voucher already settled"),
+    }
+}
+
+/// Stores the final status and drops the voucher from the outstanding lists
+fn close_voucher(env: &Env, voucher: &Voucher) {
+    save_voucher(env, voucher);
+
+    let user_key = user_index_key(env, &voucher.program_id, &voucher.user);
+    let program_key = program_index_key(env, &voucher.program_id);
+    remove_from_index(env, &user_key, &voucher.code_hash);
+    remove_from_index(env, &program_key, &voucher.code_hash);
+}
+
+fn save_voucher(env: &Env, voucher: &Voucher) {
+    let key = (Symbol::new(env, "voucher"), voucher.code_hash.clone());
+    env.storage().persistent().set(&key, voucher);
+}
+
+fn user_index_key(env: &Env, program_id: &BytesN<32>, user_address: &Address) -> Val {
+    (
+        Symbol::new(env, "user_vouchers"),
+        program_id.clone(),
+        user_address.clone(),
+    )
+        .into_val(env)
+}
+
+fn program_index_key(env: &Env, program_id: &BytesN<32>) -> Val {
+    (Symbol::new(env, "open_vouchers"), program_id.clone()).into_val(env)
+}
+
+// Each index is a length under its key plus one entry per position, so adding or
+// removing a voucher touches a fixed number of entries
+
+fn entry_key(env: &Env, key: &Val, position: u32) -> Val {
+    (*key, position).into_val(env)
+}
+
+fn position_key(env: &Env, key: &Val, code_hash: &BytesN<32>) -> Val {
+    (*key, code_hash.clone()).into_val(env)
+}
+
+fn index_len(env: &Env, key: &Val) -> u32 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn add_to_index(env: &Env, key: &Val, code_hash: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    let len = index_len(env, key);
+
+    storage.set(&entry_key(env, key, len), code_hash);
+    storage.set(&position_key(env, key, code_hash), &len);
+    storage.set(key, &(len + 1));
+}
+
+/// Moves the last entry into the removed voucher's place
+fn remove_from_index(env: &Env, key: &Val, code_hash: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    let position: u32 = match storage.get(&position_key(env, key, code_hash)) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let last = index_len(env, key) - 1;
+    if position != last {
+        let last_code: BytesN<32> = storage.get(&entry_key(env, key, last)).unwrap();
+        storage.set(&entry_key(env, key, position), &last_code);
+        storage.set(&position_key(env, key, &last_code), &position);
+    }
+
+    storage.remove(&entry_key(env, key, last));
+    storage.remove(&position_key(env, key, code_hash));
+    storage.set(key, &last);
+}
+
+/// Vouchers on one page of an index that can still be collected
+fn load_outstanding(env: &Env, key: &Val, start: u32, limit: u32) -> Vec<Voucher> {
+    let end = start
+        .saturating_add(limit.min(MAX_VOUCHER_PAGE))
+        .min(index_len(env, key));
+
+    let mut vouchers = Vec::new(env);
+    for position in start..end {
+        let code_hash: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&entry_key(env, key, position))
+            .unwrap();
+        let voucher = get_voucher(env, code_hash);
+        if voucher.status == VoucherStatus::Issued {
+            vouchers.push_back(voucher);
+        }
+    }
+    vouchers
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracterror, contracttype, Address, String, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Vec};
 
 /// Main categories for rating different aspects of products/services
 /// Used to organize and segment ratings into specific areas of evaluation
@@ -42,6 +42,9 @@ pub enum DataKeys {
     AlreadyVoted(u64, u32, Address), // (product_id, review_id, voter)
     UserReviewReport(u64, u32, Address), // (product_id, review_id, reporter)
     VoteRateLimit(Address),
+    TransactionNftContract, // Transaction NFT contract accepted as proof of purchase
+    ProductReference(u64),  // Transaction NFT product hash linked to a product_id
+    TransactionUsed(BytesN<32>), // Transaction NFT already used to verify a purchase
 }
 
 /// Error types that can occur during contract operations
@@ -73,6 +76,8 @@ pub enum PurchaseReviewError {
     RateLimitExceeded = 21,
     InvalidPurchaseLink = 22,
     InvalidResponseText = 23,
+    TransactionNftNotConfigured = 24, // No transaction NFT contract has been set
+    InvalidTransactionNft = 25,       // Transaction NFT does not prove this purchase
 }
 
 /// Represents a rating for a specific category with additional metadata
@@ -110,12 +115,25 @@ pub struct ReviewDetails {
 /// Data structure for purchase verification
 #[contracttype]
 pub struct PurchaseVerificationData {
-    pub user: Address,         // User who made the purchase
-    pub product_id: u64,       // ID of the purchased product
-    pub purchase_link: String, // Link to purchase proof
-    pub is_verified: bool,     // Verification status
-    pub timestamp: u64,        // When purchase was made
-    pub has_review: bool,      // Whether user has reviewed
+    pub user: Address,                      // User who made the purchase
+    pub product_id: u64,                    // ID of the purchased product
+    pub purchase_link: String,              // Link to purchase proof
+    pub is_verified: bool,                  // Verification status
+    pub timestamp: u64,                     // When purchase was made
+    pub has_review: bool,                   // Whether user has reviewed
+    pub transaction_id: Option<BytesN<32>>, // Transaction NFT used as proof, if any
+}
+
+/// Purchase receipt as stored by the transaction NFT contract
+#[contracttype]
+#[derive(Clone)]
+pub struct TransactionReceipt {
+    pub buyer: Address,      // Original purchaser
+    pub seller: Address,     // Seller of the product
+    pub amount: u64,         // Price paid
+    pub product: BytesN<32>, // Product hash recorded at mint
+    pub nonce: u64,          // Order reference
+    pub timestamp: u64,      // When the receipt was minted
 }
 
 /// Information about reported reviews
@@ -1,8 +1,11 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
+};
 
 use crate::datatype::{
     DataKeys, ProductRatings, PurchaseReviewError, PurchaseVerificationData, ReviewDetails,
+    TransactionReceipt,
 };
 
 mod datatype;
@@ -62,6 +65,7 @@ impl PurchaseReviewContract {
             is_verified: true,
             timestamp: env.ledger().timestamp(),
             has_review: false,
+            transaction_id: None,
         };
 
         let key = DataKeys::PurchaseVerification(product_id, user.clone());
@@ -75,6 +79,109 @@ impl PurchaseReviewContract {
         Ok(true)
     }
 
+    // Set the transaction NFT contract whose receipts prove purchases
+    pub fn set_transaction_nft_contract(
+        env: Env,
+        nft_contract: Address,
+    ) -> Result<(), PurchaseReviewError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKeys::TransactionNftContract, &nft_contract);
+
+        Ok(())
+    }
+
+    // Link a product_id to the product hash recorded in transaction NFTs
+    pub fn link_product_reference(
+        env: Env,
+        product_id: u64,
+        product_ref: BytesN<32>,
+    ) -> Result<(), PurchaseReviewError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&DataKeys::ProductReference(product_id), &product_ref);
+
+        Ok(())
+    }
+
+    // Verify a purchase using a transaction NFT receipt instead of a purchase link
+    pub fn verify_purchase_with_nft(
+        env: Env,
+        user: Address,
+        product_id: u64,
+        tx_id: BytesN<32>,
+    ) -> Result<bool, PurchaseReviewError> {
+        user.require_auth();
+
+        let nft_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKeys::TransactionNftContract)
+            .ok_or(PurchaseReviewError::TransactionNftNotConfigured)?;
+        let product_ref: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKeys::ProductReference(product_id))
+            .ok_or(PurchaseReviewError::ProductNotFound)?;
+
+        let key = DataKeys::PurchaseVerification(product_id, user.clone());
+        let used_key = DataKeys::TransactionUsed(tx_id.clone());
+        if env.storage().persistent().has(&key) || env.storage().persistent().has(&used_key) {
+            return Err(PurchaseReviewError::AlreadyVerified);
+        }
+
+        // The receipt must be for this product and currently held by this user.
+        // Receipts are transferable, so the owner is checked rather than the original buyer.
+        let args: Vec<Val> = Vec::from_array(&env, [tx_id.into_val(&env)]);
+        let receipt = env
+            .try_invoke_contract::<Option<TransactionReceipt>, soroban_sdk::Error>(
+                &nft_contract,
+                &Symbol::new(&env, "get_nft_metadata"),
+                args.clone(),
+            )
+            .ok()
+            .and_then(|result| result.ok())
+            .flatten()
+            .ok_or(PurchaseReviewError::InvalidTransactionNft)?;
+        let owner = env
+            .try_invoke_contract::<Address, soroban_sdk::Error>(
+                &nft_contract,
+                &Symbol::new(&env, "owner_of"),
+                args,
+            )
+            .ok()
+            .and_then(|result| result.ok())
+            .ok_or(PurchaseReviewError::InvalidTransactionNft)?;
+        if owner != user || receipt.product != product_ref {
+            return Err(PurchaseReviewError::InvalidTransactionNft);
+        }
+
+        let verification_data = PurchaseVerificationData {
+            user: user.clone(),
+            product_id,
+            purchase_link: String::from_str(&env, ""),
+            is_verified: true,
+            timestamp: receipt.timestamp,
+            has_review: false,
+            transaction_id: Some(tx_id.clone()),
+        };
+        env.storage().persistent().set(&key, &verification_data);
+        env.storage().persistent().set(&used_key, &true);
+
+        env.events().publish(
+            (Symbol::new(&env, "purchase_verified"), user),
+            (product_id, tx_id),
+        );
+
+        Ok(true)
+    }
+
     pub fn is_purchase_verified(
         env: Env,
         _user: Address,
@@ -324,6 +324,7 @@ fn test_pre_review_purchase() {
         is_verified: true,
         timestamp: env.ledger().timestamp(),
         has_review: true,
+        transaction_id: None,
     };
 
     env.as_contract(&client.address, || {
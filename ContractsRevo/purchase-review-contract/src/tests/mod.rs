@@ -2,5 +2,6 @@
 
 pub mod aggregation;
 pub mod review;
+pub mod transaction_nft;
 pub mod utils;
 pub mod validation;
@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use super::super::*;
+use super::utils::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, BytesN, Env,
+};
+use transaction_nft_contract::{TransactionNFTContract, TransactionNFTContractClient};
+
+const PRODUCT_ID: u64 = 12345;
+
+fn setup_nft(
+    env: &Env,
+    client: &PurchaseReviewContractClient,
+) -> TransactionNFTContractClient<'static> {
+    // Receipts cannot be minted at ledger time zero
+    env.ledger().with_mut(|li| li.timestamp = 1);
+
+    let nft_id = env.register(TransactionNFTContract, ());
+    client.set_transaction_nft_contract(&nft_id);
+    client.link_product_reference(&PRODUCT_ID, &BytesN::from_array(env, &[7; 32]));
+
+    TransactionNFTContractClient::new(env, &nft_id)
+}
+
+fn mint_receipt(
+    env: &Env,
+    nft: &TransactionNFTContractClient,
+    buyer: &Address,
+    product_seed: u8,
+    nonce: u64,
+) -> BytesN<32> {
+    nft.mint_nft(
+        buyer,
+        &Address::generate(env),
+        &1000,
+        &BytesN::from_array(env, &[product_seed; 32]),
+        &nonce,
+    )
+}
+
+#[test]
+fn test_verify_purchase_with_nft() {
+    let (env, client, _, user) = setup_test();
+    let nft = setup_nft(&env, &client);
+    let tx_id = mint_receipt(&env, &nft, &user, 7, 1);
+
+    assert!(client.verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id));
+    assert!(client.is_purchase_verified(&user, &PRODUCT_ID));
+
+    env.as_contract(&client.address, || {
+        let data: PurchaseVerificationData = env
+            .storage()
+            .persistent()
+            .get(&DataKeys::PurchaseVerification(PRODUCT_ID, user.clone()))
+            .unwrap();
+        assert_eq!(data.transaction_id, Some(tx_id.clone()));
+    });
+}
+
+#[test]
+fn test_verify_purchase_with_nft_not_configured() {
+    let (env, client, _, user) = setup_test();
+    let tx_id = BytesN::from_array(&env, &[1; 32]);
+
+    let result = client.try_verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+    assert_eq!(
+        result,
+        Err(Ok(PurchaseReviewError::TransactionNftNotConfigured))
+    );
+}
+
+#[test]
+fn test_verify_purchase_with_nft_unlinked_product() {
+    let (env, client, _, user) = setup_test();
+    let nft = setup_nft(&env, &client);
+    let tx_id = mint_receipt(&env, &nft, &user, 7, 1);
+
+    let result = client.try_verify_purchase_with_nft(&user, &(PRODUCT_ID + 1), &tx_id);
+    assert_eq!(result, Err(Ok(PurchaseReviewError::ProductNotFound)));
+}
+
+#[test]
+fn test_verify_purchase_with_unknown_nft() {
+    let (env, client, _, user) = setup_test();
+    setup_nft(&env, &client);
+    let tx_id = BytesN::from_array(&env, &[9; 32]);
+
+    let result = client.try_verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+    assert_eq!(result, Err(Ok(PurchaseReviewError::InvalidTransactionNft)));
+}
+
+#[test]
+fn test_verify_purchase_with_nft_of_other_buyer() {
+    let (env, client, _, user) = setup_test();
+    let nft = setup_nft(&env, &client);
+    let tx_id = mint_receipt(&env, &nft, &Address::generate(&env), 7, 1);
+
+    let result = client.try_verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+    assert_eq!(result, Err(Ok(PurchaseReviewError::InvalidTransactionNft)));
+}
+
+#[test]
+fn test_verify_purchase_with_transferred_nft() {
+    let (env, client, _, user) = setup_test();
+    let nft = setup_nft(&env, &client);
+    let holder = Address::generate(&env);
+    let tx_id = mint_receipt(&env, &nft, &user, 7, 1);
+    nft.transfer(&user, &holder, &tx_id);
+
+    // The original buyer no longer holds the receipt
+    let result = client.try_verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+    assert_eq!(result, Err(Ok(PurchaseReviewError::InvalidTransactionNft)));
+
+    assert!(client.verify_purchase_with_nft(&holder, &PRODUCT_ID, &tx_id));
+}
+
+#[test]
+fn test_verify_purchase_with_nft_for_other_product() {
+    let (env, client, _, user) = setup_test();
+    let nft = setup_nft(&env, &client);
+    let tx_id = mint_receipt(&env, &nft, &user, 8, 1);
+
+    let result = client.try_verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+    assert_eq!(result, Err(Ok(PurchaseReviewError::InvalidTransactionNft)));
+}
+
+#[test]
+fn test_verify_purchase_with_nft_reused() {
+    let (env, client, _, user) = setup_test();
+    let nft = setup_nft(&env, &client);
+    let tx_id = mint_receipt(&env, &nft, &user, 7, 1);
+
+    client.verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+
+    let result = client.try_verify_purchase_with_nft(&user, &PRODUCT_ID, &tx_id);
+    assert_eq!(result, Err(Ok(PurchaseReviewError::AlreadyVerified)));
+}
@@ -49,6 +49,7 @@ pub fn create_test_verification_data(
         is_verified: true,
         timestamp: env.ledger().timestamp(),
         has_review: false,
+        transaction_id: None,
     }
 }
 
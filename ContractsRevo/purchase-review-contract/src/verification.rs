@@ -127,6 +127,7 @@ impl VerificationOperations for PurchaseReviewContract {
             is_verified: true,
             timestamp: env.ledger().timestamp(),
             has_review: false,
+            transaction_id: None,
         };
 
         env.storage().persistent().set(&key, &verification_data);
@@ -0,0 +1,135 @@
+use crate::datatypes::{Accreditation, ActorRole, DataKey, Product, StageTier, SupplyChainError};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Approve an actor for the given roles. Approvals by the admin apply to
+/// every product; approvals by anyone else apply to that farmer's products.
+pub fn approve_actor(
+    env: Env,
+    approver: Address,
+    actor: Address,
+    roles: Vec<ActorRole>,
+) -> Result<(), SupplyChainError> {
+    approver.require_auth();
+
+    // Validate input data
+    if roles.is_empty() {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    let accreditation = Accreditation {
+        actor: actor.clone(),
+        roles: roles.clone(),
+        approved_by: approver.clone(),
+        approved_at: env.ledger().timestamp(),
+        revoked: false,
+    };
+
+    env.storage().persistent().set(
+        &DataKey::Accreditation(approver.clone(), actor.clone()),
+        &accreditation,
+    );
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(&env, "actor_approved"), approver),
+        (actor, roles),
+    );
+
+    Ok(())
+}
+
+/// Revoke an accreditation previously granted by the approver
+pub fn revoke_actor(env: Env, approver: Address, actor: Address) -> Result<(), SupplyChainError> {
+    approver.require_auth();
+
+    let key = DataKey::Accreditation(approver.clone(), actor.clone());
+    let mut accreditation: Accreditation = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(SupplyChainError::ActorNotAccredited)?;
+
+    accreditation.revoked = true;
+    env.storage().persistent().set(&key, &accreditation);
+
+    // Emit event
+    env.events()
+        .publish((Symbol::new(&env, "actor_revoked"), approver), actor);
+
+    Ok(())
+}
+
+/// Get the accreditation an approver granted to an actor
+pub fn get_accreditation(
+    env: Env,
+    approver: Address,
+    actor: Address,
+) -> Result<Accreditation, SupplyChainError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Accreditation(approver, actor))
+        .ok_or(SupplyChainError::ActorNotAccredited)
+}
+
+/// Check that a handler may record a stage of the given tier on a product.
+/// Farmers may always record growing stages on their own products.
+pub fn authorize_stage(
+    env: &Env,
+    product: &Product,
+    handler: &Address,
+    tier: &StageTier,
+) -> Result<(), SupplyChainError> {
+    if *handler == product.farmer_id && ActorRole::Grower.allows(tier) {
+        return Ok(());
+    }
+
+    let accreditations = active_accreditations(env, product, handler);
+    if accreditations.is_empty() {
+        return Err(SupplyChainError::ActorNotAccredited);
+    }
+
+    for accreditation in accreditations.iter() {
+        if accreditation.roles.iter().any(|role| role.allows(tier)) {
+            return Ok(());
+        }
+    }
+
+    Err(SupplyChainError::TierNotPermitted)
+}
+
+/// Check that a handler is the product's farmer or an accredited actor for it
+pub fn authorize_handler(
+    env: &Env,
+    product: &Product,
+    handler: &Address,
+) -> Result<(), SupplyChainError> {
+    if *handler == product.farmer_id || !active_accreditations(env, product, handler).is_empty() {
+        return Ok(());
+    }
+
+    Err(SupplyChainError::ActorNotAccredited)
+}
+
+/// Helper function to collect the unrevoked admin and farmer approvals of a handler
+fn active_accreditations(env: &Env, product: &Product, handler: &Address) -> Vec<Accreditation> {
+    let mut approvers = Vec::new(env);
+    if let Some(admin) = env.storage().instance().get::<_, Address>(&DataKey::Admin) {
+        approvers.push_back(admin);
+    }
+    approvers.push_back(product.farmer_id.clone());
+
+    let mut accreditations = Vec::new(env);
+    for approver in approvers.iter() {
+        let accreditation: Option<Accreditation> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Accreditation(approver, handler.clone()));
+        if let Some(accreditation) = accreditation {
+            if !accreditation.revoked {
+                accreditations.push_back(accreditation);
+            }
+        }
+    }
+
+    accreditations
+}
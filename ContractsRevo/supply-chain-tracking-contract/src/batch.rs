@@ -0,0 +1,310 @@
+use crate::datatypes::{
+    BatchLink, CertificateId, DataKey, Product, ProductRegistration, SupplyChainError, TraceNode,
+    MAX_MERGE_PARENTS, MAX_SPLIT_CHILDREN, MAX_TRACE_NODES,
+};
+use crate::{actors, product, recall, utils};
+use soroban_sdk::{vec, Address, BytesN, Env, String, Symbol, Vec};
+
+/// Split a product batch into child batches that inherit its stage history
+pub fn split_product(
+    env: Env,
+    parent_id: BytesN<32>,
+    handler: Address,
+    batch_numbers: Vec<String>,
+) -> Result<Vec<BytesN<32>>, SupplyChainError> {
+    handler.require_auth();
+
+    // Validate input data
+    if batch_numbers.is_empty() || batch_numbers.len() > MAX_SPLIT_CHILDREN {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    let parent = load_unconsumed(&env, &parent_id)?;
+    actors::authorize_handler(&env, &parent, &handler)?;
+    let registration = product::get_product_registration(env.clone(), parent_id.clone())?;
+    let links = vec![&env, link_to(&env, &parent)];
+
+    let mut children = Vec::new(&env);
+    for batch_number in batch_numbers.iter() {
+        let child_id = create_child(
+            &env,
+            &parent,
+            &registration,
+            batch_number,
+            parent.certificate_id.clone(),
+            &links,
+        )?;
+        children.push_back(child_id);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProductChildren(parent_id.clone()), &children);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(&env, "product_split"), handler),
+        (parent_id, children.clone()),
+    );
+
+    Ok(children)
+}
+
+/// Blend several batches of the same product type into one child batch.
+/// The first parent is the primary lot whose farmer, registration and stage
+/// history the blend continues; every parent stays reachable through the trace.
+pub fn merge_products(
+    env: Env,
+    parent_ids: Vec<BytesN<32>>,
+    handler: Address,
+    batch_number: String,
+) -> Result<BytesN<32>, SupplyChainError> {
+    handler.require_auth();
+
+    // Validate input data
+    if parent_ids.len() < 2 || parent_ids.len() > MAX_MERGE_PARENTS {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    let primary_id = parent_ids.get(0).unwrap();
+    let primary = load_unconsumed(&env, &primary_id)?;
+    let registration = product::get_product_registration(env.clone(), primary_id.clone())?;
+    let primary_tier = current_tier_value(&primary);
+
+    let mut parents: Vec<BytesN<32>> = Vec::new(&env);
+    let mut links = Vec::new(&env);
+    for parent_id in parent_ids.iter() {
+        if parents.contains(&parent_id) {
+            return Err(SupplyChainError::InvalidInput);
+        }
+
+        let parent = load_unconsumed(&env, &parent_id)?;
+        actors::authorize_handler(&env, &parent, &handler)?;
+        let parent_registration =
+            product::get_product_registration(env.clone(), parent_id.clone())?;
+
+        // Only lots of the same product at the same tier can be blended
+        if parent_registration.product_type != registration.product_type
+            || current_tier_value(&parent) != primary_tier
+        {
+            return Err(SupplyChainError::IncompatibleBatches);
+        }
+
+        links.push_back(link_to(&env, &parent));
+        parents.push_back(parent_id);
+    }
+
+    // A blend is no longer covered by any single lot's certificate
+    let child_id = create_child(
+        &env,
+        &primary,
+        &registration,
+        batch_number,
+        CertificateId::None,
+        &links,
+    )?;
+
+    for parent_id in parents.iter() {
+        env.storage().persistent().set(
+            &DataKey::ProductChildren(parent_id),
+            &vec![&env, child_id.clone()],
+        );
+    }
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(&env, "products_merged"), handler),
+        (parents, child_id.clone()),
+    );
+
+    Ok(child_id)
+}
+
+/// Walk the lineage DAG from a product back to every origin batch
+pub fn get_batch_trace(
+    env: Env,
+    product_id: BytesN<32>,
+) -> Result<Vec<TraceNode>, SupplyChainError> {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Product(product_id.clone()))
+    {
+        return Err(SupplyChainError::ProductNotFound);
+    }
+
+    let mut visited: Vec<BytesN<32>> = vec![&env, product_id];
+    let mut nodes = Vec::new(&env);
+    let mut next = 0;
+
+    while next < visited.len() {
+        let current_id = visited.get(next).unwrap();
+        next += 1;
+
+        let current = product::get_product_details(env.clone(), current_id.clone())?;
+        let registration = product::get_product_registration(env.clone(), current_id.clone())?;
+
+        let mut parents = Vec::new(&env);
+        for link in get_product_parents(env.clone(), current_id.clone()).iter() {
+            if !visited.contains(&link.parent_id) {
+                if visited.len() >= MAX_TRACE_NODES {
+                    return Err(SupplyChainError::TraceLimitExceeded);
+                }
+                visited.push_back(link.parent_id.clone());
+            }
+            parents.push_back(link.parent_id);
+        }
+
+        nodes.push_back(TraceNode {
+            product_id: current_id,
+            farmer_id: current.farmer_id,
+            batch_number: registration.batch_number,
+            origin_location: registration.origin_location,
+            parents,
+        });
+    }
+
+    Ok(nodes)
+}
+
+/// List the distinct farmers of every origin batch a product was derived from
+pub fn get_origin_farms(
+    env: Env,
+    product_id: BytesN<32>,
+) -> Result<Vec<Address>, SupplyChainError> {
+    let mut farms: Vec<Address> = Vec::new(&env);
+    for node in get_batch_trace(env.clone(), product_id)?.iter() {
+        if node.parents.is_empty() && !farms.contains(&node.farmer_id) {
+            farms.push_back(node.farmer_id);
+        }
+    }
+
+    Ok(farms)
+}
+
+/// Get the parent links of a split or merged batch
+pub fn get_product_parents(env: Env, product_id: BytesN<32>) -> Vec<BatchLink> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProductParents(product_id))
+        .unwrap_or_else(|| Vec::new(&env))
+}
+
+/// Get the batches a product was split or merged into
+pub fn get_product_children(env: Env, product_id: BytesN<32>) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProductChildren(product_id))
+        .unwrap_or_else(|| Vec::new(&env))
+}
+
+/// Count the leading stages a split or merged batch copied from its primary parent
+pub fn inherited_stage_count(env: &Env, product_id: &BytesN<32>) -> u32 {
+    match get_product_parents(env.clone(), product_id.clone()).first() {
+        // Parents are consumed, so their history no longer grows
+        Some(link) => product::get_product_details(env.clone(), link.parent_id)
+            .map(|parent| parent.stages.len())
+            .unwrap_or(0),
+        None => 0,
+    }
+}
+
+/// Check whether a product has already been split or merged
+pub fn is_consumed(env: &Env, product_id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::ProductChildren(product_id.clone()))
+}
+
+/// Helper function to load a product that can still be split or merged
+fn load_unconsumed(env: &Env, product_id: &BytesN<32>) -> Result<Product, SupplyChainError> {
+    let product = product::get_product_details(env.clone(), product_id.clone())?;
+
+    if is_consumed(env, product_id) {
+        return Err(SupplyChainError::ProductConsumed);
+    }
+    // Recalled lots must not reach new batches
+    if recall::is_recalled(env, product_id) {
+        return Err(SupplyChainError::ProductRecalled);
+    }
+
+    Ok(product)
+}
+
+/// Helper function to snapshot the head of a parent's stage chain for its children
+fn link_to(env: &Env, parent: &Product) -> BatchLink {
+    BatchLink {
+        parent_id: parent.product_id.clone(),
+        parent_hash: utils::chain_head(env, parent),
+    }
+}
+
+/// Helper function to get the tier value of the latest stage (0 if none)
+fn current_tier_value(product: &Product) -> u32 {
+    match product.stages.last() {
+        Some(stage) => stage.tier.value(),
+        None => 0,
+    }
+}
+
+/// Helper function to register a child batch continuing `template`'s history
+fn create_child(
+    env: &Env,
+    template: &Product,
+    registration: &ProductRegistration,
+    batch_number: String,
+    certificate_id: CertificateId,
+    links: &Vec<BatchLink>,
+) -> Result<BytesN<32>, SupplyChainError> {
+    if batch_number.is_empty() {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    let child_id = utils::generate_product_id(
+        env,
+        &template.farmer_id,
+        &registration.product_type,
+        &batch_number,
+    );
+
+    // Check if product already exists
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Product(child_id.clone()))
+    {
+        return Err(SupplyChainError::DuplicateProduct);
+    }
+
+    // The inherited history is re-linked onto a genesis committing to every parent
+    let genesis = utils::lineage_genesis(env, &child_id, &template.farmer_id, links);
+    let child = Product {
+        product_id: child_id.clone(),
+        farmer_id: template.farmer_id.clone(),
+        stages: utils::rechain_stages(env, &template.stages, genesis),
+        certificate_id,
+    };
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Product(child_id.clone()), &child);
+    env.storage().persistent().set(
+        &DataKey::ProductRegistration(child_id.clone()),
+        &ProductRegistration {
+            product_type: registration.product_type.clone(),
+            batch_number: batch_number.clone(),
+            origin_location: registration.origin_location.clone(),
+            metadata_hash: registration.metadata_hash.clone(),
+            registered_at: env.ledger().timestamp(),
+        },
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProductParents(child_id.clone()), links);
+
+    product::update_farmer_products(env, &template.farmer_id, &child_id)?;
+    product::update_product_type_index(env, &registration.product_type, &child_id)?;
+    product::update_batch_index(env, &batch_number, &child_id);
+
+    Ok(child_id)
+}
@@ -1,8 +1,13 @@
-use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Symbol, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Map, String, Symbol, Vec};
 
 pub const CERTIFICATE_MANAGEMENT_CONTRACT_KEY: &str = "cert_mgmt_contract";
 pub const MAX_PRODUCTS_PER_FARMER: u32 = 1000;
 pub const MAX_PRODUCTS_PER_TYPE: u32 = 5000;
+pub const MAX_SPLIT_CHILDREN: u32 = 50;
+pub const MAX_MERGE_PARENTS: u32 = 20;
+pub const MAX_TRACE_NODES: u32 = 200;
+pub const MAX_PAGE_SIZE: u32 = 100;
+pub const MAX_RECALL_PRODUCTS: u32 = 500;
 
 /// Storage keys for different data types
 #[contracttype]
@@ -13,8 +18,22 @@ pub enum DataKey {
     ProductRegistration(BytesN<32>), // Product ID -> ProductRegistration details
     FarmerProducts(Address), // Farmer -> Vec<BytesN<32>>
     ProductTypeIndex(String), // Product Type -> Vec<BytesN<32>>
-    StageValidation(u32), // Stage validation rules
+    StageValidation(u32), // Tier value -> StageValidation rules
     QRCodeMapping(String), // QR Code -> BytesN<32>
+    ProductParents(BytesN<32>), // Product ID -> Vec<BatchLink>
+    ProductChildren(BytesN<32>), // Product ID -> Vec<BytesN<32>>
+    Accreditation(Address, Address), // (Approver, Actor) -> Accreditation
+    SensorOracle(Address), // Sensor -> bool (registered)
+    ConditionRange(String), // Product Type -> ConditionRange
+    ColdChainStatus(BytesN<32>), // Product ID -> ColdChainStatus
+    TelemetryReading(BytesN<32>, u32), // (Product ID, index) -> TelemetryReading
+    Excursion(BytesN<32>, u32), // (Product ID, index) -> Excursion
+    BatchIndex(String),  // Batch Number -> Vec<BytesN<32>>
+    RecallAuthority(Address), // Authority -> bool (may issue recalls)
+    RecallCounter,       // Last issued recall ID
+    Recall(u32),         // Recall ID -> Recall
+    RecallAffected(u32, u32), // (Recall ID, index) -> Product ID
+    ProductRecall(BytesN<32>), // Product ID -> Recall ID
 }
 
 /// Product structure
@@ -43,6 +62,26 @@ pub struct ProductRegistration {
     pub batch_number: String,
     pub origin_location: String,
     pub metadata_hash: BytesN<32>,
+    pub registered_at: u64,
+}
+
+/// Link from a split or merged batch to one of its parent batches
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchLink {
+    pub parent_id: BytesN<32>,
+    pub parent_hash: BytesN<32>, // Head of the parent's stage chain at the time of the split/merge
+}
+
+/// One product batch in the lineage DAG returned by `get_batch_trace`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceNode {
+    pub product_id: BytesN<32>,
+    pub farmer_id: Address,
+    pub batch_number: String,
+    pub origin_location: String,
+    pub parents: Vec<BytesN<32>>,
 }
 
 /// Stage structure
@@ -54,7 +93,32 @@ pub struct Stage {
     pub name: String,
     pub timestamp: u64,
     pub location: String,
-    pub data_hash: BytesN<32>, // Hash of off-chain data
+    pub data_hash: BytesN<32>,         // Hash of off-chain data
+    pub handler: Address,              // Actor that recorded the stage
+    pub metadata: Map<String, String>, // Metadata checked against StageValidation.required_fields
+    pub prev_hash: BytesN<32>,         // Cumulative hash of the previous stage (or chain genesis)
+    pub stage_hash: BytesN<32>,        // sha256(prev_hash || content hash of this stage)
+}
+
+/// Details of a stage to record, including its metadata
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StageInput {
+    pub tier: StageTier,
+    pub name: String,
+    pub location: String,
+    pub data_hash: BytesN<32>,
+    pub metadata: Map<String, String>,
+}
+
+/// Data a third party needs to verify one stage against a published chain root
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StageProof {
+    pub product_id: BytesN<32>,
+    pub stage: Stage,
+    pub later_content_hashes: Vec<BytesN<32>>, // Content hashes of every later stage, in order
+    pub root: BytesN<32>,                      // Cumulative hash of the latest stage
 }
 
 /// Stage tiers in the agricultural supply chain process
@@ -113,12 +177,138 @@ impl StageTier {
     }
 }
 
+/// Roles of supply chain actors that may record stages
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ActorRole {
+    Grower,      // Planting, Cultivation, Harvesting
+    Processor,   // Processing, Packaging
+    Warehouse,   // Storage
+    Transporter, // Transportation
+    Distributor, // Distribution
+    Retailer,    // Retail, Consumer
+}
+
+impl ActorRole {
+    /// Check whether this role may record a stage of the given tier
+    pub fn allows(&self, tier: &StageTier) -> bool {
+        matches!(
+            (self, tier),
+            (
+                ActorRole::Grower,
+                StageTier::Planting | StageTier::Cultivation | StageTier::Harvesting
+            ) | (
+                ActorRole::Processor,
+                StageTier::Processing | StageTier::Packaging
+            ) | (ActorRole::Warehouse, StageTier::Storage)
+                | (ActorRole::Transporter, StageTier::Transportation)
+                | (ActorRole::Distributor, StageTier::Distribution)
+                | (ActorRole::Retailer, StageTier::Retail | StageTier::Consumer)
+        )
+    }
+}
+
+/// Approval of an actor by the admin (all products) or a farmer (own products)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Accreditation {
+    pub actor: Address,
+    pub roles: Vec<ActorRole>,
+    pub approved_by: Address,
+    pub approved_at: u64,
+    pub revoked: bool,
+}
+
+/// Products a recall applies to, before propagation to descendants
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecallScope {
+    Product(BytesN<32>),
+    Batch(String),                  // Every product registered with this batch number
+    FarmerRange(Address, u64, u64), // Farmer's products registered within [from, to]
+}
+
+/// A recall issued by the admin or a certifying authority
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Recall {
+    pub recall_id: u32,
+    pub scope: RecallScope,
+    pub reason: String,
+    pub issued_by: Address,
+    pub issued_at: u64,
+    pub affected_count: u32,
+}
+
+/// Recall status reported alongside a product trace
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecallStatus {
+    NotRecalled,
+    Recalled(Recall),
+}
+
+/// Allowed storage conditions for a product type, in tenths of a unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionRange {
+    pub min_temperature: i32, // Tenths of a degree Celsius
+    pub max_temperature: i32,
+    pub min_humidity: u32, // Tenths of a percent relative humidity
+    pub max_humidity: u32,
+}
+
+/// A telemetry reading submitted by a sensor oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TelemetryReading {
+    pub sensor: Address,
+    pub stage_id: u32, // Stage the product was in when the reading was taken
+    pub temperature: i32,
+    pub humidity: u32,
+    pub recorded_at: u64,
+}
+
+/// A reading that fell outside the allowed condition range
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Excursion {
+    pub reading_index: u32,
+    pub stage_id: u32,
+    pub tier: StageTier,
+    pub temperature: i32,
+    pub humidity: u32,
+    pub recorded_at: u64,
+    pub temperature_breach: bool,
+    pub humidity_breach: bool,
+}
+
+/// Telemetry counters for a product
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColdChainStatus {
+    pub reading_count: u32,
+    pub excursion_count: u32,
+    pub last_reading_at: u64,
+    pub last_excursion_at: u64,
+}
+
+/// Result of an authenticity check, including cold-chain breaches across the batch lineage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthenticityReport {
+    pub authentic: bool,
+    pub cold_chain_breaches: u32,
+    pub last_breach_at: u64,
+}
+
+/// Admin-configured rules for the stages of one tier
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StageValidation {
-    pub required_fields: Vec<String>,
-    pub allowed_transitions: Vec<u32>,
-    pub minimum_duration: u64,
+    pub required_fields: Vec<String>, // Metadata keys a stage of this tier must carry
+    pub allowed_transitions: Vec<u32>, // Tiers that may follow this one (empty: only the next tier)
+    pub minimum_duration: u64,        // Seconds a product must remain at this tier
 }
 
 /// Error types for supply chain tracking operations
@@ -143,6 +333,20 @@ pub enum SupplyChainError {
     DuplicateStageTier = 16,
     InvalidTierProgression = 17,
     ProductLimitExceeded = 18,
+    ProductConsumed = 27,
+    IncompatibleBatches = 28,
+    TraceLimitExceeded = 29,
+    ActorNotAccredited = 30,
+    TierNotPermitted = 31,
+    MinimumDurationNotMet = 32,
+    MissingRequiredField = 33,
+    ValidationRuleNotFound = 34,
+    SensorNotRegistered = 35,
+    ConditionRangeNotFound = 36,
+    ProductNotMonitored = 37,
+    RecallNotFound = 38,
+    RecallLimitExceeded = 39,
+    ProductRecalled = 40,
 }
 
 // Certificate datatypes
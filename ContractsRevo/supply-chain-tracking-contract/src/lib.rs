@@ -1,8 +1,12 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Vec};
 
+mod actors;
+mod batch;
 mod datatypes;
 mod product;
+mod recall;
+mod telemetry;
 mod tracking;
 mod utils;
 mod validation;
@@ -90,6 +94,33 @@ impl SupplyChainTrackingContract {
             .ok_or(SupplyChainError::NotInitialized)
     }
 
+    /// Configure validation rules (allowed transitions, dwell time, required metadata) for a tier
+    pub fn set_stage_validation(
+        env: Env,
+        admin: Address,
+        tier: StageTier,
+        rules: StageValidation,
+    ) -> Result<(), SupplyChainError> {
+        tracking::set_stage_validation(env, admin, tier, rules)
+    }
+
+    /// Remove the validation rules for a tier
+    pub fn remove_stage_validation(
+        env: Env,
+        admin: Address,
+        tier: StageTier,
+    ) -> Result<(), SupplyChainError> {
+        tracking::remove_stage_validation(env, admin, tier)
+    }
+
+    /// Get the validation rules configured for a tier
+    pub fn get_stage_validation(
+        env: Env,
+        tier: StageTier,
+    ) -> Result<StageValidation, SupplyChainError> {
+        tracking::get_stage_validation(env, tier)
+    }
+
     /// Get the contract admin
     pub fn get_admin(env: Env) -> Result<Address, SupplyChainError> {
         env.storage()
@@ -129,9 +160,24 @@ impl SupplyChainTrackingContract {
         handler: Address,
         data_hash: BytesN<32>,
     ) -> Result<u32, SupplyChainError> {
-        tracking::add_stage(
-            env, product_id, stage_tier, stage_name, location, handler, data_hash,
-        )
+        let input = StageInput {
+            tier: stage_tier,
+            name: stage_name,
+            location,
+            data_hash,
+            metadata: Map::new(&env),
+        };
+        tracking::add_stage(env, product_id, handler, input)
+    }
+
+    /// Record a new stage carrying metadata, as required by some tiers' validation rules
+    pub fn add_stage_with_metadata(
+        env: Env,
+        product_id: BytesN<32>,
+        handler: Address,
+        input: StageInput,
+    ) -> Result<u32, SupplyChainError> {
+        tracking::add_stage(env, product_id, handler, input)
     }
 
     /// Retrieve the full lifecycle of a product
@@ -142,13 +188,14 @@ impl SupplyChainTrackingContract {
         tracking::get_product_trace(env, product_id)
     }
 
-    /// Validate product authenticity against recorded data and certifications
+    /// Validate product authenticity against recorded data and certifications,
+    /// reporting any cold-chain breaches along the batch lineage
     pub fn verify_authenticity(
         env: Env,
         farmer_id: Address,
         product_id: BytesN<32>,
         verification_data: BytesN<32>,
-    ) -> Result<bool, SupplyChainError> {
+    ) -> Result<AuthenticityReport, SupplyChainError> {
         validation::verify_authenticity(env, farmer_id, product_id, verification_data)
     }
 
@@ -162,6 +209,214 @@ impl SupplyChainTrackingContract {
         validation::link_certificate(env, product_id, certificate_id, authority)
     }
 
+    // ========== ACTOR REGISTRY FUNCTIONS ==========
+
+    /// Approve an actor for roles; admin approvals cover all products, farmer approvals their own
+    pub fn approve_actor(
+        env: Env,
+        approver: Address,
+        actor: Address,
+        roles: Vec<ActorRole>,
+    ) -> Result<(), SupplyChainError> {
+        actors::approve_actor(env, approver, actor, roles)
+    }
+
+    /// Revoke an accreditation previously granted by the approver
+    pub fn revoke_actor(
+        env: Env,
+        approver: Address,
+        actor: Address,
+    ) -> Result<(), SupplyChainError> {
+        actors::revoke_actor(env, approver, actor)
+    }
+
+    /// Get the accreditation an approver granted to an actor
+    pub fn get_accreditation(
+        env: Env,
+        approver: Address,
+        actor: Address,
+    ) -> Result<Accreditation, SupplyChainError> {
+        actors::get_accreditation(env, approver, actor)
+    }
+
+    // ========== COLD CHAIN FUNCTIONS ==========
+
+    /// Register a sensor oracle allowed to submit telemetry (admin only)
+    pub fn register_sensor(
+        env: Env,
+        admin: Address,
+        sensor: Address,
+    ) -> Result<(), SupplyChainError> {
+        telemetry::register_sensor(env, admin, sensor)
+    }
+
+    /// Remove a sensor oracle (admin only)
+    pub fn remove_sensor(
+        env: Env,
+        admin: Address,
+        sensor: Address,
+    ) -> Result<(), SupplyChainError> {
+        telemetry::remove_sensor(env, admin, sensor)
+    }
+
+    /// Check whether a sensor oracle is registered
+    pub fn is_sensor_registered(env: Env, sensor: Address) -> bool {
+        telemetry::is_sensor_registered(env, sensor)
+    }
+
+    /// Configure the allowed temperature and humidity range for a product type (admin only)
+    pub fn set_condition_range(
+        env: Env,
+        admin: Address,
+        product_type: String,
+        range: ConditionRange,
+    ) -> Result<(), SupplyChainError> {
+        telemetry::set_condition_range(env, admin, product_type, range)
+    }
+
+    /// Get the allowed condition range for a product type
+    pub fn get_condition_range(
+        env: Env,
+        product_type: String,
+    ) -> Result<ConditionRange, SupplyChainError> {
+        telemetry::get_condition_range(env, product_type)
+    }
+
+    /// Submit a sensor reading for a product in Storage or Transportation; false on excursion
+    pub fn submit_reading(
+        env: Env,
+        sensor: Address,
+        product_id: BytesN<32>,
+        temperature: i32,
+        humidity: u32,
+        recorded_at: u64,
+    ) -> Result<bool, SupplyChainError> {
+        telemetry::submit_reading(env, sensor, product_id, temperature, humidity, recorded_at)
+    }
+
+    /// Get the telemetry counters for a product
+    pub fn get_cold_chain_status(env: Env, product_id: BytesN<32>) -> ColdChainStatus {
+        telemetry::get_cold_chain_status(env, product_id)
+    }
+
+    /// Get a page of a product's telemetry readings
+    pub fn get_readings(
+        env: Env,
+        product_id: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<TelemetryReading> {
+        telemetry::get_readings(env, product_id, start, limit)
+    }
+
+    /// Get a page of a product's cold-chain excursions
+    pub fn get_excursions(
+        env: Env,
+        product_id: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Excursion> {
+        telemetry::get_excursions(env, product_id, start, limit)
+    }
+
+    // ========== RECALL FUNCTIONS ==========
+
+    /// Allow a certifying authority to issue recalls (admin only)
+    pub fn add_recall_authority(
+        env: Env,
+        admin: Address,
+        authority: Address,
+    ) -> Result<(), SupplyChainError> {
+        recall::add_recall_authority(env, admin, authority)
+    }
+
+    /// Revoke a certifying authority's right to issue recalls (admin only)
+    pub fn remove_recall_authority(
+        env: Env,
+        admin: Address,
+        authority: Address,
+    ) -> Result<(), SupplyChainError> {
+        recall::remove_recall_authority(env, admin, authority)
+    }
+
+    /// Recall products by ID, batch number or farmer and date range, including descendants
+    pub fn issue_recall(
+        env: Env,
+        issuer: Address,
+        scope: RecallScope,
+        reason: String,
+    ) -> Result<u32, SupplyChainError> {
+        recall::issue_recall(env, issuer, scope, reason)
+    }
+
+    /// Get a recall by ID
+    pub fn get_recall(env: Env, recall_id: u32) -> Result<Recall, SupplyChainError> {
+        recall::get_recall(env, recall_id)
+    }
+
+    /// Get the recall status of a product
+    pub fn get_recall_status(env: Env, product_id: BytesN<32>) -> RecallStatus {
+        recall::get_recall_status(env, product_id)
+    }
+
+    /// Get a page of the products affected by a recall
+    pub fn list_recalled_products(
+        env: Env,
+        recall_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<BytesN<32>>, SupplyChainError> {
+        recall::list_recalled_products(env, recall_id, start, limit)
+    }
+
+    // ========== BATCH FUNCTIONS ==========
+
+    /// Split a product batch into child batches inheriting its stage history
+    pub fn split_product(
+        env: Env,
+        parent_id: BytesN<32>,
+        handler: Address,
+        batch_numbers: Vec<String>,
+    ) -> Result<Vec<BytesN<32>>, SupplyChainError> {
+        batch::split_product(env, parent_id, handler, batch_numbers)
+    }
+
+    /// Merge several batches of the same product type into one blended batch
+    pub fn merge_products(
+        env: Env,
+        parent_ids: Vec<BytesN<32>>,
+        handler: Address,
+        batch_number: String,
+    ) -> Result<BytesN<32>, SupplyChainError> {
+        batch::merge_products(env, parent_ids, handler, batch_number)
+    }
+
+    /// Walk the split/merge lineage of a product back to every origin batch
+    pub fn get_batch_trace(
+        env: Env,
+        product_id: BytesN<32>,
+    ) -> Result<Vec<TraceNode>, SupplyChainError> {
+        batch::get_batch_trace(env, product_id)
+    }
+
+    /// List the farmers of every origin batch a product was derived from
+    pub fn get_origin_farms(
+        env: Env,
+        product_id: BytesN<32>,
+    ) -> Result<Vec<Address>, SupplyChainError> {
+        batch::get_origin_farms(env, product_id)
+    }
+
+    /// Get the parent links of a split or merged batch
+    pub fn get_product_parents(env: Env, product_id: BytesN<32>) -> Vec<BatchLink> {
+        batch::get_product_parents(env, product_id)
+    }
+
+    /// Get the batches a product was split or merged into
+    pub fn get_product_children(env: Env, product_id: BytesN<32>) -> Vec<BytesN<32>> {
+        batch::get_product_children(env, product_id)
+    }
+
     // ========== ADDITIONAL FUNCTIONS ==========
 
     /// Get detailed information about a specific product
@@ -196,6 +451,11 @@ impl SupplyChainTrackingContract {
         product::list_products_by_type(env, product_type)
     }
 
+    /// List products registered with a batch number
+    pub fn list_products_by_batch(env: Env, batch_number: String) -> Vec<BytesN<32>> {
+        product::list_products_by_batch(env, batch_number)
+    }
+
     /// Validate stage transition logic
     pub fn validate_stage_transition(
         env: Env,
@@ -228,12 +488,12 @@ impl SupplyChainTrackingContract {
         tracking::get_stage_by_id(env, product_id, stage_id)
     }
 
-    /// Get the next expected tier for a product
-    pub fn get_next_expected_tier(
+    /// Get the tiers a product may move to next, following the configured transitions
+    pub fn get_next_expected_tiers(
         env: Env,
         product_id: BytesN<32>,
-    ) -> Result<Option<StageTier>, SupplyChainError> {
-        tracking::get_next_expected_tier(env, product_id)
+    ) -> Result<Vec<StageTier>, SupplyChainError> {
+        tracking::get_next_expected_tiers(env, product_id)
     }
 
     /// Get the current tier for a product
@@ -244,13 +504,14 @@ impl SupplyChainTrackingContract {
         tracking::get_current_tier(env, product_id)
     }
 
-    /// Get product trace using QR code
+    /// Get product trace and recall status using QR code
     pub fn trace_by_qr_code(
         env: Env,
         qr_code: String,
-    ) -> Result<(Product, Vec<Stage>), SupplyChainError> {
+    ) -> Result<(Product, Vec<Stage>, RecallStatus), SupplyChainError> {
         let product_id = utils::resolve_qr_code(&env, &qr_code)?;
-        tracking::get_product_trace(env, product_id)
+        let (product, stages) = tracking::get_product_trace(env.clone(), product_id.clone())?;
+        Ok((product, stages, recall::get_recall_status(env, product_id)))
     }
 
     /// Get linked certificate for a product
@@ -266,6 +527,29 @@ impl SupplyChainTrackingContract {
         utils::verify_hash_chain(&env, &product_id)
     }
 
+    /// Get the latest cumulative stage hash, the root stage proofs verify against
+    pub fn get_chain_root(
+        env: Env,
+        product_id: BytesN<32>,
+    ) -> Result<BytesN<32>, SupplyChainError> {
+        let product = product::get_product_details(env.clone(), product_id)?;
+        Ok(utils::chain_head(&env, &product))
+    }
+
+    /// Get the data needed to verify one stage against the product's chain root
+    pub fn get_stage_proof(
+        env: Env,
+        product_id: BytesN<32>,
+        stage_id: u32,
+    ) -> Result<StageProof, SupplyChainError> {
+        utils::get_stage_proof(&env, &product_id, stage_id)
+    }
+
+    /// Check a stage proof against the root it carries
+    pub fn verify_stage_proof(env: Env, proof: StageProof) -> bool {
+        utils::verify_stage_proof(&env, &proof)
+    }
+
     /// Generate QR code data for consumer access to traceability
     pub fn generate_qr_code(env: Env, product_id: BytesN<32>) -> Result<String, SupplyChainError> {
         utils::generate_qr_code_data(&env, &product_id)
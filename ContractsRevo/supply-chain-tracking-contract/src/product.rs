@@ -52,6 +52,7 @@ pub fn register_product(
         batch_number: batch_number.clone(),
         origin_location: origin_location.clone(),
         metadata_hash: metadata_hash.clone(),
+        registered_at: env.ledger().timestamp(),
     };
 
     // Store the registration details
@@ -66,6 +67,9 @@ pub fn register_product(
     // Update product type index for traceability
     update_product_type_index(&env, &product_type, &product_id)?;
 
+    // Update batch index for recalls
+    update_batch_index(&env, &batch_number, &product_id);
+
     // Emit event
     env.events().publish(
         (Symbol::new(&env, "product_registered"), farmer_id),
@@ -123,7 +127,7 @@ pub fn list_products_by_type(
 }
 
 /// Helper function to update farmer's product list
-fn update_farmer_products(
+pub(crate) fn update_farmer_products(
     env: &Env,
     farmer_id: &Address,
     product_id: &BytesN<32>,
@@ -152,7 +156,7 @@ fn update_farmer_products(
 }
 
 /// Helper function to update product type index
-fn update_product_type_index(
+pub(crate) fn update_product_type_index(
     env: &Env,
     product_type: &String,
     product_id: &BytesN<32>,
@@ -179,3 +183,24 @@ fn update_product_type_index(
 
     Ok(())
 }
+
+/// Helper function to update the batch number index
+pub(crate) fn update_batch_index(env: &Env, batch_number: &String, product_id: &BytesN<32>) {
+    let key = DataKey::BatchIndex(batch_number.clone());
+    let mut products: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+
+    products.push_back(product_id.clone());
+    env.storage().persistent().set(&key, &products);
+}
+
+/// List products registered with a batch number
+pub fn list_products_by_batch(env: Env, batch_number: String) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BatchIndex(batch_number))
+        .unwrap_or_else(|| Vec::new(&env))
+}
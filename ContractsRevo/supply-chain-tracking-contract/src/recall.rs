@@ -0,0 +1,233 @@
+use crate::datatypes::{
+    DataKey, Recall, RecallScope, RecallStatus, SupplyChainError, MAX_RECALL_PRODUCTS,
+};
+use crate::{batch, product, utils};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+/// Allow a certifying authority to issue recalls (admin only)
+pub fn add_recall_authority(
+    env: Env,
+    admin: Address,
+    authority: Address,
+) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::RecallAuthority(authority.clone()), &true);
+
+    env.events().publish(
+        (Symbol::new(&env, "recall_authority_added"), admin),
+        authority,
+    );
+
+    Ok(())
+}
+
+/// Revoke a certifying authority's right to issue recalls (admin only)
+pub fn remove_recall_authority(
+    env: Env,
+    admin: Address,
+    authority: Address,
+) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RecallAuthority(authority.clone()));
+
+    env.events().publish(
+        (Symbol::new(&env, "recall_authority_removed"), admin),
+        authority,
+    );
+
+    Ok(())
+}
+
+/// Recall the products matching a scope along with every product split or merged from them
+pub fn issue_recall(
+    env: Env,
+    issuer: Address,
+    scope: RecallScope,
+    reason: String,
+) -> Result<u32, SupplyChainError> {
+    issuer.require_auth();
+
+    // Only the admin or a registered certifying authority may recall
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SupplyChainError::NotInitialized)?;
+    if issuer != admin
+        && !env
+            .storage()
+            .persistent()
+            .has(&DataKey::RecallAuthority(issuer.clone()))
+    {
+        return Err(SupplyChainError::UnauthorizedAccess);
+    }
+
+    // Validate input data
+    if reason.is_empty() {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    let affected = collect_affected(&env, resolve_scope(&env, &scope)?)?;
+
+    let recall_id: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::RecallCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::RecallCounter, &recall_id);
+
+    for (index, product_id) in affected.iter().enumerate() {
+        env.storage().persistent().set(
+            &DataKey::RecallAffected(recall_id, index as u32),
+            &product_id,
+        );
+
+        // A product keeps the first recall that reached it
+        let recall_key = DataKey::ProductRecall(product_id);
+        if !env.storage().persistent().has(&recall_key) {
+            env.storage().persistent().set(&recall_key, &recall_id);
+        }
+    }
+
+    let recall = Recall {
+        recall_id,
+        scope,
+        reason,
+        issued_by: issuer.clone(),
+        issued_at: env.ledger().timestamp(),
+        affected_count: affected.len(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Recall(recall_id), &recall);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(&env, "recall_issued"), issuer),
+        (recall_id, recall.affected_count),
+    );
+
+    Ok(recall_id)
+}
+
+/// Get a recall by ID
+pub fn get_recall(env: Env, recall_id: u32) -> Result<Recall, SupplyChainError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Recall(recall_id))
+        .ok_or(SupplyChainError::RecallNotFound)
+}
+
+/// Get the recall status of a product
+pub fn get_recall_status(env: Env, product_id: BytesN<32>) -> RecallStatus {
+    let recall_id: Option<u32> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ProductRecall(product_id));
+
+    match recall_id.and_then(|recall_id| get_recall(env, recall_id).ok()) {
+        Some(recall) => RecallStatus::Recalled(recall),
+        None => RecallStatus::NotRecalled,
+    }
+}
+
+/// Check whether a product is under recall
+pub fn is_recalled(env: &Env, product_id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::ProductRecall(product_id.clone()))
+}
+
+/// Get a page of the products affected by a recall
+pub fn list_recalled_products(
+    env: Env,
+    recall_id: u32,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<BytesN<32>>, SupplyChainError> {
+    let recall = get_recall(env.clone(), recall_id)?;
+
+    let mut products = Vec::new(&env);
+    for index in utils::page_range(start, limit, recall.affected_count) {
+        if let Some(product_id) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecallAffected(recall_id, index))
+        {
+            products.push_back(product_id);
+        }
+    }
+
+    Ok(products)
+}
+
+/// Helper function to find the products a recall scope names directly
+fn resolve_scope(env: &Env, scope: &RecallScope) -> Result<Vec<BytesN<32>>, SupplyChainError> {
+    let products = match scope {
+        RecallScope::Product(product_id) => {
+            product::get_product_details(env.clone(), product_id.clone())?;
+            Vec::from_array(env, [product_id.clone()])
+        }
+        RecallScope::Batch(batch_number) => {
+            product::list_products_by_batch(env.clone(), batch_number.clone())
+        }
+        RecallScope::FarmerRange(farmer_id, from, to) => {
+            if from > to {
+                return Err(SupplyChainError::InvalidInput);
+            }
+
+            let mut products = Vec::new(env);
+            for product_id in
+                product::list_products_by_farmer(env.clone(), farmer_id.clone())?.iter()
+            {
+                let registration =
+                    product::get_product_registration(env.clone(), product_id.clone())?;
+                if registration.registered_at >= *from && registration.registered_at <= *to {
+                    products.push_back(product_id);
+                }
+            }
+            products
+        }
+    };
+
+    if products.is_empty() {
+        return Err(SupplyChainError::ProductNotFound);
+    }
+
+    Ok(products)
+}
+
+/// Helper function to extend recalled products with all of their descendants
+fn collect_affected(
+    env: &Env,
+    roots: Vec<BytesN<32>>,
+) -> Result<Vec<BytesN<32>>, SupplyChainError> {
+    let mut affected: Vec<BytesN<32>> = Vec::new(env);
+    let mut pending = roots;
+
+    while let Some(product_id) = pending.pop_front() {
+        if affected.contains(&product_id) {
+            continue;
+        }
+        if affected.len() >= MAX_RECALL_PRODUCTS {
+            return Err(SupplyChainError::RecallLimitExceeded);
+        }
+
+        pending.append(&batch::get_product_children(
+            env.clone(),
+            product_id.clone(),
+        ));
+        affected.push_back(product_id);
+    }
+
+    Ok(affected)
+}
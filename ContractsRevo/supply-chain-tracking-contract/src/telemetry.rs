@@ -0,0 +1,243 @@
+use crate::datatypes::{
+    ColdChainStatus, ConditionRange, DataKey, Excursion, StageTier, SupplyChainError,
+    TelemetryReading,
+};
+use crate::{batch, product, utils};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+/// Register a sensor oracle allowed to submit telemetry (admin only)
+pub fn register_sensor(env: Env, admin: Address, sensor: Address) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::SensorOracle(sensor.clone()), &true);
+
+    env.events()
+        .publish((Symbol::new(&env, "sensor_registered"), admin), sensor);
+
+    Ok(())
+}
+
+/// Remove a sensor oracle (admin only)
+pub fn remove_sensor(env: Env, admin: Address, sensor: Address) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    let key = DataKey::SensorOracle(sensor.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(SupplyChainError::SensorNotRegistered);
+    }
+    env.storage().persistent().remove(&key);
+
+    env.events()
+        .publish((Symbol::new(&env, "sensor_removed"), admin), sensor);
+
+    Ok(())
+}
+
+/// Check whether a sensor oracle is registered
+pub fn is_sensor_registered(env: Env, sensor: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::SensorOracle(sensor))
+}
+
+/// Configure the allowed temperature and humidity range for a product type (admin only)
+pub fn set_condition_range(
+    env: Env,
+    admin: Address,
+    product_type: String,
+    range: ConditionRange,
+) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    // Validate input data
+    if product_type.is_empty()
+        || range.min_temperature > range.max_temperature
+        || range.min_humidity > range.max_humidity
+        || range.max_humidity > 1000
+    {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ConditionRange(product_type.clone()), &range);
+
+    env.events().publish(
+        (Symbol::new(&env, "condition_range_set"), admin),
+        product_type,
+    );
+
+    Ok(())
+}
+
+/// Get the allowed condition range for a product type
+pub fn get_condition_range(
+    env: Env,
+    product_type: String,
+) -> Result<ConditionRange, SupplyChainError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ConditionRange(product_type))
+        .ok_or(SupplyChainError::ConditionRangeNotFound)
+}
+
+/// Record a signed reading for a product in the Storage or Transportation tier.
+/// Returns false and raises an alert if the reading is outside the allowed range.
+pub fn submit_reading(
+    env: Env,
+    sensor: Address,
+    product_id: BytesN<32>,
+    temperature: i32,
+    humidity: u32,
+    recorded_at: u64,
+) -> Result<bool, SupplyChainError> {
+    sensor.require_auth();
+
+    if !is_sensor_registered(env.clone(), sensor.clone()) {
+        return Err(SupplyChainError::SensorNotRegistered);
+    }
+
+    let product = product::get_product_details(env.clone(), product_id.clone())?;
+    let registration = product::get_product_registration(env.clone(), product_id.clone())?;
+    let range = get_condition_range(env.clone(), registration.product_type)?;
+
+    // Only cold-chain tiers are monitored
+    let stage = product
+        .stages
+        .last()
+        .ok_or(SupplyChainError::ProductNotMonitored)?;
+    if stage.tier != StageTier::Storage && stage.tier != StageTier::Transportation {
+        return Err(SupplyChainError::ProductNotMonitored);
+    }
+
+    // Readings must belong to the current stage and arrive in order
+    let mut status = get_cold_chain_status(env.clone(), product_id.clone());
+    if recorded_at > env.ledger().timestamp()
+        || recorded_at < stage.timestamp
+        || recorded_at < status.last_reading_at
+    {
+        return Err(SupplyChainError::InvalidInput);
+    }
+
+    let reading_index = status.reading_count;
+    env.storage().persistent().set(
+        &DataKey::TelemetryReading(product_id.clone(), reading_index),
+        &TelemetryReading {
+            sensor,
+            stage_id: stage.stage_id,
+            temperature,
+            humidity,
+            recorded_at,
+        },
+    );
+    status.reading_count += 1;
+    status.last_reading_at = recorded_at;
+
+    let temperature_breach =
+        temperature < range.min_temperature || temperature > range.max_temperature;
+    let humidity_breach = humidity < range.min_humidity || humidity > range.max_humidity;
+    let within_range = !temperature_breach && !humidity_breach;
+
+    if !within_range {
+        let excursion = Excursion {
+            reading_index,
+            stage_id: stage.stage_id,
+            tier: stage.tier,
+            temperature,
+            humidity,
+            recorded_at,
+            temperature_breach,
+            humidity_breach,
+        };
+        env.storage().persistent().set(
+            &DataKey::Excursion(product_id.clone(), status.excursion_count),
+            &excursion,
+        );
+        status.excursion_count += 1;
+        status.last_excursion_at = recorded_at;
+
+        // Emit alert
+        env.events().publish(
+            (
+                Symbol::new(&env, "cold_chain_excursion"),
+                product_id.clone(),
+            ),
+            excursion,
+        );
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ColdChainStatus(product_id), &status);
+
+    Ok(within_range)
+}
+
+/// Get the telemetry counters for a product
+pub fn get_cold_chain_status(env: Env, product_id: BytesN<32>) -> ColdChainStatus {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ColdChainStatus(product_id))
+        .unwrap_or(ColdChainStatus {
+            reading_count: 0,
+            excursion_count: 0,
+            last_reading_at: 0,
+            last_excursion_at: 0,
+        })
+}
+
+/// Get a page of a product's telemetry readings
+pub fn get_readings(
+    env: Env,
+    product_id: BytesN<32>,
+    start: u32,
+    limit: u32,
+) -> Vec<TelemetryReading> {
+    let status = get_cold_chain_status(env.clone(), product_id.clone());
+    let mut readings = Vec::new(&env);
+    for index in utils::page_range(start, limit, status.reading_count) {
+        if let Some(reading) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TelemetryReading(product_id.clone(), index))
+        {
+            readings.push_back(reading);
+        }
+    }
+    readings
+}
+
+/// Get a page of a product's recorded excursions
+pub fn get_excursions(env: Env, product_id: BytesN<32>, start: u32, limit: u32) -> Vec<Excursion> {
+    let status = get_cold_chain_status(env.clone(), product_id.clone());
+    let mut excursions = Vec::new(&env);
+    for index in utils::page_range(start, limit, status.excursion_count) {
+        if let Some(excursion) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Excursion(product_id.clone(), index))
+        {
+            excursions.push_back(excursion);
+        }
+    }
+    excursions
+}
+
+/// Count excursions across a product and every batch it was derived from.
+/// Returns the total and the time of the most recent one.
+pub fn lineage_breaches(
+    env: &Env,
+    product_id: &BytesN<32>,
+) -> Result<(u32, u64), SupplyChainError> {
+    let mut breaches = 0;
+    let mut last_breach_at = 0;
+    for node in batch::get_batch_trace(env.clone(), product_id.clone())?.iter() {
+        let status = get_cold_chain_status(env.clone(), node.product_id);
+        breaches += status.excursion_count;
+        last_breach_at = last_breach_at.max(status.last_excursion_at);
+    }
+
+    Ok((breaches, last_breach_at))
+}
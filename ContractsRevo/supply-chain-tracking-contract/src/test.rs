@@ -5,7 +5,7 @@ use soroban_sdk::{
     contract, contractimpl, log, symbol_short,
     testutils::{Address as _, Ledger},
     xdr::ToXdr,
-    Address, BytesN, Env, Map, String, Symbol,
+    Address, Bytes, BytesN, Env, Map, String, Symbol,
 };
 
 // Test Constants
@@ -54,6 +54,9 @@ fn setup_test_environment(
     // Initialize supply chain contract with certificate management contract
     supply_chain_client.initialize(&admin, &cert_mgmt_id);
 
+    // Accredit the handler for every tier across all products
+    supply_chain_client.approve_actor(&admin, &handler, &all_roles(env));
+
     (
         admin,
         farmer,
@@ -64,6 +67,19 @@ fn setup_test_environment(
     )
 }
 
+/// Every actor role, covering all stage tiers
+fn all_roles(env: &Env) -> Vec<ActorRole> {
+    soroban_sdk::vec![
+        env,
+        ActorRole::Grower,
+        ActorRole::Processor,
+        ActorRole::Warehouse,
+        ActorRole::Transporter,
+        ActorRole::Distributor,
+        ActorRole::Retailer,
+    ]
+}
+
 /// Create test product registration data
 fn create_test_product_data(env: &Env, prefix: &str) -> (String, String, String, BytesN<32>) {
     // Helper to concatenate prefix and suffix as bytes, then convert to &str
@@ -1003,10 +1019,10 @@ fn test_add_stage_complete_wrong_sequence() {
     );
 
     // Verify correct next tier is expected
-    let next_tier = supply_chain_client.get_next_expected_tier(&product_id);
+    let next_tier = supply_chain_client.get_next_expected_tiers(&product_id);
     assert_eq!(
         next_tier,
-        Some(StageTier::Harvesting),
+        soroban_sdk::vec![&env, StageTier::Harvesting],
         "Next expected tier should be Harvesting"
     );
 
@@ -1113,8 +1129,11 @@ fn test_add_stage_after_final_tier() {
         "Should be at Consumer tier"
     );
 
-    let next_tier = supply_chain_client.get_next_expected_tier(&product_id);
-    assert_eq!(next_tier, None, "Should have no next tier after Consumer");
+    let next_tier = supply_chain_client.get_next_expected_tiers(&product_id);
+    assert!(
+        next_tier.is_empty(),
+        "Should have no next tier after Consumer"
+    );
 
     // Try to add another stage after Consumer - should fail
     let result = supply_chain_client.try_add_stage(
@@ -1171,10 +1190,10 @@ fn test_tier_validation_edge_cases() {
     );
 
     // Test next expected tier when no stages exist
-    let next_tier = supply_chain_client.get_next_expected_tier(&product_id);
+    let next_tier = supply_chain_client.get_next_expected_tiers(&product_id);
     assert_eq!(
         next_tier,
-        Some(StageTier::Planting),
+        soroban_sdk::vec![&env, StageTier::Planting],
         "Should expect Planting as first tier"
     );
 
@@ -1196,10 +1215,10 @@ fn test_tier_validation_edge_cases() {
         "Should have Planting as current tier"
     );
 
-    let next_tier = supply_chain_client.get_next_expected_tier(&product_id);
+    let next_tier = supply_chain_client.get_next_expected_tiers(&product_id);
     assert_eq!(
         next_tier,
-        Some(StageTier::Cultivation),
+        soroban_sdk::vec![&env, StageTier::Cultivation],
         "Should expect Cultivation as next tier"
     );
 
@@ -1411,10 +1430,10 @@ fn test_add_stage_tier_validation_with_get_functions() {
 
     for (i, tier) in valid_progression.iter().enumerate() {
         // Check expected next tier before adding
-        let expected_next = supply_chain_client.get_next_expected_tier(&product_id);
+        let expected_next = supply_chain_client.get_next_expected_tiers(&product_id);
         assert_eq!(
             expected_next,
-            Some(tier.clone()),
+            soroban_sdk::vec![&env, tier.clone()],
             "Expected next tier should match current tier being added"
         );
 
@@ -1481,10 +1500,10 @@ fn test_add_stage_tier_validation_with_get_functions() {
         "Current tier should be Packaging"
     );
 
-    let next_tier = supply_chain_client.get_next_expected_tier(&product_id);
+    let next_tier = supply_chain_client.get_next_expected_tiers(&product_id);
     assert_eq!(
         next_tier,
-        Some(StageTier::Storage),
+        soroban_sdk::vec![&env, StageTier::Storage],
         "Next expected tier should be Storage"
     );
 }
@@ -1706,16 +1725,21 @@ fn test_verify_authenticity_without_certificate() {
 
     // Calculate the actual supply chain hash using the contract's own hash calculation
     let test_hash = create_test_validation_hash(&env, &farmer, &product_id, 1u8);
-    let is_authentic = supply_chain_client.verify_authenticity(&farmer, &product_id, &test_hash);
+    let report = supply_chain_client.verify_authenticity(&farmer, &product_id, &test_hash);
+    assert_eq!(
+        report.cold_chain_breaches, 0,
+        "No cold-chain breaches recorded"
+    );
     assert!(
-        is_authentic == true,
+        report.authentic == true,
         "Should return a boolean result for authenticity verification"
     );
 
     // Test with definitively wrong hash - should return false
     let wrong_verification_data = BytesN::from_array(&env, &[99u8; 32]);
-    let is_not_authentic =
-        supply_chain_client.verify_authenticity(&farmer, &product_id, &wrong_verification_data);
+    let is_not_authentic = supply_chain_client
+        .verify_authenticity(&farmer, &product_id, &wrong_verification_data)
+        .authentic;
 
     assert_eq!(
         is_not_authentic, false,
@@ -1768,7 +1792,9 @@ fn test_verify_authenticity_with_certificate() {
     );
     supply_chain_client.link_certificate(&product_id, &certificate_id, &authority);
 
-    let is_authentic = supply_chain_client.verify_authenticity(&farmer, &product_id, &test_hash);
+    let is_authentic = supply_chain_client
+        .verify_authenticity(&farmer, &product_id, &test_hash)
+        .authentic;
     assert_eq!(
         is_authentic, true,
         "Should be authentic with correct certificate hash"
@@ -1905,7 +1931,9 @@ fn test_qr_code_generation_and_tracing() {
     assert!(qr_code.len() > 0, "QR code should be generated");
 
     // Use QR code to trace product
-    let (traced_product, traced_stages) = supply_chain_client.trace_by_qr_code(&qr_code);
+    let (traced_product, traced_stages, recall_status) =
+        supply_chain_client.trace_by_qr_code(&qr_code);
+    assert_eq!(recall_status, RecallStatus::NotRecalled);
     assert_eq!(
         traced_product.product_id, product_id,
         "Traced product ID should match"
@@ -2081,6 +2109,1152 @@ fn test_get_current_stage_no_stages() {
     );
 }
 
+// =====================================================================================
+// STAGE VALIDATION RULE TESTS
+// =====================================================================================
+
+/// Build stage validation rules for a test
+fn create_stage_rules(
+    env: &Env,
+    required_fields: &[&str],
+    allowed_transitions: &[u32],
+    minimum_duration: u64,
+) -> StageValidation {
+    let mut fields = Vec::new(env);
+    for field in required_fields {
+        fields.push_back(String::from_str(env, field));
+    }
+    let mut transitions = Vec::new(env);
+    for tier in allowed_transitions {
+        transitions.push_back(*tier);
+    }
+    StageValidation {
+        required_fields: fields,
+        allowed_transitions: transitions,
+        minimum_duration,
+    }
+}
+
+/// Record a stage with the default test name, location and data hash
+fn try_record_stage(
+    env: &Env,
+    client: &SupplyChainTrackingContractClient,
+    product_id: &BytesN<32>,
+    handler: &Address,
+    tier: StageTier,
+) -> Result<u32, SupplyChainError> {
+    let data_hash = BytesN::from_array(env, &[tier.value() as u8; 32]);
+    client
+        .try_add_stage(
+            product_id,
+            &tier,
+            &String::from_str(env, "Stage"),
+            &String::from_str(env, "Location"),
+            handler,
+            &data_hash,
+        )
+        .map(|stage_id| stage_id.unwrap())
+        .map_err(|error| error.unwrap())
+}
+
+#[test]
+fn test_stage_validation_allows_configured_skip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let product_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "SKIP", 4);
+
+    // Without a rule, Processing must be followed by Packaging
+    let result = try_record_stage(
+        &env,
+        &supply_chain_client,
+        &product_id,
+        &handler,
+        StageTier::Transportation,
+    );
+    assert_eq!(result, Err(SupplyChainError::InvalidTierProgression));
+
+    supply_chain_client.set_stage_validation(
+        &admin,
+        &StageTier::Processing,
+        &create_stage_rules(&env, &[], &[5, 7], 0),
+    );
+    assert_eq!(
+        supply_chain_client.get_next_expected_tiers(&product_id),
+        soroban_sdk::vec![&env, StageTier::Packaging, StageTier::Transportation],
+        "Next tiers should follow the configured transitions"
+    );
+
+    let result = try_record_stage(
+        &env,
+        &supply_chain_client,
+        &product_id,
+        &handler,
+        StageTier::Transportation,
+    );
+    assert_eq!(result, Ok(5), "Configured skip should be accepted");
+    assert!(supply_chain_client.verify_hash_chain(&product_id));
+}
+
+#[test]
+fn test_stage_validation_minimum_duration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    supply_chain_client.set_stage_validation(
+        &admin,
+        &StageTier::Harvesting,
+        &create_stage_rules(&env, &[], &[], 3600),
+    );
+    let product_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "DWELL", 3);
+
+    let result = try_record_stage(
+        &env,
+        &supply_chain_client,
+        &product_id,
+        &handler,
+        StageTier::Processing,
+    );
+    assert_eq!(result, Err(SupplyChainError::MinimumDurationNotMet));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+    let result = try_record_stage(
+        &env,
+        &supply_chain_client,
+        &product_id,
+        &handler,
+        StageTier::Processing,
+    );
+    assert_eq!(
+        result,
+        Ok(4),
+        "Stage should be accepted after the dwell time"
+    );
+}
+
+#[test]
+fn test_stage_validation_required_fields() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    supply_chain_client.set_stage_validation(
+        &admin,
+        &StageTier::Storage,
+        &create_stage_rules(&env, &["temperature", "facility"], &[], 0),
+    );
+    let product_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "FIELDS", 5);
+
+    let result = try_record_stage(
+        &env,
+        &supply_chain_client,
+        &product_id,
+        &handler,
+        StageTier::Storage,
+    );
+    assert_eq!(result, Err(SupplyChainError::MissingRequiredField));
+
+    let mut metadata = Map::new(&env);
+    metadata.set(
+        String::from_str(&env, "temperature"),
+        String::from_str(&env, "4C"),
+    );
+    let mut input = StageInput {
+        tier: StageTier::Storage,
+        name: String::from_str(&env, "Storage"),
+        location: String::from_str(&env, "Warehouse"),
+        data_hash: BytesN::from_array(&env, &[6u8; 32]),
+        metadata: metadata.clone(),
+    };
+    let result = supply_chain_client.try_add_stage_with_metadata(&product_id, &handler, &input);
+    assert_eq!(result, Err(Ok(SupplyChainError::MissingRequiredField)));
+
+    metadata.set(
+        String::from_str(&env, "facility"),
+        String::from_str(&env, "Cold Store 2"),
+    );
+    input.metadata = metadata.clone();
+    let stage_id = supply_chain_client.add_stage_with_metadata(&product_id, &handler, &input);
+
+    let stage = supply_chain_client.get_stage_by_id(&product_id, &stage_id);
+    assert_eq!(stage.metadata, metadata, "Stage should keep its metadata");
+    assert!(supply_chain_client.verify_hash_chain(&product_id));
+}
+
+#[test]
+fn test_set_stage_validation_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, _, _, supply_chain_client, _) = setup_test_environment(&env);
+    let rules = create_stage_rules(&env, &["inspector"], &[6, 7], 60);
+
+    let result =
+        supply_chain_client.try_set_stage_validation(&farmer, &StageTier::Packaging, &rules);
+    assert_eq!(result, Err(Ok(SupplyChainError::UnauthorizedAccess)));
+
+    let backwards = create_stage_rules(&env, &[], &[3], 0);
+    let result =
+        supply_chain_client.try_set_stage_validation(&admin, &StageTier::Packaging, &backwards);
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidStageTransition)));
+
+    let unknown = create_stage_rules(&env, &[], &[11], 0);
+    let result =
+        supply_chain_client.try_set_stage_validation(&admin, &StageTier::Packaging, &unknown);
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidStageTier)));
+
+    supply_chain_client.set_stage_validation(&admin, &StageTier::Packaging, &rules);
+    assert_eq!(
+        supply_chain_client.get_stage_validation(&StageTier::Packaging),
+        rules
+    );
+
+    supply_chain_client.remove_stage_validation(&admin, &StageTier::Packaging);
+    let result = supply_chain_client.try_get_stage_validation(&StageTier::Packaging);
+    assert_eq!(result, Err(Ok(SupplyChainError::ValidationRuleNotFound)));
+}
+
+// =====================================================================================
+// STAGE HASH CHAIN TESTS
+// =====================================================================================
+
+#[test]
+fn test_stage_hash_chain_links_stages() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let product_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "CHAIN", 3);
+
+    let stages = supply_chain_client.get_stage_history(&product_id);
+    for i in 1..stages.len() {
+        assert_eq!(
+            stages.get(i).unwrap().prev_hash,
+            stages.get(i - 1).unwrap().stage_hash,
+            "Each stage should commit to the previous one"
+        );
+    }
+    assert_eq!(
+        supply_chain_client.get_chain_root(&product_id),
+        stages.get(2).unwrap().stage_hash,
+        "Root should be the latest cumulative hash"
+    );
+    assert!(supply_chain_client.verify_hash_chain(&product_id));
+
+    // Rewriting an earlier stage breaks the chain
+    env.as_contract(&supply_chain_client.address, || {
+        let mut product: Product = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Product(product_id.clone()))
+            .unwrap();
+        let mut stage = product.stages.get(0).unwrap();
+        stage.location = String::from_str(&env, "Forged");
+        product.stages.set(0, stage);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Product(product_id.clone()), &product);
+    });
+    assert!(!supply_chain_client.verify_hash_chain(&product_id));
+}
+
+#[test]
+fn test_get_stage_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let product_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "PROOF", 3);
+
+    let proof = supply_chain_client.get_stage_proof(&product_id, &1);
+    assert_eq!(proof.stage.stage_id, 1);
+    assert_eq!(proof.later_content_hashes.len(), 2);
+    assert_eq!(proof.root, supply_chain_client.get_chain_root(&product_id));
+    assert!(supply_chain_client.verify_stage_proof(&proof));
+
+    let mut forged = proof.clone();
+    forged.stage.data_hash = BytesN::from_array(&env, &[42u8; 32]);
+    assert!(!supply_chain_client.verify_stage_proof(&forged));
+
+    let mut wrong_root = proof.clone();
+    wrong_root.root = BytesN::from_array(&env, &[42u8; 32]);
+    assert!(!supply_chain_client.verify_stage_proof(&wrong_root));
+
+    let latest = supply_chain_client.get_stage_proof(&product_id, &3);
+    assert!(latest.later_content_hashes.is_empty());
+    assert!(supply_chain_client.verify_stage_proof(&latest));
+
+    let result = supply_chain_client.try_get_stage_proof(&product_id, &9);
+    assert_eq!(result, Err(Ok(SupplyChainError::StageNotFound)));
+}
+
+#[test]
+fn test_split_child_chain_commits_to_parent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let parent_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 3);
+    let parent_root = supply_chain_client.get_chain_root(&parent_id);
+
+    let children = supply_chain_client.split_product(
+        &parent_id,
+        &handler,
+        &soroban_sdk::vec![&env, String::from_str(&env, "LOT-1-A")],
+    );
+    let child_id = children.get(0).unwrap();
+
+    // The inherited history is re-linked from a genesis over the parent's root
+    let expected_genesis = env.as_contract(&supply_chain_client.address, || {
+        let mut data = Bytes::new(&env);
+        data.append(&Bytes::from_array(&env, &child_id.to_array()));
+        data.append(&farmer.clone().to_xdr(&env));
+        data.append(&Bytes::from_array(&env, &parent_id.to_array()));
+        data.append(&Bytes::from_array(&env, &parent_root.to_array()));
+        BytesN::<32>::from(env.crypto().sha256(&data))
+    });
+    let first = supply_chain_client.get_stage_by_id(&child_id, &1);
+    assert_eq!(first.prev_hash, expected_genesis);
+
+    let child_root = supply_chain_client.get_chain_root(&child_id);
+    supply_chain_client.add_stage(
+        &child_id,
+        &StageTier::Processing,
+        &String::from_str(&env, "Processing"),
+        &String::from_str(&env, "Plant"),
+        &handler,
+        &BytesN::from_array(&env, &[4u8; 32]),
+    );
+    let stage = supply_chain_client.get_stage_by_id(&child_id, &4);
+    assert_eq!(
+        stage.prev_hash, child_root,
+        "Child stages should extend its own chain"
+    );
+    assert!(supply_chain_client.verify_hash_chain(&child_id));
+
+    let proof = supply_chain_client.get_stage_proof(&child_id, &2);
+    assert!(supply_chain_client.verify_stage_proof(&proof));
+}
+
+#[test]
+fn test_merge_chain_commits_to_every_parent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let lot_a = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-A", 3);
+    let lot_b = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-B", 3);
+    let blend_id = supply_chain_client.merge_products(
+        &soroban_sdk::vec![&env, lot_a.clone(), lot_b.clone()],
+        &handler,
+        &String::from_str(&env, "BLEND-1"),
+    );
+    assert!(supply_chain_client.verify_hash_chain(&blend_id));
+
+    // Rewrite the secondary lot's history with a self-consistent chain
+    env.as_contract(&supply_chain_client.address, || {
+        let mut product: Product = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Product(lot_b.clone()))
+            .unwrap();
+        let mut stage = product.stages.get(0).unwrap();
+        stage.location = String::from_str(&env, "Forged");
+        product.stages.set(0, stage);
+        let genesis = utils::chain_genesis(&env, &product);
+        product.stages = utils::rechain_stages(&env, &product.stages, genesis);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Product(lot_b.clone()), &product);
+    });
+
+    assert!(supply_chain_client.verify_hash_chain(&lot_b));
+    assert!(
+        !supply_chain_client.verify_hash_chain(&blend_id),
+        "The blend should commit to the secondary lot's chain"
+    );
+}
+
+// =====================================================================================
+// ACTOR REGISTRY TESTS
+// =====================================================================================
+
+#[test]
+fn test_add_stage_requires_accredited_handler() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, _, _, supply_chain_client, _) = setup_test_environment(&env);
+    let (product_type, batch_number, origin_location, metadata_hash) =
+        create_test_product_data(&env, "Actor");
+    let product_id = supply_chain_client.register_product(
+        &farmer,
+        &product_type,
+        &batch_number,
+        &origin_location,
+        &metadata_hash,
+    );
+
+    let stage_name = String::from_str(&env, "Planting");
+    let location = String::from_str(&env, "Field 1");
+    let data_hash = BytesN::from_array(&env, &[3u8; 32]);
+
+    // An unknown signer cannot inject stages
+    let impostor = Address::generate(&env);
+    let result = supply_chain_client.try_add_stage(
+        &product_id,
+        &StageTier::Planting,
+        &stage_name,
+        &location,
+        &impostor,
+        &data_hash,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ActorNotAccredited)));
+
+    // The farmer records growing stages on their own product
+    let stage_id = supply_chain_client.add_stage(
+        &product_id,
+        &StageTier::Planting,
+        &stage_name,
+        &location,
+        &farmer,
+        &data_hash,
+    );
+    let stage = supply_chain_client.get_stage_by_id(&product_id, &stage_id);
+    assert_eq!(stage.handler, farmer, "Stage should record its actor");
+}
+
+#[test]
+fn test_add_stage_role_must_cover_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, _, _, supply_chain_client, _) = setup_test_environment(&env);
+    let transporter = Address::generate(&env);
+    supply_chain_client.approve_actor(
+        &admin,
+        &transporter,
+        &soroban_sdk::vec![&env, ActorRole::Transporter],
+    );
+
+    let (product_type, batch_number, origin_location, metadata_hash) =
+        create_test_product_data(&env, "Role");
+    let product_id = supply_chain_client.register_product(
+        &farmer,
+        &product_type,
+        &batch_number,
+        &origin_location,
+        &metadata_hash,
+    );
+
+    let result = supply_chain_client.try_add_stage(
+        &product_id,
+        &StageTier::Planting,
+        &String::from_str(&env, "Planting"),
+        &String::from_str(&env, "Field 1"),
+        &transporter,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::TierNotPermitted)));
+
+    let result = supply_chain_client.try_approve_actor(&admin, &transporter, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+}
+
+#[test]
+fn test_farmer_scoped_accreditation_and_revocation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let other_farmer = Address::generate(&env);
+    let processor = Address::generate(&env);
+    let own_lot = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 3);
+    let other_lot = setup_batch(
+        &env,
+        &supply_chain_client,
+        &other_farmer,
+        &handler,
+        "LOT-2",
+        3,
+    );
+
+    supply_chain_client.approve_actor(
+        &farmer,
+        &processor,
+        &soroban_sdk::vec![&env, ActorRole::Processor],
+    );
+    let accreditation = supply_chain_client.get_accreditation(&farmer, &processor);
+    assert_eq!(accreditation.approved_by, farmer);
+    assert!(!accreditation.revoked);
+
+    let stage_name = String::from_str(&env, "Processing");
+    let location = String::from_str(&env, "Plant");
+    let data_hash = BytesN::from_array(&env, &[4u8; 32]);
+
+    // The approval covers only the approving farmer's products
+    supply_chain_client.add_stage(
+        &own_lot,
+        &StageTier::Processing,
+        &stage_name,
+        &location,
+        &processor,
+        &data_hash,
+    );
+    let result = supply_chain_client.try_add_stage(
+        &other_lot,
+        &StageTier::Processing,
+        &stage_name,
+        &location,
+        &processor,
+        &data_hash,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ActorNotAccredited)));
+
+    supply_chain_client.revoke_actor(&farmer, &processor);
+    assert!(
+        supply_chain_client
+            .get_accreditation(&farmer, &processor)
+            .revoked
+    );
+
+    let result = supply_chain_client.try_add_stage(
+        &own_lot,
+        &StageTier::Packaging,
+        &String::from_str(&env, "Packaging"),
+        &location,
+        &processor,
+        &data_hash,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ActorNotAccredited)));
+
+    let result = supply_chain_client.try_revoke_actor(&other_farmer, &processor);
+    assert_eq!(result, Err(Ok(SupplyChainError::ActorNotAccredited)));
+}
+
+// =====================================================================================
+// COLD CHAIN TELEMETRY TESTS
+// =====================================================================================
+
+/// Register a sensor and a 0-8C / 85-95% range for the batch product type
+fn setup_cold_chain(
+    env: &Env,
+    client: &SupplyChainTrackingContractClient,
+    admin: &Address,
+) -> Address {
+    let sensor = Address::generate(env);
+    client.register_sensor(admin, &sensor);
+    client.set_condition_range(
+        admin,
+        &String::from_str(env, "Organic_Tomatoes"),
+        &ConditionRange {
+            min_temperature: 0,
+            max_temperature: 80,
+            min_humidity: 850,
+            max_humidity: 950,
+        },
+    );
+    sensor
+}
+
+#[test]
+fn test_cold_chain_readings_and_excursions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let sensor = setup_cold_chain(&env, &supply_chain_client, &admin);
+    let product_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "COLD", 6);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 600;
+    });
+    let now = env.ledger().timestamp();
+
+    assert!(supply_chain_client.submit_reading(&sensor, &product_id, &40, &900, &(now - 300)));
+    assert!(!supply_chain_client.submit_reading(&sensor, &product_id, &120, &900, &now));
+
+    let status = supply_chain_client.get_cold_chain_status(&product_id);
+    assert_eq!(status.reading_count, 2);
+    assert_eq!(status.excursion_count, 1);
+    assert_eq!(status.last_excursion_at, now);
+
+    let readings = supply_chain_client.get_readings(&product_id, &0, &10);
+    assert_eq!(readings.len(), 2);
+    assert_eq!(readings.get(0).unwrap().sensor, sensor);
+    assert_eq!(readings.get(1).unwrap().stage_id, 6);
+
+    let excursions = supply_chain_client.get_excursions(&product_id, &0, &10);
+    assert_eq!(excursions.len(), 1);
+    let excursion = excursions.get(0).unwrap();
+    assert_eq!(excursion.reading_index, 1);
+    assert_eq!(excursion.tier, StageTier::Storage);
+    assert!(excursion.temperature_breach);
+    assert!(!excursion.humidity_breach);
+
+    // Authenticity reports the breach, including on batches split from the product
+    let report = supply_chain_client.verify_authenticity(
+        &farmer,
+        &product_id,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    assert_eq!(report.cold_chain_breaches, 1);
+    assert_eq!(report.last_breach_at, now);
+
+    let children = supply_chain_client.split_product(
+        &product_id,
+        &handler,
+        &soroban_sdk::vec![&env, String::from_str(&env, "COLD-A")],
+    );
+    let report = supply_chain_client.verify_authenticity(
+        &farmer,
+        &children.get(0).unwrap(),
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    assert_eq!(
+        report.cold_chain_breaches, 1,
+        "Children inherit parent breaches"
+    );
+}
+
+#[test]
+fn test_cold_chain_reading_rejections() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let harvested = setup_batch(&env, &supply_chain_client, &farmer, &handler, "WARM", 3);
+    let stored = setup_batch(&env, &supply_chain_client, &farmer, &handler, "COLD", 6);
+    let now = env.ledger().timestamp();
+
+    let sensor = Address::generate(&env);
+    let result = supply_chain_client.try_submit_reading(&sensor, &stored, &40, &900, &now);
+    assert_eq!(result, Err(Ok(SupplyChainError::SensorNotRegistered)));
+
+    supply_chain_client.register_sensor(&admin, &sensor);
+    let result = supply_chain_client.try_submit_reading(&sensor, &stored, &40, &900, &now);
+    assert_eq!(result, Err(Ok(SupplyChainError::ConditionRangeNotFound)));
+
+    let sensor = setup_cold_chain(&env, &supply_chain_client, &admin);
+    let result = supply_chain_client.try_submit_reading(&sensor, &harvested, &40, &900, &now);
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductNotMonitored)));
+
+    let result = supply_chain_client.try_submit_reading(&sensor, &stored, &40, &900, &(now + 1));
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+
+    supply_chain_client.remove_sensor(&admin, &sensor);
+    assert!(!supply_chain_client.is_sensor_registered(&sensor));
+    let result = supply_chain_client.try_submit_reading(&sensor, &stored, &40, &900, &now);
+    assert_eq!(result, Err(Ok(SupplyChainError::SensorNotRegistered)));
+
+    let inverted = ConditionRange {
+        min_temperature: 80,
+        max_temperature: 0,
+        min_humidity: 850,
+        max_humidity: 950,
+    };
+    let product_type = String::from_str(&env, "Organic_Tomatoes");
+    let result = supply_chain_client.try_set_condition_range(&admin, &product_type, &inverted);
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+    let result = supply_chain_client.try_set_condition_range(
+        &farmer,
+        &product_type,
+        &supply_chain_client.get_condition_range(&product_type),
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::UnauthorizedAccess)));
+}
+
+// =====================================================================================
+// RECALL TESTS
+// =====================================================================================
+
+#[test]
+fn test_recall_propagates_to_descendants() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let other_farmer = Address::generate(&env);
+    let lot = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 3);
+    let clean_lot = setup_batch(
+        &env,
+        &supply_chain_client,
+        &other_farmer,
+        &handler,
+        "LOT-2",
+        3,
+    );
+
+    let packages = supply_chain_client.split_product(
+        &lot,
+        &handler,
+        &soroban_sdk::vec![
+            &env,
+            String::from_str(&env, "LOT-1-A"),
+            String::from_str(&env, "LOT-1-B"),
+        ],
+    );
+    let blend = supply_chain_client.merge_products(
+        &soroban_sdk::vec![&env, packages.get(0).unwrap(), clean_lot.clone()],
+        &handler,
+        &String::from_str(&env, "BLEND-1"),
+    );
+
+    let reason = String::from_str(&env, "Salmonella contamination");
+    let recall_id =
+        supply_chain_client.issue_recall(&admin, &RecallScope::Product(lot.clone()), &reason);
+    assert_eq!(recall_id, 1);
+
+    let recall = supply_chain_client.get_recall(&recall_id);
+    assert_eq!(recall.affected_count, 4, "Lot, both packages and the blend");
+    assert_eq!(recall.issued_by, admin);
+
+    let first_page = supply_chain_client.list_recalled_products(&recall_id, &0, &2);
+    let second_page = supply_chain_client.list_recalled_products(&recall_id, &2, &10);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 2);
+    assert!(second_page.contains(&blend));
+    assert!(!first_page.contains(&clean_lot) && !second_page.contains(&clean_lot));
+
+    assert_eq!(
+        supply_chain_client.get_recall_status(&clean_lot),
+        RecallStatus::NotRecalled
+    );
+
+    // Consumers scanning the blend see the recall
+    let qr_code = supply_chain_client.generate_qr_code(&blend);
+    let (_, _, status) = supply_chain_client.trace_by_qr_code(&qr_code);
+    match status {
+        RecallStatus::Recalled(recall) => assert_eq!(recall.reason, reason),
+        RecallStatus::NotRecalled => panic!("Blend should be recalled"),
+    }
+}
+
+#[test]
+fn test_recalled_lot_cannot_be_split_merged_or_staged() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let lot = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 2);
+    let clean_lot = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-2", 2);
+
+    supply_chain_client.issue_recall(
+        &admin,
+        &RecallScope::Product(lot.clone()),
+        &String::from_str(&env, "Listeria"),
+    );
+
+    let result = supply_chain_client.try_split_product(
+        &lot,
+        &handler,
+        &soroban_sdk::vec![&env, String::from_str(&env, "LOT-1-A")],
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductRecalled)));
+
+    let result = supply_chain_client.try_merge_products(
+        &soroban_sdk::vec![&env, clean_lot.clone(), lot.clone()],
+        &handler,
+        &String::from_str(&env, "BLEND-1"),
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductRecalled)));
+
+    let result = supply_chain_client.try_add_stage(
+        &lot,
+        &StageTier::Harvesting,
+        &String::from_str(&env, "Stage"),
+        &String::from_str(&env, "Location"),
+        &handler,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductRecalled)));
+}
+
+#[test]
+fn test_recall_by_batch_and_farmer_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let other_farmer = Address::generate(&env);
+    let reason = String::from_str(&env, "Pesticide residue");
+
+    let shared_a = setup_batch(&env, &supply_chain_client, &farmer, &handler, "SHARED", 1);
+    let shared_b = setup_batch(
+        &env,
+        &supply_chain_client,
+        &other_farmer,
+        &handler,
+        "SHARED",
+        1,
+    );
+
+    let recall_id = supply_chain_client.issue_recall(
+        &admin,
+        &RecallScope::Batch(String::from_str(&env, "SHARED")),
+        &reason,
+    );
+    let affected = supply_chain_client.list_recalled_products(&recall_id, &0, &10);
+    assert_eq!(affected, soroban_sdk::vec![&env, shared_a, shared_b]);
+
+    let start = env.ledger().timestamp();
+    env.ledger().with_mut(|li| {
+        li.timestamp += 1000;
+    });
+    let late = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LATE", 1);
+
+    let recall_id = supply_chain_client.issue_recall(
+        &admin,
+        &RecallScope::FarmerRange(farmer.clone(), start + 500, start + 2000),
+        &reason,
+    );
+    let affected = supply_chain_client.list_recalled_products(&recall_id, &0, &10);
+    assert_eq!(affected, soroban_sdk::vec![&env, late]);
+
+    let result = supply_chain_client.try_issue_recall(
+        &admin,
+        &RecallScope::FarmerRange(farmer.clone(), start + 2000, start),
+        &reason,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+
+    let result = supply_chain_client.try_issue_recall(
+        &admin,
+        &RecallScope::Batch(String::from_str(&env, "UNKNOWN")),
+        &reason,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductNotFound)));
+}
+
+#[test]
+fn test_recall_authorities() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, farmer, handler, authority, supply_chain_client, _) = setup_test_environment(&env);
+    let lot = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 1);
+    let scope = RecallScope::Product(lot.clone());
+    let reason = String::from_str(&env, "Failed inspection");
+
+    let result = supply_chain_client.try_issue_recall(&authority, &scope, &reason);
+    assert_eq!(result, Err(Ok(SupplyChainError::UnauthorizedAccess)));
+
+    let result = supply_chain_client.try_add_recall_authority(&farmer, &authority);
+    assert_eq!(result, Err(Ok(SupplyChainError::UnauthorizedAccess)));
+
+    supply_chain_client.add_recall_authority(&admin, &authority);
+    let result =
+        supply_chain_client.try_issue_recall(&authority, &scope, &String::from_str(&env, ""));
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+
+    let recall_id = supply_chain_client.issue_recall(&authority, &scope, &reason);
+    assert_eq!(
+        supply_chain_client.get_recall(&recall_id).issued_by,
+        authority
+    );
+
+    supply_chain_client.remove_recall_authority(&admin, &authority);
+    let result = supply_chain_client.try_issue_recall(&authority, &scope, &reason);
+    assert_eq!(result, Err(Ok(SupplyChainError::UnauthorizedAccess)));
+
+    let result = supply_chain_client.try_get_recall(&99);
+    assert_eq!(result, Err(Ok(SupplyChainError::RecallNotFound)));
+}
+
+// =====================================================================================
+// BATCH SPLIT AND MERGE TESTS
+// =====================================================================================
+
+/// Register a product of the given type and record its first `tiers` stages
+fn setup_batch(
+    env: &Env,
+    client: &SupplyChainTrackingContractClient,
+    farmer: &Address,
+    handler: &Address,
+    batch_number: &str,
+    tiers: u32,
+) -> BytesN<32> {
+    let product_id = client.register_product(
+        farmer,
+        &String::from_str(env, "Organic_Tomatoes"),
+        &String::from_str(env, batch_number),
+        &String::from_str(env, "Farm_Location"),
+        &BytesN::from_array(env, &[1u8; 32]),
+    );
+    for tier in 1..=tiers {
+        client.add_stage(
+            &product_id,
+            &StageTier::from_value(tier).unwrap(),
+            &String::from_str(env, "Stage"),
+            &String::from_str(env, "Location"),
+            handler,
+            &BytesN::from_array(env, &[tier as u8; 32]),
+        );
+    }
+    product_id
+}
+
+#[test]
+fn test_split_product_inherits_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let parent_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 3);
+
+    let batch_numbers = soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "LOT-1-A"),
+        String::from_str(&env, "LOT-1-B"),
+    ];
+    let children = supply_chain_client.split_product(&parent_id, &handler, &batch_numbers);
+    assert_eq!(
+        children.len(),
+        2,
+        "Should create one child per batch number"
+    );
+    assert_eq!(
+        supply_chain_client.get_product_children(&parent_id),
+        children,
+        "Parent should link to its children"
+    );
+
+    let parent = supply_chain_client.get_product_details(&parent_id);
+    let child_id = children.get(0).unwrap();
+    let child = supply_chain_client.get_product_details(&child_id);
+    assert_eq!(
+        child.farmer_id, farmer,
+        "Child should keep the parent farmer"
+    );
+    assert_eq!(child.stages.len(), parent.stages.len());
+    for (inherited, original) in child.stages.iter().zip(parent.stages.iter()) {
+        assert_eq!(
+            (inherited.stage_id, inherited.data_hash, inherited.handler),
+            (original.stage_id, original.data_hash, original.handler),
+            "Child should inherit stage history"
+        );
+    }
+
+    let parents = supply_chain_client.get_product_parents(&child_id);
+    assert_eq!(parents.len(), 1, "Child should have one parent");
+    assert_eq!(parents.get(0).unwrap().parent_id, parent_id);
+    assert_eq!(
+        parents.get(0).unwrap().parent_hash,
+        supply_chain_client.get_chain_root(&parent_id),
+        "The link should record the parent's chain root"
+    );
+
+    let registration = supply_chain_client.get_product_registration(&child_id);
+    assert_eq!(registration.batch_number, String::from_str(&env, "LOT-1-A"));
+
+    // The child's hash chain commits to the parent's, so it differs from it
+    let (child_hash, parent_hash) = env.as_contract(&supply_chain_client.address, || {
+        (
+            utils::calculate_supply_chain_hash(&env, &child_id).unwrap(),
+            utils::calculate_supply_chain_hash(&env, &parent_id).unwrap(),
+        )
+    });
+    assert_ne!(
+        child_hash, parent_hash,
+        "Child hash should extend the parent chain"
+    );
+    assert!(
+        supply_chain_client
+            .verify_authenticity(&farmer, &child_id, &child_hash)
+            .authentic
+    );
+
+    // Children continue the progression; the split parent is closed
+    let stage_id = supply_chain_client.add_stage(
+        &child_id,
+        &StageTier::Processing,
+        &String::from_str(&env, "Processing"),
+        &String::from_str(&env, "Plant"),
+        &handler,
+        &BytesN::from_array(&env, &[4u8; 32]),
+    );
+    assert_eq!(
+        stage_id, 4,
+        "Child stage IDs should continue the inherited history"
+    );
+
+    let result = supply_chain_client.try_add_stage(
+        &parent_id,
+        &StageTier::Processing,
+        &String::from_str(&env, "Processing"),
+        &String::from_str(&env, "Plant"),
+        &handler,
+        &BytesN::from_array(&env, &[4u8; 32]),
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductConsumed)));
+
+    let result = supply_chain_client.try_split_product(&parent_id, &handler, &batch_numbers);
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductConsumed)));
+}
+
+#[test]
+fn test_split_product_invalid_input() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let parent_id = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 1);
+
+    let result = supply_chain_client.try_split_product(&parent_id, &handler, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+
+    let duplicate = soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "LOT-1-A"),
+        String::from_str(&env, "LOT-1-A"),
+    ];
+    let result = supply_chain_client.try_split_product(&parent_id, &handler, &duplicate);
+    assert_eq!(result, Err(Ok(SupplyChainError::DuplicateProduct)));
+
+    let missing = BytesN::from_array(&env, &[99u8; 32]);
+    let batch_numbers = soroban_sdk::vec![&env, String::from_str(&env, "LOT-X")];
+    let result = supply_chain_client.try_split_product(&missing, &handler, &batch_numbers);
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductNotFound)));
+}
+
+#[test]
+fn test_merge_products_traces_every_origin_farm() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let other_farmer = Address::generate(&env);
+    let lot_a = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-A", 3);
+    let lot_b = setup_batch(
+        &env,
+        &supply_chain_client,
+        &other_farmer,
+        &handler,
+        "LOT-B",
+        3,
+    );
+
+    let blend_id = supply_chain_client.merge_products(
+        &soroban_sdk::vec![&env, lot_a.clone(), lot_b.clone()],
+        &handler,
+        &String::from_str(&env, "BLEND-1"),
+    );
+
+    let blend = supply_chain_client.get_product_details(&blend_id);
+    assert_eq!(
+        blend.farmer_id, farmer,
+        "Blend should continue the primary lot"
+    );
+    assert_eq!(
+        blend.stages.len(),
+        3,
+        "Blend should inherit the primary history"
+    );
+    assert_eq!(blend.certificate_id, CertificateId::None);
+    assert_eq!(supply_chain_client.get_product_parents(&blend_id).len(), 2);
+
+    let trace = supply_chain_client.get_batch_trace(&blend_id);
+    assert_eq!(
+        trace.len(),
+        3,
+        "Trace should include the blend and both lots"
+    );
+    assert_eq!(trace.get(0).unwrap().product_id, blend_id);
+    assert_eq!(trace.get(0).unwrap().parents.len(), 2);
+
+    // The public trace includes the secondary lot's history too
+    let (_, stages) = supply_chain_client.get_product_trace(&blend_id);
+    assert_eq!(stages.len(), 6, "Trace should hold the stages of both lots");
+    let lot_b_stages = supply_chain_client.get_product_details(&lot_b).stages;
+    for stage in lot_b_stages.iter() {
+        assert!(stages.contains(&stage));
+    }
+    let qr_code = supply_chain_client.generate_qr_code(&blend_id);
+    let (_, qr_stages, _) = supply_chain_client.trace_by_qr_code(&qr_code);
+    assert_eq!(qr_stages, stages);
+
+    let farms = supply_chain_client.get_origin_farms(&blend_id);
+    assert_eq!(farms.len(), 2, "Both origin farms should be reported");
+    assert!(farms.contains(&farmer));
+    assert!(farms.contains(&other_farmer));
+
+    // Merged lots cannot be blended again
+    let result = supply_chain_client.try_merge_products(
+        &soroban_sdk::vec![&env, lot_a, lot_b],
+        &handler,
+        &String::from_str(&env, "BLEND-2"),
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::ProductConsumed)));
+}
+
+#[test]
+fn test_merge_products_incompatible_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let lot_a = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-A", 3);
+    let lot_b = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-B", 2);
+    let blend = String::from_str(&env, "BLEND-1");
+
+    let result = supply_chain_client.try_merge_products(
+        &soroban_sdk::vec![&env, lot_a.clone(), lot_b],
+        &handler,
+        &blend,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::IncompatibleBatches)));
+
+    let result = supply_chain_client.try_merge_products(
+        &soroban_sdk::vec![&env, lot_a.clone()],
+        &handler,
+        &blend,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+
+    let result = supply_chain_client.try_merge_products(
+        &soroban_sdk::vec![&env, lot_a.clone(), lot_a],
+        &handler,
+        &blend,
+    );
+    assert_eq!(result, Err(Ok(SupplyChainError::InvalidInput)));
+}
+
+#[test]
+fn test_split_then_merge_trace_deduplicates_ancestors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, farmer, handler, _, supply_chain_client, _) = setup_test_environment(&env);
+    let lot = setup_batch(&env, &supply_chain_client, &farmer, &handler, "LOT-1", 4);
+
+    let children = supply_chain_client.split_product(
+        &lot,
+        &handler,
+        &soroban_sdk::vec![
+            &env,
+            String::from_str(&env, "LOT-1-A"),
+            String::from_str(&env, "LOT-1-B"),
+        ],
+    );
+    let blend_id = supply_chain_client.merge_products(
+        &children,
+        &handler,
+        &String::from_str(&env, "LOT-1-REBLEND"),
+    );
+
+    let trace = supply_chain_client.get_batch_trace(&blend_id);
+    assert_eq!(trace.len(), 4, "Shared ancestor should appear only once");
+    assert_eq!(trace.get(3).unwrap().product_id, lot);
+    assert!(trace.get(3).unwrap().parents.is_empty());
+
+    let farms = supply_chain_client.get_origin_farms(&blend_id);
+    assert_eq!(farms, soroban_sdk::vec![&env, farmer]);
+}
+
 // =====================================================================================
 // MOCK CERTIFICATE MANAGEMENT CONTRACT
 // =====================================================================================
@@ -1,20 +1,20 @@
-use crate::datatypes::{DataKey, Product, Stage, StageTier, SupplyChainError};
-use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+use crate::datatypes::{
+    DataKey, Product, Stage, StageInput, StageTier, StageValidation, SupplyChainError,
+};
+use crate::utils;
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
 
 /// Add a new stage to the product lifecycle with tier validation
 pub fn add_stage(
     env: Env,
     product_id: BytesN<32>,
-    stage_tier: StageTier,
-    stage_name: String,
-    location: String,
     handler: Address,
-    data_hash: BytesN<32>,
+    input: StageInput,
 ) -> Result<u32, SupplyChainError> {
     handler.require_auth();
 
     // Validate input data
-    if stage_name.is_empty() || location.is_empty() {
+    if input.name.is_empty() || input.location.is_empty() {
         return Err(SupplyChainError::InvalidInput);
     }
 
@@ -25,21 +25,45 @@ pub fn add_stage(
         .get(&DataKey::Product(product_id.clone()))
         .ok_or(SupplyChainError::ProductNotFound)?;
 
-    // Validate tier progression
-    validate_tier_progression(&product, &stage_tier)?;
+    // A batch that has been split or merged continues only through its children
+    if crate::batch::is_consumed(&env, &product_id) {
+        return Err(SupplyChainError::ProductConsumed);
+    }
+
+    // A recalled product cannot move further along the chain
+    if crate::recall::is_recalled(&env, &product_id) {
+        return Err(SupplyChainError::ProductRecalled);
+    }
+
+    // Only accredited actors may record this tier
+    crate::actors::authorize_stage(&env, &product, &handler, &input.tier)?;
+
+    // Validate tier progression and configured stage rules
+    validate_tier_progression(&env, &product, &input.tier)?;
+    validate_required_fields(&env, &input)?;
 
     // Generate new stage ID
     let stage_id = product.stages.len() + 1;
 
-    // Create new stage
-    let stage = Stage {
+    // Create new stage, chained to the previous one
+    let prev_hash = utils::chain_head(&env, &product);
+    let mut stage = Stage {
         stage_id,
-        tier: stage_tier,
-        name: stage_name.clone(),
+        tier: input.tier,
+        name: input.name,
         timestamp: env.ledger().timestamp(),
-        location: location.clone(),
-        data_hash,
+        location: input.location,
+        data_hash: input.data_hash,
+        handler: handler.clone(),
+        metadata: input.metadata,
+        prev_hash: prev_hash.clone(),
+        stage_hash: prev_hash,
     };
+    stage.stage_hash = utils::link_stage_hash(
+        &env,
+        &stage.prev_hash,
+        &utils::stage_content_hash(&env, &stage),
+    );
 
     // Add stage to product's stages vector
     product.stages.push_back(stage.clone());
@@ -58,7 +82,71 @@ pub fn add_stage(
     Ok(stage_id)
 }
 
-/// Get the full product trace including all stages
+/// Configure the validation rules for stages of a tier (admin only)
+pub fn set_stage_validation(
+    env: Env,
+    admin: Address,
+    tier: StageTier,
+    rules: StageValidation,
+) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    // Transitions may skip tiers but never move backwards
+    for to_tier in rules.allowed_transitions.iter() {
+        if StageTier::from_value(to_tier).is_none() {
+            return Err(SupplyChainError::InvalidStageTier);
+        }
+        if to_tier <= tier.value() {
+            return Err(SupplyChainError::InvalidStageTransition);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::StageValidation(tier.value()), &rules);
+
+    env.events().publish(
+        (Symbol::new(&env, "stage_validation_set"), admin),
+        tier.value(),
+    );
+
+    Ok(())
+}
+
+/// Remove the validation rules for a tier, restoring the default progression (admin only)
+pub fn remove_stage_validation(
+    env: Env,
+    admin: Address,
+    tier: StageTier,
+) -> Result<(), SupplyChainError> {
+    utils::require_admin(&env, &admin)?;
+
+    let key = DataKey::StageValidation(tier.value());
+    if !env.storage().persistent().has(&key) {
+        return Err(SupplyChainError::ValidationRuleNotFound);
+    }
+    env.storage().persistent().remove(&key);
+
+    env.events().publish(
+        (Symbol::new(&env, "stage_validation_removed"), admin),
+        tier.value(),
+    );
+
+    Ok(())
+}
+
+/// Get the validation rules configured for a tier
+pub fn get_stage_validation(
+    env: Env,
+    tier: StageTier,
+) -> Result<StageValidation, SupplyChainError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::StageValidation(tier.value()))
+        .ok_or(SupplyChainError::ValidationRuleNotFound)
+}
+
+/// Get the full product trace, including the stages of every batch it was split or merged from
 pub fn get_product_trace(
     env: Env,
     product_id: BytesN<32>,
@@ -66,14 +154,31 @@ pub fn get_product_trace(
     let product: Product = env
         .storage()
         .persistent()
-        .get(&DataKey::Product(product_id))
+        .get(&DataKey::Product(product_id.clone()))
         .ok_or(SupplyChainError::ProductNotFound)?;
 
-    let stages = product.stages.clone();
+    // Children copy their primary parent's stages, so only take each batch's own stages
+    let mut stages: Vec<Stage> = Vec::new(&env);
+    for node in crate::batch::get_batch_trace(env.clone(), product_id)?.iter() {
+        let inherited = crate::batch::inherited_stage_count(&env, &node.product_id);
+        let batch = crate::product::get_product_details(env.clone(), node.product_id)?;
+        for stage in batch.stages.iter().skip(inherited as usize) {
+            insert_by_timestamp(&mut stages, stage);
+        }
+    }
 
     Ok((product, stages))
 }
 
+/// Helper function to keep a merged history in chronological order
+fn insert_by_timestamp(stages: &mut Vec<Stage>, stage: Stage) {
+    let mut index = stages.len();
+    while index > 0 && stages.get(index - 1).unwrap().timestamp > stage.timestamp {
+        index -= 1;
+    }
+    stages.insert(index, stage);
+}
+
 /// Get the current stage of a product
 pub fn get_current_stage(env: Env, product_id: BytesN<32>) -> Result<Stage, SupplyChainError> {
     let product: Product = env
@@ -146,6 +251,7 @@ pub fn validate_stage_transition(
 
 /// Validate tier progression logic
 fn validate_tier_progression(
+    env: &Env,
     product: &Product,
     new_tier: &StageTier,
 ) -> Result<(), SupplyChainError> {
@@ -167,28 +273,66 @@ fn validate_tier_progression(
     // Get the current (last) stage tier
     let current_stage = product.stages.get(product.stages.len() - 1).unwrap();
     let current_tier = &current_stage.tier;
+    let rules = get_stage_validation(env.clone(), current_tier.clone()).ok();
 
-    // Check if new tier is the next expected tier
-    match current_tier.next() {
-        Some(expected_next_tier) => {
-            if *new_tier != expected_next_tier {
-                return Err(SupplyChainError::InvalidTierProgression);
-            }
+    if !allowed_next_tiers(env, current_tier).contains(new_tier) {
+        return Err(SupplyChainError::InvalidTierProgression);
+    }
+
+    // The product must have dwelled long enough at the current tier
+    if let Some(rules) = rules {
+        let elapsed = env
+            .ledger()
+            .timestamp()
+            .saturating_sub(current_stage.timestamp);
+        if elapsed < rules.minimum_duration {
+            return Err(SupplyChainError::MinimumDurationNotMet);
         }
-        None => {
-            // Current tier is Consumer (final stage), no more stages allowed
-            return Err(SupplyChainError::InvalidTierProgression);
+    }
+
+    Ok(())
+}
+
+/// Check that a stage carries the metadata keys its tier requires
+fn validate_required_fields(env: &Env, input: &StageInput) -> Result<(), SupplyChainError> {
+    if let Ok(rules) = get_stage_validation(env.clone(), input.tier.clone()) {
+        for field in rules.required_fields.iter() {
+            match input.metadata.get(field) {
+                Some(value) if !value.is_empty() => {}
+                _ => return Err(SupplyChainError::MissingRequiredField),
+            }
         }
     }
 
     Ok(())
 }
 
-/// Get the next expected tier for a product
-pub fn get_next_expected_tier(
+/// Tiers that may follow `current_tier`.
+/// Configured transitions replace the default next-tier progression.
+fn allowed_next_tiers(env: &Env, current_tier: &StageTier) -> Vec<StageTier> {
+    let mut tiers = Vec::new(env);
+    match get_stage_validation(env.clone(), current_tier.clone()) {
+        Ok(rules) if !rules.allowed_transitions.is_empty() => {
+            for value in rules.allowed_transitions.iter() {
+                if let Some(tier) = StageTier::from_value(value) {
+                    tiers.push_back(tier);
+                }
+            }
+        }
+        _ => {
+            if let Some(tier) = current_tier.next() {
+                tiers.push_back(tier);
+            }
+        }
+    }
+    tiers
+}
+
+/// Get the tiers a product may move to next under the configured transitions
+pub fn get_next_expected_tiers(
     env: Env,
     product_id: BytesN<32>,
-) -> Result<Option<StageTier>, SupplyChainError> {
+) -> Result<Vec<StageTier>, SupplyChainError> {
     let product: Product = env
         .storage()
         .persistent()
@@ -196,11 +340,11 @@ pub fn get_next_expected_tier(
         .ok_or(SupplyChainError::ProductNotFound)?;
 
     if product.stages.is_empty() {
-        return Ok(Some(StageTier::Planting));
+        return Ok(Vec::from_array(&env, [StageTier::Planting]));
     }
 
     let current_stage = product.stages.get(product.stages.len() - 1).unwrap();
-    Ok(current_stage.tier.next())
+    Ok(allowed_next_tiers(&env, &current_stage.tier))
 }
 
 /// Get the current tier for a product
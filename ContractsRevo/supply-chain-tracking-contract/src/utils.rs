@@ -1,5 +1,24 @@
-use crate::datatypes::{DataKey, Product, SupplyChainError};
-use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, String};
+use crate::datatypes::{
+    BatchLink, DataKey, Product, Stage, StageProof, SupplyChainError, MAX_PAGE_SIZE,
+};
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+
+/// Require that the caller is the contract admin
+pub fn require_admin(env: &Env, admin: &Address) -> Result<(), SupplyChainError> {
+    admin.require_auth();
+
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SupplyChainError::NotInitialized)?;
+
+    if *admin != stored_admin {
+        return Err(SupplyChainError::UnauthorizedAccess);
+    }
+
+    Ok(())
+}
 
 /// Generate unique product ID using hash of farmer, product type, batch, and timestamp
 pub fn generate_product_id(
@@ -56,9 +75,24 @@ pub fn calculate_supply_chain_hash(
         return Err(SupplyChainError::InvalidHash);
     }
 
+    Ok(product_chain_hash(env, &product))
+}
+
+/// Hash a product's stage chain, including the recorded hashes of any parent batches
+pub fn product_chain_hash(env: &Env, product: &Product) -> BytesN<32> {
     let mut combined_data = Bytes::new(env);
     combined_data.append(&Bytes::from_array(env, &product.product_id.to_array()));
-    combined_data.append(&product.farmer_id.to_xdr(env));
+    combined_data.append(&product.farmer_id.clone().to_xdr(env));
+
+    // Split and merged batches commit to the chain hashes of their parents
+    let parents: Vec<BatchLink> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ProductParents(product.product_id.clone()))
+        .unwrap_or_else(|| Vec::new(env));
+    for link in parents.iter() {
+        combined_data.append(&Bytes::from_array(env, &link.parent_hash.to_array()));
+    }
 
     // Add all stage hashes to create a chain
     for stage in product.stages.iter() {
@@ -66,7 +100,7 @@ pub fn calculate_supply_chain_hash(
         combined_data.append(&Bytes::from_array(env, &stage.timestamp.to_be_bytes()));
     }
 
-    Ok(env.crypto().sha256(&combined_data).into())
+    env.crypto().sha256(&combined_data).into()
 }
 
 /// Generate QR code data for consumer access
@@ -102,7 +136,8 @@ pub fn resolve_qr_code(env: &Env, qr_code: &String) -> Result<BytesN<32>, Supply
         .ok_or(SupplyChainError::QRCodeNotFound)
 }
 
-/// Verify the hash chain integrity of a product's supply chain
+/// Verify the hash chain integrity of a product's supply chain, including every
+/// batch it was split or merged from
 pub fn verify_hash_chain(env: &Env, product_id: &BytesN<32>) -> Result<bool, SupplyChainError> {
     let product: Product = env
         .storage()
@@ -114,22 +149,194 @@ pub fn verify_hash_chain(env: &Env, product_id: &BytesN<32>) -> Result<bool, Sup
         return Ok(false);
     }
 
+    for node in crate::batch::get_batch_trace(env.clone(), product_id.clone())?.iter() {
+        let batch: Product = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Product(node.product_id))
+            .ok_or(SupplyChainError::ProductNotFound)?;
+        if !stage_chain_valid(env, &batch) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Helper function to recheck the stages of a single batch against its genesis
+fn stage_chain_valid(env: &Env, product: &Product) -> bool {
     // Verify each stage hash is valid
     for stage in product.stages.iter() {
         // Basic validation: ensure hash is not zero
         if stage.data_hash.to_array().iter().all(|&x| x == 0) {
-            return Ok(false);
+            return false;
         }
     }
 
     // Verify sequential stage IDs
     for (i, stage) in product.stages.iter().enumerate() {
         if stage.stage_id != (i as u32 + 1) {
-            return Ok(false);
+            return false;
         }
     }
 
-    Ok(true)
+    // Verify each parent still ends at the chain head recorded when it was linked
+    let parents: Vec<BatchLink> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ProductParents(product.product_id.clone()))
+        .unwrap_or_else(|| Vec::new(env));
+    for link in parents.iter() {
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Product>(&DataKey::Product(link.parent_id))
+        {
+            Some(parent) if chain_head(env, &parent) == link.parent_hash => {}
+            _ => return false,
+        }
+    }
+
+    // Verify every stage commits to the one before it
+    let mut expected_prev = lineage_genesis(env, &product.product_id, &product.farmer_id, &parents);
+    for stage in product.stages.iter() {
+        if stage.prev_hash != expected_prev
+            || stage.stage_hash
+                != link_stage_hash(env, &stage.prev_hash, &stage_content_hash(env, &stage))
+        {
+            return false;
+        }
+        expected_prev = stage.stage_hash;
+    }
+
+    true
+}
+
+/// Genesis hash a product's stage chain starts from
+pub fn chain_genesis(env: &Env, product: &Product) -> BytesN<32> {
+    let parents: Vec<BatchLink> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ProductParents(product.product_id.clone()))
+        .unwrap_or_else(|| Vec::new(env));
+    lineage_genesis(env, &product.product_id, &product.farmer_id, &parents)
+}
+
+/// Genesis hash of the batch and farmer. Split and merged batches also commit to
+/// the ID and recorded chain head of every parent, in the order they were linked.
+pub fn lineage_genesis(
+    env: &Env,
+    product_id: &BytesN<32>,
+    farmer_id: &Address,
+    parents: &Vec<BatchLink>,
+) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&Bytes::from_array(env, &product_id.to_array()));
+    data.append(&farmer_id.clone().to_xdr(env));
+
+    for link in parents.iter() {
+        data.append(&Bytes::from_array(env, &link.parent_id.to_array()));
+        data.append(&Bytes::from_array(env, &link.parent_hash.to_array()));
+    }
+
+    env.crypto().sha256(&data).into()
+}
+
+/// Re-link inherited stages onto a new genesis hash
+pub fn rechain_stages(env: &Env, stages: &Vec<Stage>, genesis: BytesN<32>) -> Vec<Stage> {
+    let mut prev_hash = genesis;
+    let mut linked = Vec::new(env);
+    for mut stage in stages.iter() {
+        stage.prev_hash = prev_hash;
+        stage.stage_hash = link_stage_hash(env, &stage.prev_hash, &stage_content_hash(env, &stage));
+        prev_hash = stage.stage_hash.clone();
+        linked.push_back(stage);
+    }
+    linked
+}
+
+/// Latest cumulative hash of a product's stage chain (the genesis hash if it has no stages)
+pub fn chain_head(env: &Env, product: &Product) -> BytesN<32> {
+    match product.stages.last() {
+        Some(stage) => stage.stage_hash,
+        None => chain_genesis(env, product),
+    }
+}
+
+/// Hash the recorded content of a stage, excluding its chain links
+pub fn stage_content_hash(env: &Env, stage: &Stage) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&Bytes::from_array(env, &stage.stage_id.to_be_bytes()));
+    data.append(&Bytes::from_array(env, &stage.tier.value().to_be_bytes()));
+    data.append(&stage.name.clone().to_xdr(env));
+    data.append(&Bytes::from_array(env, &stage.timestamp.to_be_bytes()));
+    data.append(&stage.location.clone().to_xdr(env));
+    data.append(&Bytes::from_array(env, &stage.data_hash.to_array()));
+    data.append(&stage.handler.clone().to_xdr(env));
+    data.append(&stage.metadata.clone().to_xdr(env));
+    env.crypto().sha256(&data).into()
+}
+
+/// Combine the previous cumulative hash with a stage's content hash
+pub fn link_stage_hash(env: &Env, prev_hash: &BytesN<32>, content_hash: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&Bytes::from_array(env, &prev_hash.to_array()));
+    data.append(&Bytes::from_array(env, &content_hash.to_array()));
+    env.crypto().sha256(&data).into()
+}
+
+/// Build the proof linking one stage to the product's current chain root
+pub fn get_stage_proof(
+    env: &Env,
+    product_id: &BytesN<32>,
+    stage_id: u32,
+) -> Result<StageProof, SupplyChainError> {
+    let product: Product = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Product(product_id.clone()))
+        .ok_or(SupplyChainError::ProductNotFound)?;
+
+    let mut stage = None;
+    let mut later_content_hashes = Vec::new(env);
+    for candidate in product.stages.iter() {
+        if candidate.stage_id == stage_id {
+            stage = Some(candidate);
+        } else if stage.is_some() {
+            later_content_hashes.push_back(stage_content_hash(env, &candidate));
+        }
+    }
+
+    Ok(StageProof {
+        product_id: product_id.clone(),
+        stage: stage.ok_or(SupplyChainError::StageNotFound)?,
+        later_content_hashes,
+        root: chain_head(env, &product),
+    })
+}
+
+/// Check a stage proof without reading contract storage
+pub fn verify_stage_proof(env: &Env, proof: &StageProof) -> bool {
+    let mut hash = link_stage_hash(
+        env,
+        &proof.stage.prev_hash,
+        &stage_content_hash(env, &proof.stage),
+    );
+    if hash != proof.stage.stage_hash {
+        return false;
+    }
+
+    for content_hash in proof.later_content_hashes.iter() {
+        hash = link_stage_hash(env, &hash, &content_hash);
+    }
+
+    hash == proof.root
+}
+
+/// Clamp a page request of `limit` entries from `start` to the `count` stored entries
+pub fn page_range(start: u32, limit: u32, count: u32) -> core::ops::Range<u32> {
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+    start.min(end)..end
 }
 
 /// Simple hex encoding helper
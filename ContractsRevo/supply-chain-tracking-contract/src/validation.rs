@@ -1,8 +1,8 @@
 use crate::datatypes::{
-    CertStatus, CertificateId, Certification, CertificationError, DataKey, Product,
-    SupplyChainError, VerifyError, CERTIFICATE_MANAGEMENT_CONTRACT_KEY,
+    AuthenticityReport, CertStatus, CertificateId, Certification, CertificationError, DataKey,
+    Product, SupplyChainError, VerifyError, CERTIFICATE_MANAGEMENT_CONTRACT_KEY,
 };
-use crate::utils;
+use crate::{telemetry, utils};
 use soroban_sdk::{vec, Address, BytesN, Env, IntoVal, Symbol, Vec};
 
 /// Validate product authenticity against recorded data and certifications
@@ -11,7 +11,7 @@ pub fn verify_authenticity(
     farmer_id: Address,
     product_id: BytesN<32>,
     verification_data: BytesN<32>,
-) -> Result<bool, SupplyChainError> {
+) -> Result<AuthenticityReport, SupplyChainError> {
     let product: Product = env
         .storage()
         .persistent()
@@ -24,14 +24,21 @@ pub fn verify_authenticity(
     }
 
     // Basic verification against stages data
-    let is_authentic = verify_stages_integrity(&env, &product, &verification_data);
+    let mut is_authentic = verify_stages_integrity(&env, &product, &verification_data);
 
     // If certificate is linked, verify with certificate-management-contract
-    if let CertificateId::Some(cert_id) = product.certificate_id {
-        return validate_certificate_hash(&env, &farmer_id, &cert_id, &verification_data);
+    if let CertificateId::Some(cert_id) = &product.certificate_id {
+        is_authentic = validate_certificate_hash(&env, &farmer_id, cert_id, &verification_data)?;
     }
 
-    Ok(is_authentic)
+    // Report cold-chain excursions recorded on this batch or any batch it came from
+    let (cold_chain_breaches, last_breach_at) = telemetry::lineage_breaches(&env, &product_id)?;
+
+    Ok(AuthenticityReport {
+        authentic: is_authentic,
+        cold_chain_breaches,
+        last_breach_at,
+    })
 }
 
 /// Associate a product with a certification from certificate-management-contract
@@ -1,21 +1,27 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
 
 mod metadata;
 mod mint;
+mod ownership;
 mod proof;
+mod storage;
+mod supply_chain;
 
 #[contract]
 pub struct TransactionNFTContract;
 
 #[contractimpl]
 impl TransactionNFTContract {
+    /// Mint a receipt NFT for a purchase. `nonce` is the order reference that keeps
+    /// otherwise identical purchases apart.
     pub fn mint_nft(
         env: Env,
         buyer: Address,
         seller: Address,
         amount: u64,
         product: BytesN<32>,
+        nonce: u64,
     ) -> BytesN<32> {
         if buyer == seller {
             panic!("Buyer and seller cannot be the same address");
@@ -30,10 +36,12 @@ impl TransactionNFTContract {
         buyer.require_auth();
         seller.require_auth();
 
+        supply_chain::validate_product(&env, &product);
+
         let product_bytes: soroban_sdk::Bytes = product.clone().into();
 
         // Check for duplicate transaction before generating proof
-        if proof::transaction_exists(&env, &buyer, &seller, amount, &product_bytes) {
+        if proof::transaction_exists(&env, &buyer, &seller, amount, &product_bytes, nonce) {
             panic!("Duplicate transaction detected");
         }
 
@@ -43,9 +51,18 @@ impl TransactionNFTContract {
             seller.clone(),
             amount,
             product_bytes,
+            nonce,
         );
 
-        mint::mint_nft(&env, &buyer, tx_id.clone(), &seller, amount, &product);
+        mint::mint_nft(
+            &env,
+            &buyer,
+            tx_id.clone(),
+            &seller,
+            amount,
+            &product,
+            nonce,
+        );
 
         tx_id
     }
@@ -53,6 +70,99 @@ impl TransactionNFTContract {
     pub fn get_nft_metadata(env: Env, tx_id: BytesN<32>) -> Option<mint::NFTMetadata> {
         metadata::get_metadata(&env, &tx_id)
     }
+
+    /// Recompute a receipt's transaction ID from its purchase details and confirm it was minted here
+    pub fn verify_proof(
+        env: Env,
+        tx_id: BytesN<32>,
+        buyer: Address,
+        seller: Address,
+        amount: u64,
+        product: BytesN<32>,
+        nonce: u64,
+    ) -> bool {
+        proof::verify_proof(
+            &env,
+            &tx_id,
+            &buyer,
+            &seller,
+            amount,
+            &product.into(),
+            nonce,
+        )
+    }
+
+    /// Set the admin and the base URI used to build token metadata URIs
+    pub fn initialize(env: Env, admin: Address, base_uri: String) {
+        metadata::initialize(&env, &admin, &base_uri);
+    }
+
+    pub fn set_base_uri(env: Env, admin: Address, base_uri: String) {
+        metadata::set_base_uri(&env, &admin, &base_uri);
+    }
+
+    /// Validate minted products against a supply-chain-tracking contract, or stop with `None`
+    pub fn set_supply_chain_contract(env: Env, admin: Address, contract: Option<Address>) {
+        supply_chain::set_supply_chain_contract(&env, &admin, contract);
+    }
+
+    pub fn get_supply_chain_contract(env: Env) -> Option<Address> {
+        supply_chain::get_supply_chain_contract(&env)
+    }
+
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, "Transaction NFT")
+    }
+
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "TXNFT")
+    }
+
+    pub fn token_uri(env: Env, tx_id: BytesN<32>) -> String {
+        metadata::token_uri(&env, &tx_id)
+    }
+
+    pub fn owner_of(env: Env, tx_id: BytesN<32>) -> Address {
+        ownership::owner_of(&env, &tx_id)
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        ownership::balance_of(&env, &owner)
+    }
+
+    /// Transfer a token held by `from`, signed by the owner
+    pub fn transfer(env: Env, from: Address, to: Address, tx_id: BytesN<32>) {
+        ownership::transfer(&env, &from, &from, &to, &tx_id);
+    }
+
+    /// Transfer a token on the owner's behalf, signed by its approved address
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        tx_id: BytesN<32>,
+    ) {
+        ownership::transfer(&env, &spender, &from, &to, &tx_id);
+    }
+
+    /// Approve an address to transfer a token, or clear the approval with `None`
+    pub fn approve(env: Env, owner: Address, approved: Option<Address>, tx_id: BytesN<32>) {
+        ownership::approve(&env, &owner, approved, &tx_id);
+    }
+
+    pub fn get_approved(env: Env, tx_id: BytesN<32>) -> Option<Address> {
+        ownership::get_approved(&env, &tx_id)
+    }
+
+    pub fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> BytesN<32> {
+        ownership::token_of_owner_by_index(&env, &owner, index)
+    }
+
+    /// List up to `limit` tokens held by `owner`, starting at `start`
+    pub fn tokens_of_owner(env: Env, owner: Address, start: u32, limit: u32) -> Vec<BytesN<32>> {
+        ownership::tokens_of_owner(&env, &owner, start, limit)
+    }
 }
 
 #[cfg(test)]
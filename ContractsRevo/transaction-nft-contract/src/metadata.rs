@@ -1,6 +1,70 @@
 use crate::mint::NFTMetadata;
-use soroban_sdk::{BytesN, Env};
+use crate::storage::DataKey;
+use soroban_sdk::{Address, BytesN, Env, String, Symbol};
+
+/// Longest base URI that still leaves room for the hex token ID
+pub const MAX_BASE_URI_LEN: u32 = 192;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
 
 pub fn get_metadata(env: &Env, tx_id: &BytesN<32>) -> Option<NFTMetadata> {
-    env.storage().instance().get(tx_id)
+    env.storage()
+        .persistent()
+        .get(&DataKey::Metadata(tx_id.clone()))
+}
+
+pub fn initialize(env: &Env, admin: &Address, base_uri: &String) {
+    if env.storage().instance().has(&DataKey::Admin) {
+        panic!("Contract already initialized");
+    }
+    admin.require_auth();
+
+    env.storage().instance().set(&DataKey::Admin, admin);
+    store_base_uri(env, base_uri);
+}
+
+pub fn set_base_uri(env: &Env, admin: &Address, base_uri: &String) {
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic!("Contract not initialized"));
+    if stored_admin != *admin {
+        panic!("Only the admin can update the base URI");
+    }
+    admin.require_auth();
+
+    store_base_uri(env, base_uri);
+
+    env.events()
+        .publish((Symbol::new(env, "base_uri_updated"),), base_uri.clone());
+}
+
+pub fn get_base_uri(env: &Env) -> Option<String> {
+    env.storage().instance().get(&DataKey::BaseUri)
+}
+
+/// Metadata URI of a token: the configured base URI followed by the hex tx_id
+pub fn token_uri(env: &Env, tx_id: &BytesN<32>) -> String {
+    if get_metadata(env, tx_id).is_none() {
+        panic!("Token does not exist");
+    }
+    let base_uri = get_base_uri(env).unwrap_or_else(|| panic!("Base URI not configured"));
+
+    let base_len = base_uri.len() as usize;
+    let mut buffer = [0u8; MAX_BASE_URI_LEN as usize + 64];
+    base_uri.copy_into_slice(&mut buffer[..base_len]);
+    for (i, byte) in tx_id.to_array().iter().enumerate() {
+        buffer[base_len + i * 2] = HEX_DIGITS[(byte >> 4) as usize];
+        buffer[base_len + i * 2 + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
+    }
+
+    String::from_bytes(env, &buffer[..base_len + 64])
+}
+
+fn store_base_uri(env: &Env, base_uri: &String) {
+    if base_uri.is_empty() || base_uri.len() > MAX_BASE_URI_LEN {
+        panic!("Invalid base URI");
+    }
+    env.storage().instance().set(&DataKey::BaseUri, base_uri);
 }
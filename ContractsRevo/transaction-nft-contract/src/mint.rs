@@ -1,3 +1,5 @@
+use crate::ownership;
+use crate::storage::DataKey;
 use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol};
 
 #[contracttype]
@@ -6,6 +8,7 @@ pub struct NFTMetadata {
     pub seller: Address,
     pub amount: u64,
     pub product: BytesN<32>,
+    pub nonce: u64,
     pub timestamp: u64,
 }
 
@@ -16,6 +19,7 @@ pub fn mint_nft(
     seller: &Address,
     amount: u64,
     product: &BytesN<32>,
+    nonce: u64,
 ) {
     // Fetch the ledger timestamp
     let timestamp = env.ledger().timestamp();
@@ -30,11 +34,15 @@ pub fn mint_nft(
         seller: seller.clone(),
         amount,
         product: product.clone(),
+        nonce,
         timestamp,
     };
 
-    // Store metadata in contract instance storage
-    env.storage().instance().set(&tx_id, &metadata);
+    // Store metadata under its own persistent entry and hand the token to the buyer
+    env.storage()
+        .persistent()
+        .set(&DataKey::Metadata(tx_id.clone()), &metadata);
+    ownership::assign(env, &tx_id, buyer);
 
     // Emit an event for tracking the mint operation
     env.events().publish(
@@ -0,0 +1,133 @@
+use crate::storage::DataKey;
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+/// Maximum number of tokens returned by a single enumeration call
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+pub fn owner_of(env: &Env, tx_id: &BytesN<32>) -> Address {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Owner(tx_id.clone()))
+        .unwrap_or_else(|| panic!("Token does not exist"))
+}
+
+pub fn balance_of(env: &Env, owner: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(owner.clone()))
+        .unwrap_or(0)
+}
+
+pub fn get_approved(env: &Env, tx_id: &BytesN<32>) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Approved(tx_id.clone()))
+}
+
+/// Give a freshly minted token to its first owner
+pub fn assign(env: &Env, tx_id: &BytesN<32>, owner: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Owner(tx_id.clone()), owner);
+    add_to_owner(env, owner, tx_id);
+}
+
+pub fn approve(env: &Env, owner: &Address, approved: Option<Address>, tx_id: &BytesN<32>) {
+    owner.require_auth();
+
+    if owner_of(env, tx_id) != *owner {
+        panic!("Only the owner can approve a transfer");
+    }
+
+    let key = DataKey::Approved(tx_id.clone());
+    match &approved {
+        Some(spender) => {
+            if spender == owner {
+                panic!("Owner cannot approve themselves");
+            }
+            env.storage().persistent().set(&key, spender);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "nft_approved"), tx_id.clone()),
+        (owner.clone(), approved),
+    );
+}
+
+/// Move a token from `from` to `to`. `spender` must be the owner or the approved address.
+pub fn transfer(env: &Env, spender: &Address, from: &Address, to: &Address, tx_id: &BytesN<32>) {
+    spender.require_auth();
+
+    let owner = owner_of(env, tx_id);
+    if owner != *from {
+        panic!("Sender does not own this token");
+    }
+    if spender != from && get_approved(env, tx_id).as_ref() != Some(spender) {
+        panic!("Caller is not authorized to transfer this token");
+    }
+    if from == to {
+        panic!("Cannot transfer a token to its current owner");
+    }
+
+    // Approvals do not survive a change of ownership
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approved(tx_id.clone()));
+
+    remove_from_owner(env, from, tx_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Owner(tx_id.clone()), to);
+    add_to_owner(env, to, tx_id);
+
+    env.events().publish(
+        (Symbol::new(env, "nft_transferred"), tx_id.clone()),
+        (from.clone(), to.clone()),
+    );
+}
+
+pub fn token_of_owner_by_index(env: &Env, owner: &Address, index: u32) -> BytesN<32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OwnedToken(owner.clone(), index))
+        .unwrap_or_else(|| panic!("Owner index out of bounds"))
+}
+
+pub fn tokens_of_owner(env: &Env, owner: &Address, start: u32, limit: u32) -> Vec<BytesN<32>> {
+    let end = balance_of(env, owner).min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+    let mut tokens = Vec::new(env);
+    for index in start..end {
+        tokens.push_back(token_of_owner_by_index(env, owner, index));
+    }
+    tokens
+}
+
+fn add_to_owner(env: &Env, owner: &Address, tx_id: &BytesN<32>) {
+    let index = balance_of(env, owner);
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::OwnedToken(owner.clone(), index), tx_id);
+    storage.set(&DataKey::OwnedIndex(tx_id.clone()), &index);
+    storage.set(&DataKey::Balance(owner.clone()), &(index + 1));
+}
+
+fn remove_from_owner(env: &Env, owner: &Address, tx_id: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    let index: u32 = storage
+        .get(&DataKey::OwnedIndex(tx_id.clone()))
+        .unwrap_or_else(|| panic!("Token is not indexed"));
+    let last_index = balance_of(env, owner) - 1;
+
+    // Move the last token into the freed slot so the list stays contiguous
+    if index != last_index {
+        let last_token: BytesN<32> = token_of_owner_by_index(env, owner, last_index);
+        storage.set(&DataKey::OwnedToken(owner.clone(), index), &last_token);
+        storage.set(&DataKey::OwnedIndex(last_token), &index);
+    }
+
+    storage.remove(&DataKey::OwnedToken(owner.clone(), last_index));
+    storage.remove(&DataKey::OwnedIndex(tx_id.clone()));
+    storage.set(&DataKey::Balance(owner.clone()), &last_index);
+}
@@ -1,32 +1,44 @@
+use crate::storage::DataKey;
 use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{Address, Bytes, BytesN, Env};
 
+/// Derive the transaction ID from the purchase details and the caller-supplied
+/// nonce (order reference). The ledger time is deliberately left out so anyone
+/// holding the receipt details can recompute the ID later.
+pub fn compute_transaction_id(
+    env: &Env,
+    buyer: &Address,
+    seller: &Address,
+    amount: u64,
+    product: &Bytes,
+    nonce: u64,
+) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&buyer.clone().to_xdr(env));
+    data.append(&seller.clone().to_xdr(env));
+    data.append(&Bytes::from_array(env, &amount.to_be_bytes()));
+    data.append(product);
+    data.append(&Bytes::from_array(env, &nonce.to_be_bytes()));
+    data.append(&env.current_contract_address().to_xdr(env));
+
+    let hash = env.crypto().sha256(&data);
+    BytesN::from_array(env, &hash.to_array())
+}
+
 pub fn generate_transaction_proof(
     env: Env,
     buyer: Address,
     seller: Address,
     amount: u64,
     product: Bytes,
+    nonce: u64,
 ) -> BytesN<32> {
-    let buyer_xdr: Bytes = buyer.to_xdr(&env);
-    let seller_xdr: Bytes = seller.to_xdr(&env);
-    let amount_bytes = amount.to_be_bytes();
-    let timestamp_bytes = env.ledger().timestamp().to_be_bytes();
-    let contract_address = env.current_contract_address();
-
-    let mut data = Bytes::new(&env);
-    data.append(&buyer_xdr);
-    data.append(&seller_xdr);
-    data.append(&Bytes::from_array(&env, &amount_bytes));
-    data.append(&product);
-    data.append(&Bytes::from_array(&env, &timestamp_bytes));
-    data.append(&contract_address.to_xdr(&env));
-
-    let hash = env.crypto().sha256(&data);
-    let tx_id = BytesN::from_array(&env, &hash.to_array());
+    let tx_id = compute_transaction_id(&env, &buyer, &seller, amount, &product, nonce);
 
     // Store proof to prevent duplicate transactions
-    env.storage().instance().set(&tx_id, &true);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proof(tx_id.clone()), &true);
 
     tx_id
 }
@@ -37,26 +49,27 @@ pub fn transaction_exists(
     seller: &Address,
     amount: u64,
     product: &Bytes,
+    nonce: u64,
 ) -> bool {
-    let buyer_xdr: Bytes = buyer.to_xdr(env);
-    let seller_xdr: Bytes = seller.to_xdr(env);
-    let amount_bytes = amount.to_be_bytes();
-    let contract_address = env.current_contract_address();
-
-    let mut data = Bytes::new(env);
-    data.append(&buyer_xdr);
-    data.append(&seller_xdr);
-    data.append(&Bytes::from_array(env, &amount_bytes));
-    data.append(&product);
-    data.append(&Bytes::from_array(
-        env,
-        &env.ledger().timestamp().to_be_bytes(),
-    ));
-    data.append(&contract_address.to_xdr(env));
-
-    let hash = env.crypto().sha256(&data);
-    let tx_id = BytesN::from_array(env, &hash.to_array());
+    let tx_id = compute_transaction_id(env, buyer, seller, amount, product, nonce);
 
     // Check if the transaction proof exists
-    env.storage().instance().has(&tx_id)
+    env.storage().persistent().has(&DataKey::Proof(tx_id))
+}
+
+/// Check that `tx_id` was recorded by this contract for exactly these purchase details
+pub fn verify_proof(
+    env: &Env,
+    tx_id: &BytesN<32>,
+    buyer: &Address,
+    seller: &Address,
+    amount: u64,
+    product: &Bytes,
+    nonce: u64,
+) -> bool {
+    compute_transaction_id(env, buyer, seller, amount, product, nonce) == *tx_id
+        && env
+            .storage()
+            .persistent()
+            .has(&DataKey::Proof(tx_id.clone()))
 }
@@ -0,0 +1,17 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+/// Storage keys. `Admin`, `BaseUri` and `SupplyChain` live in instance storage; everything
+/// else is a persistent per-token or per-owner entry.
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    BaseUri,
+    SupplyChain,
+    Proof(BytesN<32>),
+    Metadata(BytesN<32>),
+    Owner(BytesN<32>),
+    Approved(BytesN<32>),
+    Balance(Address),
+    OwnedToken(Address, u32),
+    OwnedIndex(BytesN<32>),
+}
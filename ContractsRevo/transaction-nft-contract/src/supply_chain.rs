@@ -0,0 +1,45 @@
+use crate::storage::DataKey;
+use soroban_sdk::{Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+pub fn set_supply_chain_contract(env: &Env, admin: &Address, contract: Option<Address>) {
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic!("Contract not initialized"));
+    if stored_admin != *admin {
+        panic!("Only the admin can configure the supply chain contract");
+    }
+    admin.require_auth();
+
+    match &contract {
+        Some(address) => env.storage().instance().set(&DataKey::SupplyChain, address),
+        None => env.storage().instance().remove(&DataKey::SupplyChain),
+    }
+
+    env.events()
+        .publish((Symbol::new(env, "supply_chain_updated"),), contract);
+}
+
+pub fn get_supply_chain_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::SupplyChain)
+}
+
+/// When a supply chain contract is configured, require the product to be registered there
+pub fn validate_product(env: &Env, product: &BytesN<32>) {
+    let Some(supply_chain) = get_supply_chain_contract(env) else {
+        return;
+    };
+
+    let args: Vec<Val> = Vec::from_array(env, [product.into_val(env)]);
+    if env
+        .try_invoke_contract::<Val, soroban_sdk::Error>(
+            &supply_chain,
+            &Symbol::new(env, "get_product_details"),
+            args,
+        )
+        .is_err()
+    {
+        panic!("Product not registered in supply chain");
+    }
+}
@@ -8,7 +8,7 @@ fn test_nft_creation_success() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Verify the transaction ID is valid
     assert_eq!(tx_id.len(), 32, "Transaction ID should be 32 bytes");
@@ -27,7 +27,7 @@ fn test_unique_nft_ids() {
     let mut nft_ids = std::vec::Vec::new();
 
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         nft_ids.push(tx_id);
     }
 
@@ -49,7 +49,7 @@ fn test_nft_creation_with_metadata_validation() {
     let amount = 5000_u64;
     let product = create_product_id(&env, 42);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
     // Validate all metadata fields
@@ -72,7 +72,7 @@ fn test_nft_creation_with_different_amounts() {
 
     for &amount in &test_amounts {
         let (buyer, seller, _, product) = create_transaction_with_amount(&env, amount);
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
         let metadata = client.get_nft_metadata(&tx_id).unwrap();
         assert_eq!(
@@ -93,7 +93,7 @@ fn test_nft_creation_with_different_products() {
     let products = create_product_variations(&env);
 
     for product in products {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
         assert_eq!(
@@ -109,7 +109,7 @@ fn test_nft_creation_event_emission() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Check that the minting event was emitted
     let events = env.events().all();
@@ -130,7 +130,7 @@ fn test_nft_creation_timestamp_accuracy() {
     let (env, _contract_id, client) = setup_test_with_timestamp(timestamp);
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
     assert_eq!(
@@ -145,7 +145,7 @@ fn test_nft_creation_with_time_progression() {
     let (buyer, seller, amount, product1) = create_standard_transaction(&env);
 
     // Create first NFT
-    let tx_id1 = client.mint_nft(&buyer, &seller, &amount, &product1);
+    let tx_id1 = client.mint_nft(&buyer, &seller, &amount, &product1, &ORDER_NONCE);
     let metadata1 = client.get_nft_metadata(&tx_id1).unwrap();
 
     // Advance time
@@ -153,7 +153,7 @@ fn test_nft_creation_with_time_progression() {
 
     // Create second NFT with different product
     let product2 = create_product_id(&env, 2);
-    let tx_id2 = client.mint_nft(&buyer, &seller, &amount, &product2);
+    let tx_id2 = client.mint_nft(&buyer, &seller, &amount, &product2, &ORDER_NONCE);
     let metadata2 = client.get_nft_metadata(&tx_id2).unwrap();
 
     assert!(
@@ -175,7 +175,7 @@ fn test_nft_creation_same_buyer_seller() {
     let product = create_product_id(&env, 1);
 
     // This should panic because buyer and seller are the same
-    client.mint_nft(&address, &address, &amount, &product);
+    client.mint_nft(&address, &address, &amount, &product, &ORDER_NONCE);
 }
 
 #[test]
@@ -188,7 +188,7 @@ fn test_nft_creation_zero_amount() {
     let product = create_product_id(&env, 1);
 
     // This should panic because amount is zero
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 }
 
 #[test]
@@ -198,10 +198,10 @@ fn test_nft_creation_duplicate_transaction() {
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
     // Create the first NFT
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Attempting to create the same transaction again should panic
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 }
 
 #[test]
@@ -214,7 +214,7 @@ fn test_nft_creation_with_invalid_timestamp_environment() {
     let product = create_product_id(&env, 1);
 
     // This should panic because timestamp is 0
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 }
 
 #[test]
@@ -232,7 +232,7 @@ fn test_nft_creation_different_buyer_seller_combinations() {
             if i != j {
                 // Ensure buyer != seller
                 let product = create_product_id(&env, combination_count);
-                let tx_id = client.mint_nft(buyer, seller, &amount, &product);
+                let tx_id = client.mint_nft(buyer, seller, &amount, &product, &ORDER_NONCE);
                 nft_ids.push(tx_id);
                 combination_count += 1;
             }
@@ -273,7 +273,7 @@ fn test_nft_creation_metadata_consistency() {
     let transactions = create_multiple_transactions(&env, 10);
 
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
         // Verify metadata consistency
@@ -315,7 +315,7 @@ fn test_nft_creation_large_amounts() {
     let large_amounts = [u64::MAX - 1, u64::MAX / 2, 1_000_000_000_000_u64];
 
     for &amount in &large_amounts {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
         assert_eq!(
@@ -340,7 +340,7 @@ fn test_nft_creation_edge_case_products() {
     ];
 
     for product in edge_products {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
         assert_eq!(
@@ -1,4 +1,5 @@
 pub mod creation;
+pub mod supply_chain;
 pub mod transfer;
 pub mod utils;
 pub mod verification;
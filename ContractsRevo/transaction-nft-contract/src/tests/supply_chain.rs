@@ -0,0 +1,98 @@
+extern crate std;
+
+use crate::tests::utils::*;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, testutils::Address as _, Address, BytesN, Env, String,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MockSupplyChainError {
+    ProductNotFound = 1,
+}
+
+/// Stand-in for supply-chain-tracking-contract's product lookup
+#[contract]
+pub struct MockSupplyChain;
+
+#[contractimpl]
+impl MockSupplyChain {
+    pub fn register(env: Env, product_id: BytesN<32>) {
+        env.storage().persistent().set(&product_id, &true);
+    }
+
+    pub fn get_product_details(
+        env: Env,
+        product_id: BytesN<32>,
+    ) -> Result<BytesN<32>, MockSupplyChainError> {
+        if env.storage().persistent().has(&product_id) {
+            Ok(product_id)
+        } else {
+            Err(MockSupplyChainError::ProductNotFound)
+        }
+    }
+}
+
+fn setup_supply_chain(
+    env: &Env,
+    client: &crate::TransactionNFTContractClient,
+) -> (Address, MockSupplyChainClient<'static>) {
+    let admin = Address::generate(env);
+    client.initialize(&admin, &String::from_str(env, "https://nft.revo.example/"));
+
+    let supply_chain_id = env.register(MockSupplyChain, ());
+    client.set_supply_chain_contract(&admin, &Some(supply_chain_id.clone()));
+
+    (admin, MockSupplyChainClient::new(env, &supply_chain_id))
+}
+
+#[test]
+fn test_mint_without_supply_chain_skips_validation() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    assert_eq!(client.get_supply_chain_contract(), None);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+}
+
+#[test]
+fn test_mint_registered_supply_chain_product() {
+    let (env, _contract_id, client) = setup_test();
+    let (_admin, supply_chain) = setup_supply_chain(&env, &client);
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    supply_chain.register(&product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+
+    assert_eq!(client.get_nft_metadata(&tx_id).unwrap().product, product);
+}
+
+#[test]
+#[should_panic(expected = "Product not registered in supply chain")]
+fn test_mint_unregistered_supply_chain_product_fails() {
+    let (env, _contract_id, client) = setup_test();
+    setup_supply_chain(&env, &client);
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+}
+
+#[test]
+fn test_clearing_supply_chain_disables_validation() {
+    let (env, _contract_id, client) = setup_test();
+    let (admin, _supply_chain) = setup_supply_chain(&env, &client);
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    client.set_supply_chain_contract(&admin, &None);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+}
+
+#[test]
+#[should_panic(expected = "Only the admin can configure the supply chain contract")]
+fn test_set_supply_chain_requires_admin() {
+    let (env, _contract_id, client) = setup_test();
+    setup_supply_chain(&env, &client);
+
+    client.set_supply_chain_contract(&Address::generate(&env), &None);
+}
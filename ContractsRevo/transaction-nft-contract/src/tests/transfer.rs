@@ -1,17 +1,14 @@
 extern crate std;
 
 use crate::tests::utils::*;
-
-// Note: The current contract doesn't have explicit transfer functionality.
-// These tests focus on authorization mechanisms and ownership concepts
-// that would be needed for a complete NFT transfer system.
+use soroban_sdk::{testutils::Address as _, Address, String};
 
 #[test]
 fn test_nft_ownership_verification() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
     // Verify the buyer is recorded as the NFT owner in metadata
@@ -27,7 +24,7 @@ fn test_nft_metadata_immutability() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     let original_metadata = client.get_nft_metadata(&tx_id).unwrap();
 
     // Advance time to simulate potential changes
@@ -68,7 +65,7 @@ fn test_authorization_requirement_during_minting() {
 
     // The contract requires authorization from both buyer and seller
     // This test verifies that the authorization check is working
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // If we reach here, authorization was successful
     assert_eq!(
@@ -90,7 +87,7 @@ fn test_multiple_nft_ownership_tracking() {
     // Create multiple NFTs with the same buyer but different sellers
     for (i, seller) in sellers.iter().enumerate() {
         let product = create_product_id(&env, (i + 1) as u8);
-        let tx_id = client.mint_nft(&buyer, seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, seller, &amount, &product, &ORDER_NONCE);
         nft_ids.push((tx_id, seller));
     }
 
@@ -115,7 +112,7 @@ fn test_nft_uniqueness_prevents_double_ownership() {
     let product = create_product_id(&env, 1);
 
     // First buyer creates NFT
-    let tx_id1 = client.mint_nft(&buyer1, &seller, &amount, &product);
+    let tx_id1 = client.mint_nft(&buyer1, &seller, &amount, &product, &ORDER_NONCE);
 
     // Verify first NFT exists
     let metadata = client.get_nft_metadata(&tx_id1).unwrap();
@@ -132,10 +129,10 @@ fn test_nft_duplicate_transaction_prevention() {
     let product = create_product_id(&env, 1);
 
     // First buyer creates NFT
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Same buyer tries to create NFT for same transaction (should panic)
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 }
 
 #[test]
@@ -143,7 +140,7 @@ fn test_nft_access_control_metadata_retrieval() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Anyone should be able to retrieve metadata (public read access)
     let metadata = client.get_nft_metadata(&tx_id).unwrap();
@@ -166,7 +163,7 @@ fn test_nft_transaction_proof_linkage() {
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
     let product_bytes = create_product_bytes(&env, 1);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Verify the transaction proof exists and links to the NFT
     let proof_exists = verify_transaction_proof_exists(
@@ -176,6 +173,7 @@ fn test_nft_transaction_proof_linkage() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         proof_exists,
@@ -198,7 +196,13 @@ fn test_transfer_restrictions_same_address_prevention() {
     let product = create_product_id(&env, 1);
 
     // Attempt to mint NFT with same buyer and seller should panic
-    client.mint_nft(&same_address, &same_address, &amount, &product);
+    client.mint_nft(
+        &same_address,
+        &same_address,
+        &amount,
+        &product,
+        &ORDER_NONCE,
+    );
 }
 
 #[test]
@@ -210,7 +214,7 @@ fn test_ownership_chain_verification() {
 
     // Create multiple NFTs and track ownership
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
         ownership_records.push((tx_id, metadata.buyer, metadata.seller));
@@ -248,7 +252,7 @@ fn test_unauthorized_nft_creation_prevention() {
 
     // In the current implementation, mock_all_auths() allows all auth
     // This test verifies the auth structure is in place
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     assert_eq!(
         tx_id.len(),
         32,
@@ -268,7 +272,7 @@ fn test_nft_transfer_history_tracking() {
     // Create NFTs representing a transaction history
     for (i, seller) in sellers.iter().enumerate() {
         let product = create_product_id(&env, (i + 10) as u8);
-        let tx_id = client.mint_nft(&buyer, seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, seller, &amount, &product, &ORDER_NONCE);
         transaction_history.push((tx_id, seller.clone(), env.ledger().timestamp()));
 
         if i < sellers.len() - 1 {
@@ -300,7 +304,7 @@ fn test_bulk_ownership_verification() {
     for (i, seller) in sellers.iter().enumerate() {
         let amount = base_amount + i as u64;
         let product = create_product_id(&env, (i + 1) as u8);
-        let tx_id = client.mint_nft(&owner, seller, &amount, &product);
+        let tx_id = client.mint_nft(&owner, seller, &amount, &product, &ORDER_NONCE);
         owned_nfts.push(tx_id);
     }
 
@@ -320,7 +324,7 @@ fn test_transfer_validation_edge_cases() {
 
     // Test with minimum valid amount
     let (buyer1, seller1, _, product1) = create_transaction_with_amount(&env, 1);
-    let tx_id1 = client.mint_nft(&buyer1, &seller1, &1, &product1);
+    let tx_id1 = client.mint_nft(&buyer1, &seller1, &1, &product1, &ORDER_NONCE);
     let metadata1 = client.get_nft_metadata(&tx_id1).unwrap();
     assert_eq!(
         metadata1.amount, 1,
@@ -329,7 +333,7 @@ fn test_transfer_validation_edge_cases() {
 
     // Test with maximum valid amount
     let (buyer2, seller2, _, product2) = create_transaction_with_amount(&env, u64::MAX);
-    let tx_id2 = client.mint_nft(&buyer2, &seller2, &u64::MAX, &product2);
+    let tx_id2 = client.mint_nft(&buyer2, &seller2, &u64::MAX, &product2, &ORDER_NONCE);
     let metadata2 = client.get_nft_metadata(&tx_id2).unwrap();
     assert_eq!(
         metadata2.amount,
@@ -347,7 +351,7 @@ fn test_concurrent_nft_creation_ownership() {
 
     // Simulate concurrent NFT creation
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         nft_records.push((tx_id, buyer, seller));
     }
 
@@ -379,7 +383,7 @@ fn test_nft_metadata_access_patterns() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Test multiple access patterns
     for _ in 0..5 {
@@ -409,7 +413,7 @@ fn test_transfer_authorization_simulation() {
 
     // Simulate authorization requirement during NFT creation
     // The mint_nft function requires auth from both buyer and seller
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Verify the NFT was created with proper authorization
     let metadata = client.get_nft_metadata(&tx_id).unwrap();
@@ -419,3 +423,169 @@ fn test_transfer_authorization_simulation() {
         "Authorized seller should be recorded"
     );
 }
+
+#[test]
+fn test_buyer_owns_minted_nft() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+
+    assert_eq!(client.owner_of(&tx_id), buyer);
+    assert_eq!(client.balance_of(&buyer), 1);
+    assert_eq!(client.balance_of(&seller), 0);
+    assert_eq!(client.token_of_owner_by_index(&buyer, &0), tx_id);
+}
+
+#[test]
+fn test_transfer_updates_owner_and_balances() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+    let new_owner = Address::generate(&env);
+
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+    client.transfer(&buyer, &new_owner, &tx_id);
+
+    assert_eq!(client.owner_of(&tx_id), new_owner);
+    assert_eq!(client.balance_of(&buyer), 0);
+    assert_eq!(client.balance_of(&new_owner), 1);
+    assert_eq!(client.tokens_of_owner(&new_owner, &0, &10).len(), 1);
+
+    // The original purchase record is unchanged by resale
+    assert_eq!(client.get_nft_metadata(&tx_id).unwrap().buyer, buyer);
+}
+
+#[test]
+#[should_panic(expected = "Sender does not own this token")]
+fn test_transfer_by_non_owner_fails() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+
+    client.transfer(&seller, &Address::generate(&env), &tx_id);
+}
+
+#[test]
+fn test_approved_spender_can_transfer_once() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+    let marketplace = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+    client.approve(&buyer, &Some(marketplace.clone()), &tx_id);
+    assert_eq!(client.get_approved(&tx_id), Some(marketplace.clone()));
+
+    client.transfer_from(&marketplace, &buyer, &new_owner, &tx_id);
+
+    assert_eq!(client.owner_of(&tx_id), new_owner);
+    assert_eq!(client.get_approved(&tx_id), None);
+}
+
+#[test]
+#[should_panic(expected = "Caller is not authorized to transfer this token")]
+fn test_transfer_from_without_approval_fails() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+
+    client.transfer_from(&seller, &buyer, &seller, &tx_id);
+}
+
+#[test]
+#[should_panic(expected = "Caller is not authorized to transfer this token")]
+fn test_cleared_approval_blocks_transfer() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+    let marketplace = Address::generate(&env);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+
+    client.approve(&buyer, &Some(marketplace.clone()), &tx_id);
+    client.approve(&buyer, &None, &tx_id);
+
+    client.transfer_from(&marketplace, &buyer, &marketplace, &tx_id);
+}
+
+#[test]
+#[should_panic(expected = "Token does not exist")]
+fn test_owner_of_unknown_token_fails() {
+    let (env, _contract_id, client) = setup_test();
+    client.owner_of(&create_product_id(&env, 9));
+}
+
+#[test]
+fn test_owner_enumeration_after_transfers() {
+    let (env, _contract_id, client) = setup_test();
+    let buyer = create_buyer(&env);
+    let seller = create_seller(&env);
+    let other = Address::generate(&env);
+
+    let mut minted = std::vec::Vec::new();
+    for seed in 1..=4u8 {
+        let product = create_product_id(&env, seed);
+        minted.push(client.mint_nft(&buyer, &seller, &1000, &product, &ORDER_NONCE));
+    }
+
+    // Removing a token from the middle keeps the owner's list contiguous
+    client.transfer(&buyer, &other, &minted[1]);
+
+    let remaining = client.tokens_of_owner(&buyer, &0, &10);
+    assert_eq!(remaining.len(), 3);
+    assert_eq!(client.balance_of(&buyer), 3);
+    for tx_id in [&minted[0], &minted[2], &minted[3]] {
+        assert!(remaining.contains(tx_id));
+    }
+    assert!(!remaining.contains(&minted[1]));
+
+    // Pagination
+    assert_eq!(client.tokens_of_owner(&buyer, &2, &10).len(), 1);
+    assert_eq!(client.tokens_of_owner(&buyer, &5, &10).len(), 0);
+    assert_eq!(
+        client.tokens_of_owner(&other, &0, &10).get(0),
+        Some(minted[1].clone())
+    );
+}
+
+#[test]
+fn test_token_uri_uses_base_uri() {
+    let (env, _contract_id, client) = setup_test();
+    let admin = Address::generate(&env);
+    let (buyer, seller, _amount, _product) = create_standard_transaction(&env);
+
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "https://nft.revo.example/tx/"),
+    );
+    let tx_id = client.mint_nft(
+        &buyer,
+        &seller,
+        &1000,
+        &create_product_id(&env, 1),
+        &ORDER_NONCE,
+    );
+
+    let hex: std::string::String = tx_id
+        .to_array()
+        .iter()
+        .map(|byte| std::format!("{:02x}", byte))
+        .collect();
+    let expected = std::format!("https://nft.revo.example/tx/{}", hex);
+    assert_eq!(client.token_uri(&tx_id), String::from_str(&env, &expected));
+
+    client.set_base_uri(&admin, &String::from_str(&env, "ipfs://receipts/"));
+    let expected = std::format!("ipfs://receipts/{}", hex);
+    assert_eq!(client.token_uri(&tx_id), String::from_str(&env, &expected));
+}
+
+#[test]
+#[should_panic(expected = "Only the admin can update the base URI")]
+fn test_set_base_uri_requires_admin() {
+    let (env, _contract_id, client) = setup_test();
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "https://nft.revo.example/"));
+    client.set_base_uri(
+        &Address::generate(&env),
+        &String::from_str(&env, "ipfs://x/"),
+    );
+}
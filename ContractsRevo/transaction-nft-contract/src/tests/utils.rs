@@ -6,6 +6,9 @@ use soroban_sdk::{
     Address, Bytes, BytesN, Env,
 };
 
+/// Order reference used by tests that don't care about the nonce
+pub const ORDER_NONCE: u64 = 1;
+
 /// Create a standardized test environment with mock auths and unlimited budget
 pub fn setup_test() -> (Env, Address, TransactionNFTContractClient<'static>) {
     let env = Env::default();
@@ -189,9 +192,10 @@ pub fn verify_transaction_proof_exists(
     seller: &Address,
     amount: u64,
     product: &Bytes,
+    nonce: u64,
 ) -> bool {
     env.as_contract(contract_id, || {
-        crate::proof::transaction_exists(env, buyer, seller, amount, product)
+        crate::proof::transaction_exists(env, buyer, seller, amount, product, nonce)
     })
 }
 
@@ -9,7 +9,7 @@ fn test_transaction_proof_generation_success() {
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
     let product_bytes = create_product_bytes(&env, 1);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Verify transaction proof was generated and stored
     let proof_exists = verify_transaction_proof_exists(
@@ -19,6 +19,7 @@ fn test_transaction_proof_generation_success() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         proof_exists,
@@ -45,13 +46,25 @@ fn test_transaction_proof_uniqueness() {
             seller.clone(),
             amount,
             product_bytes.clone(),
+            ORDER_NONCE,
         )
     });
 
-    // Advance time to create different timestamp
+    // Advance time; the proof must not depend on the ledger timestamp
     advance_time(&env, 3600);
 
-    // Generate second proof with same transaction data but different timestamp
+    let tx_id_later = env.as_contract(&contract_id, || {
+        crate::proof::compute_transaction_id(
+            &env,
+            &buyer,
+            &seller,
+            amount,
+            &product_bytes,
+            ORDER_NONCE,
+        )
+    });
+
+    // Generate second proof with same transaction data but a different order reference
     let tx_id2 = env.as_contract(&contract_id, || {
         crate::proof::generate_transaction_proof(
             env.clone(),
@@ -59,12 +72,17 @@ fn test_transaction_proof_uniqueness() {
             seller.clone(),
             amount,
             product_bytes.clone(),
+            ORDER_NONCE + 1,
         )
     });
 
+    assert_eq!(
+        tx_id1, tx_id_later,
+        "Proof IDs should be deterministic for the same transaction data and nonce"
+    );
     assert_ne!(
         tx_id1, tx_id2,
-        "Proof IDs should be unique even with same transaction data"
+        "Proof IDs should be unique for different nonces with the same transaction data"
     );
 }
 
@@ -82,6 +100,7 @@ fn test_transaction_existence_verification() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         !exists_before,
@@ -89,7 +108,7 @@ fn test_transaction_existence_verification() {
     );
 
     // Create NFT
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // After creating NFT, transaction should exist
     let exists_after = verify_transaction_proof_exists(
@@ -99,6 +118,7 @@ fn test_transaction_existence_verification() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         exists_after,
@@ -111,7 +131,7 @@ fn test_nft_transaction_linkage_validation() {
     let (env, _contract_id, client) = setup_test();
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     let metadata = client.get_nft_metadata(&tx_id).unwrap();
 
     // Verify NFT metadata matches transaction parameters
@@ -149,6 +169,7 @@ fn test_verification_of_nonexistent_transaction() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         !exists,
@@ -163,7 +184,7 @@ fn test_verification_with_invalid_parameters() {
     let product_bytes = create_product_bytes(&env, 1);
 
     // Create valid NFT
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Test verification with wrong buyer
     let wrong_buyer = create_buyer(&env);
@@ -174,6 +195,7 @@ fn test_verification_with_invalid_parameters() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         !wrong_buyer_exists,
@@ -189,6 +211,7 @@ fn test_verification_with_invalid_parameters() {
         &wrong_seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         !wrong_seller_exists,
@@ -203,6 +226,7 @@ fn test_verification_with_invalid_parameters() {
         &seller,
         amount + 1,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         !wrong_amount_exists,
@@ -218,6 +242,7 @@ fn test_verification_with_invalid_parameters() {
         &seller,
         amount,
         &wrong_product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         !wrong_product_exists,
@@ -231,7 +256,7 @@ fn test_duplicate_transaction_prevention_success() {
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
     // Create first NFT successfully
-    let tx_id1 = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id1 = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
     assert_eq!(tx_id1.len(), 32, "First NFT should be created successfully");
 
     // Verify the NFT was created
@@ -246,10 +271,10 @@ fn test_duplicate_transaction_prevention_panic() {
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
 
     // Create first NFT successfully
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Attempt to create duplicate transaction should panic
-    client.mint_nft(&buyer, &seller, &amount, &product);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 }
 
 #[test]
@@ -260,7 +285,7 @@ fn test_transaction_verification_across_multiple_nfts() {
 
     // Create multiple NFTs
     for (buyer, seller, amount, product) in &transactions {
-        let tx_id = client.mint_nft(buyer, seller, amount, product);
+        let tx_id = client.mint_nft(buyer, seller, amount, product, &ORDER_NONCE);
         tx_ids.push(tx_id);
     }
 
@@ -274,6 +299,7 @@ fn test_transaction_verification_across_multiple_nfts() {
             seller,
             *amount,
             &product_bytes,
+            ORDER_NONCE,
         );
         assert!(proof_exists, "Transaction proof should exist for each NFT");
 
@@ -303,7 +329,7 @@ fn test_verification_consistency_over_time() {
     let (buyer, seller, amount, product) = create_standard_transaction(&env);
     let product_bytes = create_product_bytes(&env, 1);
 
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Verify transaction exists immediately
     let exists_immediately = verify_transaction_proof_exists(
@@ -313,6 +339,7 @@ fn test_verification_consistency_over_time() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         exists_immediately,
@@ -322,9 +349,7 @@ fn test_verification_consistency_over_time() {
     // Advance time and verify transaction still exists
     advance_time(&env, 86400); // 24 hours
 
-    // Note: The transaction proof depends on the current ledger timestamp
-    // After advancing time, we need to generate a new proof to verify with the new timestamp
-    // The original proof still exists in storage, but verification needs current timestamp
+    // The proof does not depend on the ledger timestamp, so it can still be found
     let new_exists = verify_transaction_proof_exists(
         &env,
         &contract_id,
@@ -332,9 +357,13 @@ fn test_verification_consistency_over_time() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
+    );
+    assert!(
+        new_exists,
+        "Transaction should still exist after time passes"
     );
-    // This might be false because the proof includes timestamp and it has changed
-    // The original NFT should still exist though
+
     let metadata_still_exists = client.get_nft_metadata(&tx_id);
     assert!(
         metadata_still_exists.is_some(),
@@ -369,6 +398,7 @@ fn test_transaction_proof_hash_integrity() {
             seller.clone(),
             amount,
             product_bytes.clone(),
+            ORDER_NONCE,
         )
     });
 
@@ -380,6 +410,7 @@ fn test_transaction_proof_hash_integrity() {
             seller.clone(),
             amount,
             product_bytes.clone(),
+            ORDER_NONCE,
         )
     });
 
@@ -403,7 +434,7 @@ fn test_verification_with_edge_case_amounts() {
 
     for &amount in &edge_amounts {
         // Create NFT with edge case amount
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
         // Verify transaction exists
         let proof_exists = verify_transaction_proof_exists(
@@ -413,6 +444,7 @@ fn test_verification_with_edge_case_amounts() {
             &seller,
             amount,
             &product_bytes,
+            ORDER_NONCE,
         );
         assert!(
             proof_exists,
@@ -443,7 +475,7 @@ fn test_verification_with_different_product_variations() {
         let product_bytes = Bytes::from_array(&env, &product.to_array());
 
         // Create NFT with product variation
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, product, &ORDER_NONCE);
 
         // Verify transaction exists
         let proof_exists = verify_transaction_proof_exists(
@@ -453,6 +485,7 @@ fn test_verification_with_different_product_variations() {
             &seller,
             amount,
             &product_bytes,
+            ORDER_NONCE,
         );
         assert!(
             proof_exists,
@@ -478,7 +511,7 @@ fn test_bulk_verification_performance() {
 
     // Create bulk NFTs
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         created_transactions.push((buyer, seller, amount, product, tx_id));
     }
 
@@ -492,6 +525,7 @@ fn test_bulk_verification_performance() {
             &seller,
             amount,
             &product_bytes,
+            ORDER_NONCE,
         );
         assert!(proof_exists, "Bulk transaction proof should exist");
 
@@ -515,7 +549,7 @@ fn test_verification_integration_with_purchase_flow() {
     let product_bytes = create_product_bytes(&env, 42);
 
     // Step 1: Create NFT (simulating purchase completion)
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // Step 2: Verify transaction proof exists (for review system integration)
     let proof_exists = verify_transaction_proof_exists(
@@ -525,6 +559,7 @@ fn test_verification_integration_with_purchase_flow() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(
         proof_exists,
@@ -562,7 +597,7 @@ fn test_verification_with_concurrent_transactions() {
 
     // Create concurrent NFTs
     for (buyer, seller, amount, product) in concurrent_transactions.clone() {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         results.push((buyer, seller, amount, product, tx_id));
     }
 
@@ -577,6 +612,7 @@ fn test_verification_with_concurrent_transactions() {
             &seller,
             amount,
             &product_bytes,
+            ORDER_NONCE,
         );
         assert!(proof_exists, "Concurrent transaction proof should exist");
 
@@ -587,7 +623,7 @@ fn test_verification_with_concurrent_transactions() {
 }
 
 #[test]
-fn test_timestamp_inclusion_in_verification() {
+fn test_timestamp_exclusion_from_verification() {
     let timestamp1 = 50000_u64;
     let timestamp2 = 60000_u64;
 
@@ -603,7 +639,7 @@ fn test_timestamp_inclusion_in_verification() {
     let product_bytes1 = create_product_bytes(&env1, 1);
     let product_bytes2 = create_product_bytes(&env2, 1);
 
-    // Generate proofs with different timestamps but same transaction data structure
+    // Generate proofs at different timestamps for the same transaction data
     let tx_id1 = env1.as_contract(&contract_id1, || {
         crate::proof::generate_transaction_proof(
             env1.clone(),
@@ -611,6 +647,7 @@ fn test_timestamp_inclusion_in_verification() {
             seller1.clone(),
             amount,
             product_bytes1.clone(),
+            ORDER_NONCE,
         )
     });
 
@@ -621,13 +658,15 @@ fn test_timestamp_inclusion_in_verification() {
             seller2.clone(),
             amount,
             product_bytes2.clone(),
+            ORDER_NONCE,
         )
     });
 
-    // Since addresses and timestamps are different, proofs should be different
-    assert_ne!(
+    // Both environments derive the same addresses, so only the timestamp differs
+    assert_eq!(buyer1.to_string(), buyer2.to_string());
+    assert_eq!(
         tx_id1, tx_id2,
-        "Proofs should be different with different timestamps and addresses"
+        "Proofs should not depend on the ledger timestamp"
     );
 }
 
@@ -652,7 +691,7 @@ fn test_high_volume_nft_creation_scalability() {
 
     // Create high volume of NFTs
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
         created_nfts.push((tx_id, buyer, seller, amount, product));
     }
 
@@ -678,6 +717,7 @@ fn test_high_volume_nft_creation_scalability() {
             seller,
             *amount,
             &product_bytes,
+            ORDER_NONCE,
         );
         assert!(
             proof_exists,
@@ -696,7 +736,7 @@ fn test_concurrent_verification_operations() {
 
     // Create NFTs and immediately verify them
     for (buyer, seller, amount, product) in transactions {
-        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+        let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
         // Immediate verification
         let product_bytes = Bytes::from_array(&env, &product.to_array());
@@ -707,6 +747,7 @@ fn test_concurrent_verification_operations() {
             &seller,
             amount,
             &product_bytes,
+            ORDER_NONCE,
         );
 
         verification_results.push((tx_id, proof_exists));
@@ -729,7 +770,7 @@ fn test_verification_system_integrity() {
     let product_bytes = create_product_bytes(&env, 1);
 
     // Create NFT and verify complete system integrity
-    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product);
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
 
     // 1. Verify NFT exists
     let metadata = client.get_nft_metadata(&tx_id);
@@ -743,6 +784,7 @@ fn test_verification_system_integrity() {
         &seller,
         amount,
         &product_bytes,
+        ORDER_NONCE,
     );
     assert!(proof_exists, "Transaction proof should exist");
 
@@ -760,3 +802,87 @@ fn test_verification_system_integrity() {
     // 4. Note: Duplicate transaction prevention is tested separately
     // as it requires should_panic annotation for proper testing
 }
+
+#[test]
+fn test_same_purchase_with_new_nonce_mints_new_nft() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    let tx_id1 = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+    let tx_id2 = client.mint_nft(&buyer, &seller, &amount, &product, &(ORDER_NONCE + 1));
+
+    assert_ne!(tx_id1, tx_id2);
+    assert_eq!(
+        client.get_nft_metadata(&tx_id2).unwrap().nonce,
+        ORDER_NONCE + 1
+    );
+}
+
+#[test]
+#[should_panic(expected = "Duplicate transaction detected")]
+fn test_duplicate_nonce_rejected_after_time_passes() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+    advance_time(&env, 86400);
+    client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+}
+
+#[test]
+fn test_verify_proof_recomputes_receipt() {
+    let (env, _contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    let tx_id = client.mint_nft(&buyer, &seller, &amount, &product, &ORDER_NONCE);
+    advance_time(&env, 3600);
+
+    assert!(client.verify_proof(&tx_id, &buyer, &seller, &amount, &product, &ORDER_NONCE));
+
+    // Any mismatched detail fails verification
+    assert!(!client.verify_proof(&tx_id, &seller, &buyer, &amount, &product, &ORDER_NONCE));
+    assert!(!client.verify_proof(
+        &tx_id,
+        &buyer,
+        &seller,
+        &(amount + 1),
+        &product,
+        &ORDER_NONCE
+    ));
+    assert!(!client.verify_proof(
+        &tx_id,
+        &buyer,
+        &seller,
+        &amount,
+        &create_product_id(&env, 2),
+        &ORDER_NONCE
+    ));
+    assert!(!client.verify_proof(
+        &tx_id,
+        &buyer,
+        &seller,
+        &amount,
+        &product,
+        &(ORDER_NONCE + 1)
+    ));
+}
+
+#[test]
+fn test_verify_proof_rejects_unminted_receipt() {
+    let (env, contract_id, client) = setup_test();
+    let (buyer, seller, amount, product) = create_standard_transaction(&env);
+
+    // A correctly derived ID is not a receipt until it has been minted
+    let tx_id = env.as_contract(&contract_id, || {
+        crate::proof::compute_transaction_id(
+            &env,
+            &buyer,
+            &seller,
+            amount,
+            &product.clone().into(),
+            ORDER_NONCE,
+        )
+    });
+
+    assert!(!client.verify_proof(&tx_id, &buyer, &seller, &amount, &product, &ORDER_NONCE));
+}
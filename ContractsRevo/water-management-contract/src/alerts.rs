@@ -1,4 +1,4 @@
-use crate::{datatypes::*, error::ContractError, incentives, utils, water_usage};
+use crate::{allocations, datatypes::*, error::ContractError, incentives, utils, water_usage};
 use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
 
 /// Generates alert for excessive water consumption
@@ -72,6 +72,34 @@ pub fn check_usage_and_alert(env: &Env, usage_id: BytesN<32>) -> Result<(), Cont
     // Get the water usage record
     let usage = water_usage::get_usage(env, usage_id.clone())?;
 
+    // Check the parcel's seasonal allocation, including traded and leased units
+    if let Some(allocation) = allocations::get_active_allocation(env, &usage.parcel_id) {
+        if allocation.used > allocations::get_effective_allocation(env, &usage.parcel_id) {
+            let alert_id = generate_alert_id(
+                env,
+                &usage.farmer_id,
+                &usage.parcel_id,
+                "allocation_exceeded",
+            );
+            let message = String::from_str(env, "Seasonal water allocation exceeded");
+
+            match generate_alert(
+                env,
+                alert_id,
+                usage.farmer_id.clone(),
+                usage.parcel_id.clone(),
+                AlertType::ThresholdExceeded,
+                message,
+            ) {
+                Ok(()) => {}
+                Err(ContractError::AlertAlreadyExists) => {
+                    // Expected - alert already exists for this period
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     // Get threshold for the parcel
     let threshold_result = incentives::get_threshold(env, usage.parcel_id.clone());
     if threshold_result.is_err() {
@@ -80,21 +108,12 @@ pub fn check_usage_and_alert(env: &Env, usage_id: BytesN<32>) -> Result<(), Cont
     }
 
     let threshold = threshold_result.unwrap();
-    let _current_time = env.ledger().timestamp();
+
+    // Totals for the periods containing this usage
+    let aggregate = water_usage::get_usage_aggregate(env, usage.parcel_id.clone(), usage.timestamp);
 
     // Check daily usage
-    let day_start = utils::get_day_start(usage.timestamp);
-    let day_end = day_start + 86400; // 24 hours
-
-    let daily_report = water_usage::get_usage_report(
-        env,
-        usage.farmer_id.clone(),
-        Some(usage.parcel_id.clone()),
-        day_start,
-        day_end,
-    )?;
-
-    if daily_report.total_usage > threshold.daily_limit {
+    if aggregate.daily_total > threshold.daily_limit {
         let alert_id = generate_alert_id(env, &usage.farmer_id, &usage.parcel_id, "daily_exceeded");
         let message = String::from_str(env, "Daily water limit exceeded");
 
@@ -115,18 +134,7 @@ pub fn check_usage_and_alert(env: &Env, usage_id: BytesN<32>) -> Result<(), Cont
     }
 
     // Check weekly usage
-    let week_start = utils::get_week_start(usage.timestamp);
-    let week_end = week_start + 604800; // 7 days
-
-    let weekly_report = water_usage::get_usage_report(
-        env,
-        usage.farmer_id.clone(),
-        Some(usage.parcel_id.clone()),
-        week_start,
-        week_end,
-    )?;
-
-    if weekly_report.total_usage > threshold.weekly_limit {
+    if aggregate.weekly_total > threshold.weekly_limit {
         let alert_id =
             generate_alert_id(env, &usage.farmer_id, &usage.parcel_id, "weekly_exceeded");
         let message = String::from_str(env, "Weekly water limit exceeded");
@@ -147,6 +155,28 @@ pub fn check_usage_and_alert(env: &Env, usage_id: BytesN<32>) -> Result<(), Cont
         }
     }
 
+    // Check monthly usage
+    if aggregate.monthly_total > threshold.monthly_limit {
+        let alert_id =
+            generate_alert_id(env, &usage.farmer_id, &usage.parcel_id, "monthly_exceeded");
+        let message = String::from_str(env, "Monthly water limit exceeded");
+
+        match generate_alert(
+            env,
+            alert_id,
+            usage.farmer_id.clone(),
+            usage.parcel_id.clone(),
+            AlertType::ThresholdExceeded,
+            message,
+        ) {
+            Ok(()) => {}
+            Err(ContractError::AlertAlreadyExists) => {
+                // Expected - alert already exists for this period
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
     // Check for excessive single usage (more than 50% of daily limit in one record)
     if usage.volume > threshold.daily_limit / 2 {
         let alert_id =
@@ -237,7 +267,7 @@ pub fn get_farmer_alerts(env: &Env, farmer_id: Address, include_resolved: bool)
 }
 
 /// Generates a deterministic alert ID based on farmer, parcel, and alert type
-fn generate_alert_id(
+pub fn generate_alert_id(
     env: &Env,
     _farmer_id: &Address,
     parcel_id: &BytesN<32>,
@@ -0,0 +1,339 @@
+use crate::{datatypes::*, error::ContractError, utils};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+/// Maximum number of active leases a parcel can take part in
+const MAX_LEASES_PER_PARCEL: u32 = 20;
+
+/// Registers a parcel in a basin with its owner (admin only)
+pub fn register_parcel(
+    env: &Env,
+    admin: Address,
+    parcel_id: BytesN<32>,
+    basin_id: BytesN<32>,
+    owner: Address,
+) -> Result<(), ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+    utils::validate_identifier(env, &parcel_id)?;
+    utils::validate_identifier(env, &basin_id)?;
+
+    let key = DataKey::Parcel(parcel_id.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(ContractError::ParcelAlreadyRegistered);
+    }
+
+    let parcel = ParcelRegistration {
+        parcel_id: parcel_id.clone(),
+        basin_id: basin_id.clone(),
+        owner: owner.clone(),
+    };
+    env.storage().persistent().set(&key, &parcel);
+
+    env.events().publish(
+        (Symbol::new(env, "parcel_registered"), owner),
+        (parcel_id, basin_id),
+    );
+
+    Ok(())
+}
+
+/// Gets a parcel's registration
+pub fn get_parcel(env: &Env, parcel_id: BytesN<32>) -> Result<ParcelRegistration, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Parcel(parcel_id))
+        .ok_or(ContractError::ParcelNotFound)
+}
+
+/// Issues seasonal allocation units to a parcel (admin only)
+/// Issuing again within the same season tops up the allocation
+pub fn issue_allocation(
+    env: &Env,
+    admin: Address,
+    parcel_id: BytesN<32>,
+    season_start: u64,
+    season_end: u64,
+    units: i128,
+) -> Result<(), ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+
+    if units <= 0 || season_start >= season_end {
+        return Err(ContractError::InvalidAllocation);
+    }
+
+    let parcel = get_parcel(env, parcel_id.clone())?;
+    let allocation = match get_active_allocation(env, &parcel_id) {
+        Some(mut allocation) => {
+            if allocation.season_start != season_start || allocation.season_end != season_end {
+                return Err(ContractError::AllocationSeasonMismatch);
+            }
+            allocation.issued += units;
+            allocation.held += units;
+            allocation
+        }
+        None => WaterAllocation {
+            parcel_id: parcel_id.clone(),
+            basin_id: parcel.basin_id,
+            season_start,
+            season_end,
+            issued: units,
+            held: units,
+            used: 0,
+            overage: 0,
+        },
+    };
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Allocation(parcel_id.clone()), &allocation);
+
+    env.events().publish(
+        (Symbol::new(env, "allocation_issued"), admin),
+        (parcel_id, units, season_start, season_end),
+    );
+
+    Ok(())
+}
+
+/// Gets a parcel's latest allocation
+pub fn get_allocation(env: &Env, parcel_id: BytesN<32>) -> Result<WaterAllocation, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Allocation(parcel_id))
+        .ok_or(ContractError::AllocationNotFound)
+}
+
+/// Gets the allocation a parcel currently holds, counting active leases
+pub fn get_effective_allocation(env: &Env, parcel_id: &BytesN<32>) -> i128 {
+    let allocation = match get_active_allocation(env, parcel_id) {
+        Some(allocation) => allocation,
+        None => return 0,
+    };
+
+    let now = env.ledger().timestamp();
+    let mut effective = allocation.held;
+    for lease in get_parcel_leases(env, parcel_id.clone()).iter() {
+        if lease.expires_at > now {
+            if lease.to_parcel == *parcel_id {
+                effective += lease.units;
+            } else {
+                effective -= lease.units;
+            }
+        }
+    }
+
+    effective
+}
+
+/// Gets the unused allocation a parcel can still draw, sell or lease
+pub fn get_available_allocation(env: &Env, parcel_id: BytesN<32>) -> i128 {
+    match get_active_allocation(env, &parcel_id) {
+        Some(allocation) => get_effective_allocation(env, &parcel_id) - allocation.used,
+        None => 0,
+    }
+}
+
+/// Sells unused allocation units to another parcel in the same basin for the rest of the season
+pub fn transfer_allocation(
+    env: &Env,
+    owner: Address,
+    from_parcel: BytesN<32>,
+    to_parcel: BytesN<32>,
+    units: i128,
+) -> Result<(), ContractError> {
+    let (mut from_allocation, mut to_allocation) =
+        prepare_trade(env, &owner, &from_parcel, &to_parcel, units)?;
+
+    from_allocation.held -= units;
+    to_allocation.held += units;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Allocation(from_parcel.clone()), &from_allocation);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Allocation(to_parcel.clone()), &to_allocation);
+
+    env.events().publish(
+        (Symbol::new(env, "allocation_transferred"), owner),
+        (from_parcel, to_parcel, units),
+    );
+
+    Ok(())
+}
+
+/// Leases unused allocation units to another parcel in the same basin until `expires_at`
+pub fn lease_allocation(
+    env: &Env,
+    owner: Address,
+    from_parcel: BytesN<32>,
+    to_parcel: BytesN<32>,
+    units: i128,
+    expires_at: u64,
+) -> Result<(), ContractError> {
+    let (from_allocation, to_allocation) =
+        prepare_trade(env, &owner, &from_parcel, &to_parcel, units)?;
+
+    if expires_at <= env.ledger().timestamp() || expires_at > from_allocation.season_end {
+        return Err(ContractError::InvalidAllocation);
+    }
+
+    let lease = AllocationLease {
+        from_parcel: from_parcel.clone(),
+        to_parcel: to_parcel.clone(),
+        units,
+        expires_at,
+    };
+    add_lease(env, &from_parcel, &lease)?;
+    add_lease(env, &to_parcel, &lease)?;
+
+    // The lessee may not have held an allocation this season yet
+    env.storage()
+        .persistent()
+        .set(&DataKey::Allocation(to_parcel.clone()), &to_allocation);
+
+    env.events().publish(
+        (Symbol::new(env, "allocation_leased"), owner),
+        (from_parcel, to_parcel, units, expires_at),
+    );
+
+    Ok(())
+}
+
+/// Gets the leases a parcel takes part in, as lessor or lessee
+pub fn get_parcel_leases(env: &Env, parcel_id: BytesN<32>) -> Vec<AllocationLease> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ParcelLeases(parcel_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Counts recorded usage against the parcel's active allocation and returns the
+/// part of it drawn beyond the allocation, which is billed as overage.
+/// The usage is still recorded: metered consumption happened whether or not units were held.
+pub fn record_allocation_usage(
+    env: &Env,
+    parcel_id: &BytesN<32>,
+    volume: i128,
+    timestamp: u64,
+) -> i128 {
+    let mut allocation = match get_active_allocation(env, parcel_id) {
+        Some(allocation) if timestamp >= allocation.season_start => allocation,
+        _ => return 0,
+    };
+
+    let available = (get_effective_allocation(env, parcel_id) - allocation.used).max(0);
+    let overage = (volume - available).clamp(0, volume);
+    allocation.used += volume;
+    allocation.overage += overage;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Allocation(parcel_id.clone()), &allocation);
+
+    if overage > 0 {
+        env.events().publish(
+            (Symbol::new(env, "allocation_overage"), parcel_id.clone()),
+            (overage, allocation.overage),
+        );
+    }
+
+    overage
+}
+
+/// Gets the parcel's allocation if its season has not ended
+pub fn get_active_allocation(env: &Env, parcel_id: &BytesN<32>) -> Option<WaterAllocation> {
+    env.storage()
+        .persistent()
+        .get::<DataKey, WaterAllocation>(&DataKey::Allocation(parcel_id.clone()))
+        .filter(|allocation| env.ledger().timestamp() < allocation.season_end)
+}
+
+/// Checks ownership, basin, season and available units for a sale or lease
+fn prepare_trade(
+    env: &Env,
+    owner: &Address,
+    from_parcel: &BytesN<32>,
+    to_parcel: &BytesN<32>,
+    units: i128,
+) -> Result<(WaterAllocation, WaterAllocation), ContractError> {
+    if units <= 0 || from_parcel == to_parcel {
+        return Err(ContractError::InvalidAllocation);
+    }
+
+    let from = get_parcel(env, from_parcel.clone())?;
+    let to = get_parcel(env, to_parcel.clone())?;
+    if from.owner != *owner {
+        return Err(ContractError::Unauthorized);
+    }
+    if from.basin_id != to.basin_id {
+        return Err(ContractError::BasinMismatch);
+    }
+
+    let from_allocation =
+        get_active_allocation(env, from_parcel).ok_or(ContractError::AllocationNotFound)?;
+    // Units leased in belong to the lessor, so only owned units can be traded
+    if from_allocation.held < units || tradable_units(env, &from_allocation) < units {
+        return Err(ContractError::InsufficientAllocation);
+    }
+
+    let to_allocation = match get_active_allocation(env, to_parcel) {
+        Some(allocation) => {
+            if allocation.season_start != from_allocation.season_start
+                || allocation.season_end != from_allocation.season_end
+            {
+                return Err(ContractError::AllocationSeasonMismatch);
+            }
+            allocation
+        }
+        None => WaterAllocation {
+            parcel_id: to_parcel.clone(),
+            basin_id: to.basin_id,
+            season_start: from_allocation.season_start,
+            season_end: from_allocation.season_end,
+            issued: 0,
+            held: 0,
+            used: 0,
+            overage: 0,
+        },
+    };
+
+    Ok((from_allocation, to_allocation))
+}
+
+/// Owned units a parcel has neither used nor leased out
+fn tradable_units(env: &Env, allocation: &WaterAllocation) -> i128 {
+    let now = env.ledger().timestamp();
+    let mut leased_out = 0;
+    for lease in get_parcel_leases(env, allocation.parcel_id.clone()).iter() {
+        if lease.expires_at > now && lease.from_parcel == allocation.parcel_id {
+            leased_out += lease.units;
+        }
+    }
+
+    allocation.held - allocation.used - leased_out
+}
+
+/// Stores a lease for a parcel, dropping its expired leases
+fn add_lease(
+    env: &Env,
+    parcel_id: &BytesN<32>,
+    lease: &AllocationLease,
+) -> Result<(), ContractError> {
+    let now = env.ledger().timestamp();
+    let mut leases = Vec::new(env);
+    for existing in get_parcel_leases(env, parcel_id.clone()).iter() {
+        if existing.expires_at > now {
+            leases.push_back(existing);
+        }
+    }
+
+    if leases.len() >= MAX_LEASES_PER_PARCEL {
+        return Err(ContractError::TooManyLeases);
+    }
+    leases.push_back(lease.clone());
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ParcelLeases(parcel_id.clone()), &leases);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::{datatypes::*, error::ContractError, utils};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+/// Maximum number of blocks in a tariff
+const MAX_TARIFF_TIERS: u32 = 10;
+
+/// Tier prices are quoted per 1,000 liters
+const LITERS_PER_PRICE_UNIT: i128 = 1000;
+
+/// Sets the block tariff for a parcel (admin only)
+/// Tiers must have strictly increasing block limits; usage above the last limit is billed at the last price.
+/// Usage beyond the parcel's allocation is billed at `overage_price`, which may not undercut the top tier.
+pub fn set_tariff(
+    env: &Env,
+    admin: Address,
+    parcel_id: BytesN<32>,
+    period: BillingPeriod,
+    tiers: Vec<PriceTier>,
+    overage_price: i128,
+) -> Result<(), ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+    utils::validate_identifier(env, &parcel_id)?;
+
+    if tiers.is_empty() || tiers.len() > MAX_TARIFF_TIERS {
+        return Err(ContractError::InvalidTariff);
+    }
+
+    let mut previous_limit = 0i128;
+    let mut top_price = 0i128;
+    for tier in tiers.iter() {
+        if tier.up_to <= previous_limit || tier.price < 0 {
+            return Err(ContractError::InvalidTariff);
+        }
+        previous_limit = tier.up_to;
+        top_price = tier.price;
+    }
+    if overage_price < top_price {
+        return Err(ContractError::InvalidTariff);
+    }
+
+    let tariff = Tariff {
+        parcel_id: parcel_id.clone(),
+        period: period.clone(),
+        tiers,
+        overage_price,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Tariff(parcel_id.clone()), &tariff);
+
+    env.events()
+        .publish((Symbol::new(env, "tariff_set"), admin), (parcel_id, period));
+
+    Ok(())
+}
+
+/// Gets the block tariff for a parcel
+pub fn get_tariff(env: &Env, parcel_id: BytesN<32>) -> Result<Tariff, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Tariff(parcel_id))
+        .ok_or(ContractError::TariffNotFound)
+}
+
+/// Prices a usage at the parcel's tariff, given the aggregate before it was recorded,
+/// and stores the charge against the usage. The `overage` part of the volume, drawn
+/// beyond the parcel's allocation, is billed at the overage price instead of the blocks.
+pub fn charge_usage(
+    env: &Env,
+    usage: &WaterUsage,
+    aggregate_before: &UsageAggregate,
+    overage: i128,
+) -> i128 {
+    let tariff = match get_tariff(env, usage.parcel_id.clone()) {
+        Ok(tariff) => tariff,
+        Err(_) => return 0,
+    };
+
+    let already_used = match tariff.period {
+        BillingPeriod::Daily => aggregate_before.daily_total,
+        BillingPeriod::Weekly => aggregate_before.weekly_total,
+        BillingPeriod::Monthly => aggregate_before.monthly_total,
+    };
+
+    let allocated = usage.volume - overage;
+    let charge = tiered_cost(&tariff.tiers, already_used + allocated)
+        - tiered_cost(&tariff.tiers, already_used)
+        + overage * tariff.overage_price / LITERS_PER_PRICE_UNIT;
+    env.storage()
+        .persistent()
+        .set(&DataKey::UsageCharge(usage.usage_id.clone()), &charge);
+
+    charge
+}
+
+/// Gets the amount billed for a usage record
+pub fn get_usage_charge(env: &Env, usage_id: BytesN<32>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UsageCharge(usage_id))
+        .unwrap_or(0)
+}
+
+/// Cost of a period's cumulative volume under block pricing
+fn tiered_cost(tiers: &Vec<PriceTier>, volume: i128) -> i128 {
+    let mut cost = 0i128;
+    let mut block_start = 0i128;
+    let mut last_price = 0i128;
+
+    for tier in tiers.iter() {
+        if volume <= block_start {
+            return cost / LITERS_PER_PRICE_UNIT;
+        }
+        let block_volume = volume.min(tier.up_to) - block_start;
+        cost += block_volume * tier.price;
+        block_start = tier.up_to;
+        last_price = tier.price;
+    }
+
+    // Usage above the last block stays at the top price
+    if volume > block_start {
+        cost += (volume - block_start) * last_price;
+    }
+
+    cost / LITERS_PER_PRICE_UNIT
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -9,6 +9,7 @@ pub struct WaterUsage {
     pub volume: i128, // Water volume in liters
     pub timestamp: u64,
     pub data_hash: BytesN<32>, // Hash of off-chain sensor data
+    pub metered: bool,         // Submitted by a parcel oracle rather than self-reported
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -60,6 +61,110 @@ pub struct UsageReport {
     pub period_start: u64,
     pub period_end: u64,
     pub efficiency_score: u32, // 0-100 efficiency rating
+    pub water_bill: i128,      // Tariff charges for usage in the period
+}
+
+/// Where incentive rewards are paid
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum RewardPayout {
+    Token(Address),                      // SEP-41 reward token held by this contract
+    LoyaltyProgram(Address, BytesN<32>), // loyalty-token-contract and program ID
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct RewardPool {
+    pub payout: RewardPayout,
+    pub season: u32,
+    pub season_start: u64,
+    pub season_end: u64,
+    pub budget: i128,      // Total funded for the season, including carry-over
+    pub distributed: i128, // Rewards paid out so far this season
+    pub parcel_cap: i128,  // Maximum rewards per parcel per season
+    pub base_reward: i128, // Incentive before efficiency scaling
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ParcelRegistration {
+    pub parcel_id: BytesN<32>,
+    pub basin_id: BytesN<32>, // Allocations can only be traded within a basin
+    pub owner: Address,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct WaterAllocation {
+    pub parcel_id: BytesN<32>,
+    pub basin_id: BytesN<32>,
+    pub season_start: u64,
+    pub season_end: u64,
+    pub issued: i128,  // Units issued by the admin this season
+    pub held: i128,    // Units held after sales, excluding leases
+    pub used: i128,    // Liters used this season
+    pub overage: i128, // Liters used beyond the allocation held, billed at the overage price
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AllocationLease {
+    pub from_parcel: BytesN<32>,
+    pub to_parcel: BytesN<32>,
+    pub units: i128,
+    pub expires_at: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct MeterState {
+    pub last_reading: i128, // Last cumulative meter reading in liters
+    pub last_timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ReadingStatus {
+    Baseline,  // First reading from the oracle, nothing recorded
+    Recorded,  // Delta recorded as water usage
+    Unchanged, // Meter has not moved
+    Flagged,   // Anomaly raised as a sensor malfunction
+}
+
+/// Usage totals for the day, week and month containing a point in time
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct UsageAggregate {
+    pub day_start: u64,
+    pub daily_total: i128,
+    pub week_start: u64,
+    pub weekly_total: i128,
+    pub month_start: u64,
+    pub monthly_total: i128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum BillingPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PriceTier {
+    pub up_to: i128, // Upper bound of the block in liters per billing period
+    pub price: i128, // Price per 1,000 liters within the block
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Tariff {
+    pub parcel_id: BytesN<32>,
+    pub period: BillingPeriod,
+    pub tiers: Vec<PriceTier>,
+    pub overage_price: i128, // Price per 1,000 liters used beyond the parcel's allocation
 }
 
 #[contracttype]
@@ -73,4 +178,17 @@ pub enum DataKey {
     FarmerIncentives(Address),
     FarmerAlerts(Address), // Index of alert IDs for a farmer
     Admin,
+    RewardPool,
+    ParcelRewards(u32, BytesN<32>), // Rewards paid per (season, parcel)
+    Parcel(BytesN<32>),
+    Allocation(BytesN<32>),
+    ParcelLeases(BytesN<32>),
+    ParcelOracle(BytesN<32>, Address),
+    ParcelOracleCount(BytesN<32>),
+    MeterState(BytesN<32>, Address), // Last reading per (parcel, oracle)
+    DailyUsage(BytesN<32>, u64),     // Usage total per (parcel, day start)
+    WeeklyUsage(BytesN<32>, u64),    // Usage total per (parcel, week start)
+    MonthlyUsage(BytesN<32>, u64),   // Usage total per (parcel, month start)
+    Tariff(BytesN<32>),
+    UsageCharge(BytesN<32>), // Tariff charge per usage record
 }
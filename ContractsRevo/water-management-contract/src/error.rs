@@ -37,9 +37,35 @@ pub enum ContractError {
     InvalidFarmerId = 51,
     ParcelNotFound = 52,
     FarmerNotFound = 53,
+    ParcelAlreadyRegistered = 54,
 
     // Oracle and data errors
     OracleDataInvalid = 60,
     SensorDataCorrupted = 61,
     DataVerificationFailed = 62,
+    OracleNotRegistered = 63,
+    OracleAlreadyRegistered = 64,
+    OracleReadingRequired = 65,
+    MeterReadingNotFound = 66,
+
+    // Reward pool errors
+    RewardPoolNotConfigured = 70,
+    InvalidRewardPool = 71,
+    RewardSeasonActive = 72,
+    RewardSeasonInactive = 73,
+    RewardBudgetExhausted = 74,
+    ParcelRewardCapReached = 75,
+    RewardPayoutFailed = 76,
+
+    // Allocation errors
+    AllocationNotFound = 80,
+    InvalidAllocation = 81,
+    InsufficientAllocation = 82,
+    BasinMismatch = 83,
+    AllocationSeasonMismatch = 84,
+    TooManyLeases = 85,
+
+    // Billing errors
+    TariffNotFound = 90,
+    InvalidTariff = 91,
 }
@@ -1,14 +1,14 @@
-use crate::{datatypes::*, error::ContractError, utils, water_usage};
+use crate::{datatypes::*, error::ContractError, reward_pool, utils, water_usage};
 use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
 
 /// Issues incentive rewards for efficient water usage
-pub fn issue_incentive(
-    env: &Env,
-    usage_id: BytesN<32>,
-    base_reward: i128,
-) -> Result<(), ContractError> {
+/// Only oracle-metered usage earns rewards; the base reward comes from the admin's pool configuration
+pub fn issue_incentive(env: &Env, usage_id: BytesN<32>) -> Result<(), ContractError> {
     // Get the water usage record
     let usage = water_usage::get_usage(env, usage_id.clone())?;
+    if !usage.metered {
+        return Err(ContractError::OracleReadingRequired);
+    }
 
     // Check if incentive already exists for this usage
     let incentive_key = DataKey::Incentive(usage_id.clone());
@@ -29,13 +29,19 @@ pub fn issue_incentive(
     }
 
     // Calculate reward amount based on efficiency
-    let reward_amount =
-        utils::calculate_reward_amount(usage.volume, threshold.daily_limit, base_reward);
+    let reward_amount = utils::calculate_reward_amount(
+        usage.volume,
+        threshold.daily_limit,
+        reward_pool::get_base_reward(env),
+    );
 
     if reward_amount <= 0 {
         return Err(ContractError::InvalidRewardAmount);
     }
 
+    // Pay out of the reward pool; the record keeps the amount actually paid
+    let reward_amount = reward_pool::pay_reward(env, &usage, reward_amount)?;
+
     let timestamp = env.ledger().timestamp();
 
     // Create incentive record
@@ -138,11 +144,8 @@ pub fn calculate_farmer_rewards(
 
 /// Processes automatic incentive for a water usage record
 pub fn process_automatic_incentive(env: &Env, usage_id: BytesN<32>) -> Result<(), ContractError> {
-    // Default base reward amount (can be made configurable)
-    const DEFAULT_BASE_REWARD: i128 = 100;
-
     // Try to issue incentive - will fail if not qualified or already exists
-    match issue_incentive(env, usage_id.clone(), DEFAULT_BASE_REWARD) {
+    match issue_incentive(env, usage_id.clone()) {
         Ok(()) => {
             // Emit automatic processing event
             env.events().publish(
@@ -151,7 +154,7 @@ pub fn process_automatic_incentive(env: &Env, usage_id: BytesN<32>) -> Result<()
             );
             Ok(())
         }
-        Err(ContractError::InsufficientEfficiency) => {
+        Err(ContractError::InsufficientEfficiency) | Err(ContractError::OracleReadingRequired) => {
             // Not an error - just doesn't qualify
             Ok(())
         }
@@ -2,9 +2,13 @@
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 
 mod alerts;
+mod allocations;
+mod billing;
 mod datatypes;
 mod error;
 mod incentives;
+mod oracles;
+mod reward_pool;
 mod utils;
 mod water_usage;
 
@@ -39,7 +43,7 @@ impl WaterManagementContract {
         Ok(())
     }
 
-    /// Record water usage data for a parcel or crop
+    /// Self-report water usage for a parcel the farmer owns
     pub fn record_usage(
         env: Env,
         usage_id: BytesN<32>,
@@ -50,6 +54,16 @@ impl WaterManagementContract {
     ) -> Result<(), ContractError> {
         farmer_id.require_auth();
 
+        if allocations::get_parcel(&env, parcel_id.clone())?.owner != farmer_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Parcels with bound oracles only accept oracle-submitted readings
+        if oracles::requires_oracle(&env, &parcel_id) {
+            return Err(ContractError::OracleReadingRequired);
+        }
+        utils::validate_water_volume(volume)?;
+
         // Record the usage
         water_usage::record_usage(
             &env,
@@ -58,34 +72,78 @@ impl WaterManagementContract {
             parcel_id,
             volume,
             data_hash,
+            false,
         )?;
 
-        // Check for alerts - log errors but don't fail the main operation
-        if let Err(_e) = alerts::check_usage_and_alert(&env, usage_id.clone()) {
-            // In production, you would log this error for monitoring
-            // For now, we continue as usage recording is the primary operation
-        }
-
-        // Process automatic incentive - log errors but don't fail the main operation
-        if let Err(_e) = incentives::process_automatic_incentive(&env, usage_id) {
-            // In production, you would log this error for monitoring
-            // For now, we continue as usage recording is the primary operation
-        }
+        process_recorded_usage(&env, usage_id);
 
         Ok(())
     }
 
-    /// Issue incentive reward for efficient water usage
-    pub fn issue_incentive(
+    /// Submit a cumulative meter reading signed by a parcel's oracle
+    pub fn submit_reading(
         env: Env,
+        oracle: Address,
         usage_id: BytesN<32>,
-        base_reward: i128,
+        parcel_id: BytesN<32>,
+        meter_reading: i128,
+        data_hash: BytesN<32>,
+    ) -> Result<ReadingStatus, ContractError> {
+        oracle.require_auth();
+
+        let status = oracles::submit_reading(
+            &env,
+            oracle,
+            usage_id.clone(),
+            parcel_id,
+            meter_reading,
+            data_hash,
+        )?;
+        if status == ReadingStatus::Recorded {
+            process_recorded_usage(&env, usage_id);
+        }
+
+        Ok(status)
+    }
+
+    /// Bind a meter or IoT oracle to a registered parcel (admin only)
+    pub fn register_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        parcel_id: BytesN<32>,
     ) -> Result<(), ContractError> {
+        admin.require_auth();
+        oracles::register_oracle(&env, admin, oracle, parcel_id)
+    }
+
+    /// Unbind an oracle from a parcel (admin only)
+    pub fn revoke_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        parcel_id: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        oracles::revoke_oracle(&env, admin, oracle, parcel_id)
+    }
+
+    /// Get the last reading an oracle submitted for a parcel
+    pub fn get_meter_state(
+        env: Env,
+        parcel_id: BytesN<32>,
+        oracle: Address,
+    ) -> Result<MeterState, ContractError> {
+        oracles::get_meter_state(&env, parcel_id, oracle)
+    }
+
+    /// Issue incentive reward for efficient water usage
+    pub fn issue_incentive(env: Env, usage_id: BytesN<32>) -> Result<(), ContractError> {
         // Get the usage to verify farmer authorization
         let usage = water_usage::get_usage(&env, usage_id.clone())?;
         usage.farmer_id.require_auth();
 
-        incentives::issue_incentive(&env, usage_id, base_reward)
+        incentives::issue_incentive(&env, usage_id)
     }
 
     /// Generate alert for excessive water consumption
@@ -138,6 +196,43 @@ impl WaterManagementContract {
         incentives::get_threshold(&env, parcel_id)
     }
 
+    /// Set the block tariff for a parcel (admin only)
+    pub fn set_tariff(
+        env: Env,
+        admin: Address,
+        parcel_id: BytesN<32>,
+        period: BillingPeriod,
+        tiers: Vec<PriceTier>,
+        overage_price: i128,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        billing::set_tariff(&env, admin, parcel_id, period, tiers, overage_price)
+    }
+
+    /// Get the block tariff for a parcel
+    pub fn get_tariff(env: Env, parcel_id: BytesN<32>) -> Result<Tariff, ContractError> {
+        billing::get_tariff(&env, parcel_id)
+    }
+
+    /// Get the amount billed for a usage record
+    pub fn get_usage_charge(env: Env, usage_id: BytesN<32>) -> i128 {
+        billing::get_usage_charge(&env, usage_id)
+    }
+
+    /// Get a parcel's running usage totals for the current day, week and month
+    pub fn get_usage_aggregate(env: Env, parcel_id: BytesN<32>) -> UsageAggregate {
+        water_usage::get_usage_aggregate(&env, parcel_id, env.ledger().timestamp())
+    }
+
+    /// Get a parcel's usage totals for the day, week and month containing `timestamp`
+    pub fn get_usage_aggregate_at(
+        env: Env,
+        parcel_id: BytesN<32>,
+        timestamp: u64,
+    ) -> UsageAggregate {
+        water_usage::get_usage_aggregate(&env, parcel_id, timestamp)
+    }
+
     /// Get water usage record by ID
     pub fn get_usage(env: Env, usage_id: BytesN<32>) -> Result<WaterUsage, ContractError> {
         water_usage::get_usage(&env, usage_id)
@@ -173,6 +268,130 @@ impl WaterManagementContract {
         incentives::calculate_farmer_rewards(&env, farmer_id, period_start, period_end)
     }
 
+    /// Open a reward season paid in a token or loyalty points (admin only)
+    pub fn configure_reward_pool(
+        env: Env,
+        admin: Address,
+        payout: RewardPayout,
+        season_start: u64,
+        season_end: u64,
+        parcel_cap: i128,
+        base_reward: i128,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        reward_pool::configure_reward_pool(
+            &env,
+            admin,
+            payout,
+            season_start,
+            season_end,
+            parcel_cap,
+            base_reward,
+        )
+    }
+
+    /// Add budget to the current reward season
+    pub fn fund_reward_pool(env: Env, funder: Address, amount: i128) -> Result<(), ContractError> {
+        funder.require_auth();
+        reward_pool::fund_reward_pool(&env, funder, amount)
+    }
+
+    /// Withdraw unspent rewards after the season ends (admin only)
+    pub fn withdraw_unspent_rewards(
+        env: Env,
+        admin: Address,
+        recipient: Address,
+    ) -> Result<i128, ContractError> {
+        admin.require_auth();
+        reward_pool::withdraw_unspent_rewards(&env, admin, recipient)
+    }
+
+    /// Get the current reward pool
+    pub fn get_reward_pool(env: Env) -> Result<RewardPool, ContractError> {
+        reward_pool::get_reward_pool(&env)
+    }
+
+    /// Get rewards paid to a parcel in the current season
+    pub fn get_parcel_rewards(env: Env, parcel_id: BytesN<32>) -> i128 {
+        reward_pool::get_parcel_rewards(&env, parcel_id)
+    }
+
+    /// Register a parcel in a basin with its owner (admin only)
+    pub fn register_parcel(
+        env: Env,
+        admin: Address,
+        parcel_id: BytesN<32>,
+        basin_id: BytesN<32>,
+        owner: Address,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        allocations::register_parcel(&env, admin, parcel_id, basin_id, owner)
+    }
+
+    /// Get a parcel's basin and owner
+    pub fn get_parcel(
+        env: Env,
+        parcel_id: BytesN<32>,
+    ) -> Result<ParcelRegistration, ContractError> {
+        allocations::get_parcel(&env, parcel_id)
+    }
+
+    /// Issue seasonal allocation units to a parcel (admin only)
+    pub fn issue_allocation(
+        env: Env,
+        admin: Address,
+        parcel_id: BytesN<32>,
+        season_start: u64,
+        season_end: u64,
+        units: i128,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        allocations::issue_allocation(&env, admin, parcel_id, season_start, season_end, units)
+    }
+
+    /// Get a parcel's seasonal allocation
+    pub fn get_allocation(
+        env: Env,
+        parcel_id: BytesN<32>,
+    ) -> Result<WaterAllocation, ContractError> {
+        allocations::get_allocation(&env, parcel_id)
+    }
+
+    /// Get the unused allocation a parcel currently holds, including leases
+    pub fn get_available_allocation(env: Env, parcel_id: BytesN<32>) -> i128 {
+        allocations::get_available_allocation(&env, parcel_id)
+    }
+
+    /// Sell unused allocation to another parcel in the same basin
+    pub fn transfer_allocation(
+        env: Env,
+        owner: Address,
+        from_parcel: BytesN<32>,
+        to_parcel: BytesN<32>,
+        units: i128,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+        allocations::transfer_allocation(&env, owner, from_parcel, to_parcel, units)
+    }
+
+    /// Lease unused allocation to another parcel in the same basin
+    pub fn lease_allocation(
+        env: Env,
+        owner: Address,
+        from_parcel: BytesN<32>,
+        to_parcel: BytesN<32>,
+        units: i128,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+        allocations::lease_allocation(&env, owner, from_parcel, to_parcel, units, expires_at)
+    }
+
+    /// Get the leases a parcel takes part in
+    pub fn get_parcel_leases(env: Env, parcel_id: BytesN<32>) -> Vec<AllocationLease> {
+        allocations::get_parcel_leases(&env, parcel_id)
+    }
+
     /// Get alert by ID
     pub fn get_alert(env: Env, alert_id: BytesN<32>) -> Result<Alert, ContractError> {
         alerts::get_alert(&env, alert_id)
@@ -209,3 +428,18 @@ impl WaterManagementContract {
         alerts::get_farmer_alerts(&env, farmer_id, include_resolved)
     }
 }
+
+/// Runs alert checks and automatic incentives for a newly recorded usage
+fn process_recorded_usage(env: &Env, usage_id: BytesN<32>) {
+    // Check for alerts - log errors but don't fail the main operation
+    if let Err(_e) = alerts::check_usage_and_alert(env, usage_id.clone()) {
+        // In production, you would log this error for monitoring
+        // For now, we continue as usage recording is the primary operation
+    }
+
+    // Process automatic incentive - log errors but don't fail the main operation
+    if let Err(_e) = incentives::process_automatic_incentive(env, usage_id) {
+        // In production, you would log this error for monitoring
+        // For now, we continue as usage recording is the primary operation
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{alerts, allocations, datatypes::*, error::ContractError, utils, water_usage};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol};
+
+/// Binds a meter or IoT oracle to a registered parcel (admin only)
+pub fn register_oracle(
+    env: &Env,
+    admin: Address,
+    oracle: Address,
+    parcel_id: BytesN<32>,
+) -> Result<(), ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+    allocations::get_parcel(env, parcel_id.clone())?;
+
+    let key = DataKey::ParcelOracle(parcel_id.clone(), oracle.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(ContractError::OracleAlreadyRegistered);
+    }
+    env.storage().persistent().set(&key, &true);
+
+    let count_key = DataKey::ParcelOracleCount(parcel_id.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    env.storage().persistent().set(&count_key, &(count + 1));
+
+    env.events().publish(
+        (Symbol::new(env, "oracle_registered"), admin),
+        (oracle, parcel_id),
+    );
+
+    Ok(())
+}
+
+/// Unbinds an oracle from a parcel (admin only)
+pub fn revoke_oracle(
+    env: &Env,
+    admin: Address,
+    oracle: Address,
+    parcel_id: BytesN<32>,
+) -> Result<(), ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+
+    let key = DataKey::ParcelOracle(parcel_id.clone(), oracle.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(ContractError::OracleNotRegistered);
+    }
+    env.storage().persistent().remove(&key);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::MeterState(parcel_id.clone(), oracle.clone()));
+
+    let count_key = DataKey::ParcelOracleCount(parcel_id.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(1);
+    env.storage()
+        .persistent()
+        .set(&count_key, &count.saturating_sub(1));
+
+    env.events().publish(
+        (Symbol::new(env, "oracle_revoked"), admin),
+        (oracle, parcel_id),
+    );
+
+    Ok(())
+}
+
+/// Checks whether an oracle is bound to a parcel
+pub fn is_parcel_oracle(env: &Env, parcel_id: &BytesN<32>, oracle: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::ParcelOracle(parcel_id.clone(), oracle.clone()))
+}
+
+/// Checks whether a parcel's usage must come from its oracles
+pub fn requires_oracle(env: &Env, parcel_id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .get::<DataKey, u32>(&DataKey::ParcelOracleCount(parcel_id.clone()))
+        .unwrap_or(0)
+        > 0
+}
+
+/// Gets the last cumulative reading an oracle submitted for a parcel
+pub fn get_meter_state(
+    env: &Env,
+    parcel_id: BytesN<32>,
+    oracle: Address,
+) -> Result<MeterState, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MeterState(parcel_id, oracle))
+        .ok_or(ContractError::MeterReadingNotFound)
+}
+
+/// Accepts a cumulative meter reading from a parcel's oracle.
+/// The delta since the previous reading is recorded as usage for the parcel owner.
+/// Meter resets and jumps above the daily maximum for each day since the previous reading
+/// are not recorded; they raise a sensor malfunction alert and become the new baseline.
+pub fn submit_reading(
+    env: &Env,
+    oracle: Address,
+    usage_id: BytesN<32>,
+    parcel_id: BytesN<32>,
+    meter_reading: i128,
+    data_hash: BytesN<32>,
+) -> Result<ReadingStatus, ContractError> {
+    if !is_parcel_oracle(env, &parcel_id, &oracle) {
+        return Err(ContractError::OracleNotRegistered);
+    }
+    if meter_reading < 0 {
+        return Err(ContractError::OracleDataInvalid);
+    }
+    let parcel = allocations::get_parcel(env, parcel_id.clone())?;
+
+    let state_key = DataKey::MeterState(parcel_id.clone(), oracle.clone());
+    let previous: Option<MeterState> = env.storage().persistent().get(&state_key);
+    let timestamp = env.ledger().timestamp();
+    env.storage().persistent().set(
+        &state_key,
+        &MeterState {
+            last_reading: meter_reading,
+            last_timestamp: timestamp,
+        },
+    );
+
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return Ok(ReadingStatus::Baseline),
+    };
+
+    let delta = meter_reading - previous.last_reading;
+    let status = if delta == 0 {
+        ReadingStatus::Unchanged
+    } else if delta < 0 {
+        flag_reading(env, &parcel, "Meter reading went backwards")?;
+        ReadingStatus::Flagged
+    } else if delta > utils::max_metered_volume(timestamp - previous.last_timestamp) {
+        flag_reading(env, &parcel, "Implausible meter reading jump")?;
+        ReadingStatus::Flagged
+    } else {
+        water_usage::record_usage(
+            env,
+            usage_id,
+            parcel.owner.clone(),
+            parcel_id.clone(),
+            delta,
+            data_hash,
+            true,
+        )?;
+        ReadingStatus::Recorded
+    };
+
+    env.events().publish(
+        (Symbol::new(env, "meter_reading_submitted"), oracle),
+        (parcel_id, meter_reading, delta, status.clone()),
+    );
+
+    Ok(status)
+}
+
+/// Raises a sensor malfunction alert for the parcel owner
+fn flag_reading(
+    env: &Env,
+    parcel: &ParcelRegistration,
+    message: &str,
+) -> Result<(), ContractError> {
+    let alert_id =
+        alerts::generate_alert_id(env, &parcel.owner, &parcel.parcel_id, "sensor_malfunction");
+
+    match alerts::generate_alert(
+        env,
+        alert_id,
+        parcel.owner.clone(),
+        parcel.parcel_id.clone(),
+        AlertType::SensorMalfunction,
+        String::from_str(env, message),
+    ) {
+        Ok(()) | Err(ContractError::AlertAlreadyExists) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{allocations, datatypes::*, error::ContractError, utils};
+use soroban_sdk::{token, Address, BytesN, Env, IntoVal, Symbol};
+
+/// Base incentive used when no reward pool is configured
+pub const DEFAULT_BASE_REWARD: i128 = 100;
+
+/// Opens a reward season (admin only)
+/// Unspent budget carries over when the payout channel stays the same
+pub fn configure_reward_pool(
+    env: &Env,
+    admin: Address,
+    payout: RewardPayout,
+    season_start: u64,
+    season_end: u64,
+    parcel_cap: i128,
+    base_reward: i128,
+) -> Result<(), ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+
+    if season_start >= season_end || parcel_cap <= 0 || base_reward <= 0 {
+        return Err(ContractError::InvalidRewardPool);
+    }
+
+    let previous = get_reward_pool(env).ok();
+    let (season, carried_over) = match &previous {
+        Some(pool) => {
+            if env.ledger().timestamp() < pool.season_end {
+                return Err(ContractError::RewardSeasonActive);
+            }
+
+            let unspent = pool.budget - pool.distributed;
+            if unspent > 0 && pool.payout != payout {
+                // Unspent funds must be withdrawn before switching payout channel
+                return Err(ContractError::InvalidRewardPool);
+            }
+            (pool.season + 1, unspent.max(0))
+        }
+        None => (1, 0),
+    };
+
+    let pool = RewardPool {
+        payout,
+        season,
+        season_start,
+        season_end,
+        budget: carried_over,
+        distributed: 0,
+        parcel_cap,
+        base_reward,
+    };
+    env.storage().instance().set(&DataKey::RewardPool, &pool);
+
+    env.events().publish(
+        (Symbol::new(env, "reward_pool_configured"), admin),
+        (season, season_start, season_end, parcel_cap, base_reward),
+    );
+
+    Ok(())
+}
+
+/// Adds budget to the current season
+/// Token pools pull the funds into the contract; loyalty point pools are topped up by the admin
+pub fn fund_reward_pool(env: &Env, funder: Address, amount: i128) -> Result<(), ContractError> {
+    if amount <= 0 {
+        return Err(ContractError::InvalidRewardAmount);
+    }
+
+    let mut pool = get_reward_pool(env)?;
+    match &pool.payout {
+        RewardPayout::Token(reward_token) => {
+            token::Client::new(env, reward_token).transfer(
+                &funder,
+                &env.current_contract_address(),
+                &amount,
+            );
+        }
+        RewardPayout::LoyaltyProgram(_, _) => utils::require_admin_auth(env, &funder)?,
+    }
+
+    pool.budget += amount;
+    env.storage().instance().set(&DataKey::RewardPool, &pool);
+
+    env.events().publish(
+        (Symbol::new(env, "reward_pool_funded"), funder),
+        (pool.season, amount, pool.budget),
+    );
+
+    Ok(())
+}
+
+/// Returns unspent budget once the season is over (admin only)
+pub fn withdraw_unspent_rewards(
+    env: &Env,
+    admin: Address,
+    recipient: Address,
+) -> Result<i128, ContractError> {
+    utils::require_admin_auth(env, &admin)?;
+
+    let mut pool = get_reward_pool(env)?;
+    if env.ledger().timestamp() < pool.season_end {
+        return Err(ContractError::RewardSeasonActive);
+    }
+
+    let unspent = pool.budget - pool.distributed;
+    if unspent <= 0 {
+        return Ok(0);
+    }
+
+    if let RewardPayout::Token(reward_token) = &pool.payout {
+        token::Client::new(env, reward_token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &unspent,
+        );
+    }
+
+    pool.budget = pool.distributed;
+    env.storage().instance().set(&DataKey::RewardPool, &pool);
+
+    env.events().publish(
+        (Symbol::new(env, "reward_pool_withdrawn"), admin),
+        (pool.season, recipient, unspent),
+    );
+
+    Ok(unspent)
+}
+
+/// Gets the current reward pool
+pub fn get_reward_pool(env: &Env) -> Result<RewardPool, ContractError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::RewardPool)
+        .ok_or(ContractError::RewardPoolNotConfigured)
+}
+
+/// Gets the base incentive set by the admin for the current season
+pub fn get_base_reward(env: &Env) -> i128 {
+    match get_reward_pool(env) {
+        Ok(pool) => pool.base_reward,
+        Err(_) => DEFAULT_BASE_REWARD,
+    }
+}
+
+/// Gets the rewards already paid to a parcel in the current season
+pub fn get_parcel_rewards(env: &Env, parcel_id: BytesN<32>) -> i128 {
+    match get_reward_pool(env) {
+        Ok(pool) => env
+            .storage()
+            .persistent()
+            .get(&DataKey::ParcelRewards(pool.season, parcel_id))
+            .unwrap_or(0),
+        Err(_) => 0,
+    }
+}
+
+/// Pays an incentive out of the pool, limited by the remaining budget and the parcel cap.
+/// Only the registered owner of the parcel can be paid.
+/// Returns the amount actually paid, or the full reward when no pool is configured.
+pub fn pay_reward(
+    env: &Env,
+    usage: &WaterUsage,
+    reward_amount: i128,
+) -> Result<i128, ContractError> {
+    let farmer_id = &usage.farmer_id;
+    let parcel_id = &usage.parcel_id;
+    let mut pool = match get_reward_pool(env) {
+        Ok(pool) => pool,
+        // Without a pool incentives are only recorded
+        Err(_) => return Ok(reward_amount),
+    };
+
+    if allocations::get_parcel(env, parcel_id.clone())?.owner != *farmer_id {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let now = env.ledger().timestamp();
+    if now < pool.season_start || now >= pool.season_end {
+        return Err(ContractError::RewardSeasonInactive);
+    }
+
+    let parcel_key = DataKey::ParcelRewards(pool.season, parcel_id.clone());
+    let parcel_paid: i128 = env.storage().persistent().get(&parcel_key).unwrap_or(0);
+    if parcel_paid >= pool.parcel_cap {
+        return Err(ContractError::ParcelRewardCapReached);
+    }
+
+    let remaining_budget = pool.budget - pool.distributed;
+    if remaining_budget <= 0 {
+        return Err(ContractError::RewardBudgetExhausted);
+    }
+
+    let mut amount = reward_amount
+        .min(remaining_budget)
+        .min(pool.parcel_cap - parcel_paid);
+
+    match &pool.payout {
+        RewardPayout::Token(reward_token) => {
+            token::Client::new(env, reward_token).transfer(
+                &env.current_contract_address(),
+                farmer_id,
+                &amount,
+            );
+        }
+        RewardPayout::LoyaltyProgram(loyalty_contract, program_id) => {
+            // Settled under this contract's earning rule, once per usage record.
+            // The budget is charged the points actually awarded, tier bonuses included.
+            let result = env.try_invoke_contract::<u64, soroban_sdk::Error>(
+                loyalty_contract,
+                &Symbol::new(env, "on_settlement"),
+                (
+                    env.current_contract_address(),
+                    program_id.clone(),
+                    farmer_id.clone(),
+                    amount,
+                    usage.usage_id.clone(),
+                )
+                    .into_val(env),
+            );
+            amount = match result {
+                Ok(Ok(points)) => points as i128,
+                _ => return Err(ContractError::RewardPayoutFailed),
+            };
+        }
+    }
+
+    pool.distributed += amount;
+    env.storage().instance().set(&DataKey::RewardPool, &pool);
+    env.storage()
+        .persistent()
+        .set(&parcel_key, &(parcel_paid + amount));
+
+    env.events().publish(
+        (Symbol::new(env, "reward_paid"), farmer_id.clone()),
+        (parcel_id.clone(), pool.season, amount),
+    );
+
+    Ok(amount)
+}
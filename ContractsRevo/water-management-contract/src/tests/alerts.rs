@@ -286,6 +286,7 @@ fn test_threshold_exceeded_alert_trigger() {
     client.initialize(&admin);
 
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Set threshold
@@ -314,6 +315,7 @@ fn test_excessive_single_usage_alert() {
     client.initialize(&admin);
 
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Set threshold
@@ -346,6 +348,7 @@ fn test_weekly_threshold_exceeded_alert() {
     client.initialize(&admin);
 
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Set threshold
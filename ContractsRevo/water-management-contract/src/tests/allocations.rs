@@ -0,0 +1,301 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, BytesN, Env,
+};
+
+use crate::{AlertType, ContractError, WaterManagementContractClient};
+
+use super::utils::*;
+
+const SEASON_END: u64 = 120 * 86400;
+const SEASON_UNITS: i128 = 10_000;
+
+struct Basin {
+    seller: Address,
+    buyer: Address,
+    seller_parcel: BytesN<32>,
+    buyer_parcel: BytesN<32>,
+}
+
+fn create_basin_id(env: &Env, suffix: u8) -> BytesN<32> {
+    let mut bytes = [4u8; 32];
+    bytes[31] = suffix;
+    BytesN::from_array(env, &bytes)
+}
+
+fn setup_basin(env: &Env, client: &WaterManagementContractClient, admin: &Address) -> Basin {
+    let basin = Basin {
+        seller: Address::generate(env),
+        buyer: Address::generate(env),
+        seller_parcel: create_test_parcel_id(env, 1),
+        buyer_parcel: create_test_parcel_id(env, 2),
+    };
+
+    client.initialize(admin);
+    client.register_parcel(
+        admin,
+        &basin.seller_parcel,
+        &create_basin_id(env, 1),
+        &basin.seller,
+    );
+    client.register_parcel(
+        admin,
+        &basin.buyer_parcel,
+        &create_basin_id(env, 1),
+        &basin.buyer,
+    );
+    client.issue_allocation(admin, &basin.seller_parcel, &0, &SEASON_END, &SEASON_UNITS);
+
+    basin
+}
+
+#[test]
+fn test_issue_allocation() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+
+    let allocation = client.get_allocation(&basin.seller_parcel);
+    assert_eq!(allocation.issued, SEASON_UNITS);
+    assert_eq!(allocation.held, SEASON_UNITS);
+    assert_eq!(allocation.basin_id, create_basin_id(&env, 1));
+
+    // Top-ups must target the same season
+    client.issue_allocation(&admin, &basin.seller_parcel, &0, &SEASON_END, &500);
+    assert_eq!(
+        client.get_available_allocation(&basin.seller_parcel),
+        SEASON_UNITS + 500
+    );
+    assert_eq!(
+        client.try_issue_allocation(&admin, &basin.seller_parcel, &0, &(SEASON_END * 2), &500),
+        Err(Ok(ContractError::AllocationSeasonMismatch))
+    );
+
+    // Unregistered parcels cannot receive allocations
+    assert_eq!(
+        client.try_issue_allocation(
+            &admin,
+            &create_test_parcel_id(&env, 9),
+            &0,
+            &SEASON_END,
+            &500
+        ),
+        Err(Ok(ContractError::ParcelNotFound))
+    );
+}
+
+#[test]
+fn test_usage_draws_down_allocation() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+
+    client.record_usage(
+        &create_test_usage_id(&env, 1),
+        &basin.seller,
+        &basin.seller_parcel,
+        &4000i128,
+        &create_test_data_hash(&env, 1),
+    );
+
+    assert_eq!(client.get_allocation(&basin.seller_parcel).used, 4000);
+    assert_eq!(client.get_available_allocation(&basin.seller_parcel), 6000);
+}
+
+#[test]
+fn test_transfer_unused_allocation() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+
+    client.record_usage(
+        &create_test_usage_id(&env, 1),
+        &basin.seller,
+        &basin.seller_parcel,
+        &4000i128,
+        &create_test_data_hash(&env, 1),
+    );
+
+    // Only unused units can be sold
+    assert_eq!(
+        client.try_transfer_allocation(
+            &basin.seller,
+            &basin.seller_parcel,
+            &basin.buyer_parcel,
+            &7000
+        ),
+        Err(Ok(ContractError::InsufficientAllocation))
+    );
+
+    client.transfer_allocation(
+        &basin.seller,
+        &basin.seller_parcel,
+        &basin.buyer_parcel,
+        &6000,
+    );
+
+    assert_eq!(client.get_available_allocation(&basin.seller_parcel), 0);
+    let bought = client.get_allocation(&basin.buyer_parcel);
+    assert_eq!(bought.held, 6000);
+    assert_eq!(bought.issued, 0);
+    assert_eq!(bought.season_end, SEASON_END);
+}
+
+#[test]
+fn test_transfer_requires_owner_and_same_basin() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_transfer_allocation(
+            &basin.buyer,
+            &basin.seller_parcel,
+            &basin.buyer_parcel,
+            &100
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    let distant_parcel = create_test_parcel_id(&env, 3);
+    client.register_parcel(
+        &admin,
+        &distant_parcel,
+        &create_basin_id(&env, 2),
+        &basin.buyer,
+    );
+    assert_eq!(
+        client.try_transfer_allocation(&basin.seller, &basin.seller_parcel, &distant_parcel, &100),
+        Err(Ok(ContractError::BasinMismatch))
+    );
+}
+
+#[test]
+fn test_lease_returns_after_expiry() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+    let lease_end = 30 * 86400;
+
+    client.lease_allocation(
+        &basin.seller,
+        &basin.seller_parcel,
+        &basin.buyer_parcel,
+        &3000,
+        &lease_end,
+    );
+
+    assert_eq!(client.get_available_allocation(&basin.seller_parcel), 7000);
+    assert_eq!(client.get_available_allocation(&basin.buyer_parcel), 3000);
+    assert_eq!(client.get_parcel_leases(&basin.buyer_parcel).len(), 1);
+
+    env.ledger().with_mut(|li| li.timestamp = lease_end);
+    assert_eq!(
+        client.get_available_allocation(&basin.seller_parcel),
+        SEASON_UNITS
+    );
+    assert_eq!(client.get_available_allocation(&basin.buyer_parcel), 0);
+
+    // Leases cannot outlive the season
+    assert_eq!(
+        client.try_lease_allocation(
+            &basin.seller,
+            &basin.seller_parcel,
+            &basin.buyer_parcel,
+            &1000,
+            &(SEASON_END + 1),
+        ),
+        Err(Ok(ContractError::InvalidAllocation))
+    );
+}
+
+#[test]
+fn test_lessee_cannot_sell_leased_units() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+    let third_parcel = create_test_parcel_id(&env, 3);
+    client.register_parcel(
+        &admin,
+        &third_parcel,
+        &create_basin_id(&env, 1),
+        &Address::generate(&env),
+    );
+    client.issue_allocation(&admin, &basin.buyer_parcel, &0, &SEASON_END, &500);
+
+    client.lease_allocation(
+        &basin.seller,
+        &basin.seller_parcel,
+        &basin.buyer_parcel,
+        &3000,
+        &(30 * 86400),
+    );
+    assert_eq!(client.get_available_allocation(&basin.buyer_parcel), 3500);
+
+    // Only the lessee's own 500 units can be sold or leased on
+    assert_eq!(
+        client.try_transfer_allocation(&basin.buyer, &basin.buyer_parcel, &third_parcel, &1000),
+        Err(Ok(ContractError::InsufficientAllocation))
+    );
+    assert_eq!(
+        client.try_lease_allocation(
+            &basin.buyer,
+            &basin.buyer_parcel,
+            &third_parcel,
+            &1000,
+            &(10 * 86400),
+        ),
+        Err(Ok(ContractError::InsufficientAllocation))
+    );
+    client.transfer_allocation(&basin.buyer, &basin.buyer_parcel, &third_parcel, &500);
+    assert_eq!(client.get_allocation(&basin.buyer_parcel).held, 0);
+
+    // Units leased out cannot be sold by the lessor either
+    assert_eq!(
+        client.try_transfer_allocation(&basin.seller, &basin.seller_parcel, &third_parcel, &8000),
+        Err(Ok(ContractError::InsufficientAllocation))
+    );
+}
+
+#[test]
+fn test_alert_when_allocation_exceeded() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let basin = setup_basin(&env, &client, &admin);
+
+    client.lease_allocation(
+        &basin.seller,
+        &basin.seller_parcel,
+        &basin.buyer_parcel,
+        &2000,
+        &SEASON_END,
+    );
+
+    // Within the leased allocation: no alert
+    client.record_usage(
+        &create_test_usage_id(&env, 1),
+        &basin.buyer,
+        &basin.buyer_parcel,
+        &1500i128,
+        &create_test_data_hash(&env, 1),
+    );
+    assert_eq!(client.get_farmer_alerts(&basin.buyer, &false).len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 86400);
+    client.record_usage(
+        &create_test_usage_id(&env, 2),
+        &basin.buyer,
+        &basin.buyer_parcel,
+        &1000i128,
+        &create_test_data_hash(&env, 2),
+    );
+
+    let alerts = client.get_farmer_alerts(&basin.buyer, &false);
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(
+        alerts.get(0).unwrap().alert_type,
+        AlertType::ThresholdExceeded
+    );
+}
@@ -0,0 +1,311 @@
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Ledger as _, vec, Address, BytesN, Env};
+
+use crate::{BillingPeriod, ContractError, PriceTier, WaterManagementContractClient};
+
+use super::utils::*;
+
+const DAY: u64 = 86400;
+const OVERAGE_PRICE: i128 = 200;
+
+fn record(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    farmer: &Address,
+    parcel_id: &BytesN<32>,
+    suffix: u8,
+    volume: i128,
+) {
+    client.record_usage(
+        &create_test_usage_id(env, suffix),
+        farmer,
+        parcel_id,
+        &volume,
+        &create_test_data_hash(env, suffix),
+    );
+}
+
+fn set_block_tariff(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+    parcel_id: &BytesN<32>,
+    period: BillingPeriod,
+) {
+    // 10 per kiloliter for the first 5,000 liters, 30 up to 20,000, 100 above
+    client.set_tariff(
+        admin,
+        parcel_id,
+        &period,
+        &vec![
+            env,
+            PriceTier {
+                up_to: 5000,
+                price: 10,
+            },
+            PriceTier {
+                up_to: 20000,
+                price: 30,
+            },
+            PriceTier {
+                up_to: 50000,
+                price: 100,
+            },
+        ],
+        &OVERAGE_PRICE,
+    );
+}
+
+#[test]
+fn test_usage_aggregates_roll_over() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+    record(&env, &client, &farmer, &parcel_id, 1, 1000);
+    record(&env, &client, &farmer, &parcel_id, 2, 2000);
+
+    let aggregate = client.get_usage_aggregate(&parcel_id);
+    assert_eq!(aggregate.daily_total, 3000);
+    assert_eq!(aggregate.weekly_total, 3000);
+    assert_eq!(aggregate.monthly_total, 3000);
+
+    // Next day: the daily total starts over, longer periods keep accumulating
+    env.ledger().with_mut(|li| li.timestamp = 11 * DAY);
+    assert_eq!(client.get_usage_aggregate(&parcel_id).daily_total, 0);
+    record(&env, &client, &farmer, &parcel_id, 3, 500);
+
+    let aggregate = client.get_usage_aggregate(&parcel_id);
+    assert_eq!(aggregate.daily_total, 500);
+    assert_eq!(aggregate.monthly_total, 3500);
+}
+
+#[test]
+fn test_past_period_totals_survive_rollover() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+    record(&env, &client, &farmer, &parcel_id, 1, 4000);
+    record(&env, &client, &farmer, &parcel_id, 2, 2000);
+
+    env.ledger().with_mut(|li| li.timestamp = 11 * DAY);
+    record(&env, &client, &farmer, &parcel_id, 3, 500);
+
+    // The previous day keeps its own total after the rollover
+    let previous = client.get_usage_aggregate_at(&parcel_id, &(10 * DAY + 60));
+    assert_eq!(previous.day_start, 10 * DAY);
+    assert_eq!(previous.daily_total, 6000);
+    assert_eq!(previous.monthly_total, 6500);
+    assert_eq!(client.get_usage_aggregate(&parcel_id).daily_total, 500);
+
+    // A late check of yesterday's reading is measured against yesterday's total
+    client.set_threshold(&admin, &parcel_id, &5000i128, &35000i128, &150000i128);
+    let usage_id = create_test_usage_id(&env, 2);
+    env.as_contract(&client.address, || {
+        crate::alerts::check_usage_and_alert(&env, usage_id).unwrap();
+    });
+    let alerts = client.get_farmer_alerts(&farmer, &false);
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(
+        alerts.get(0).unwrap().alert_type,
+        crate::AlertType::ThresholdExceeded
+    );
+}
+
+#[test]
+fn test_monthly_threshold_alert() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+    client.set_threshold(&admin, &parcel_id, &5000i128, &35000i128, &140000i128);
+
+    // Stay under the daily limit every day but run over the month
+    let mut suffix = 1u8;
+    for day in 0..29u64 {
+        env.ledger().with_mut(|li| li.timestamp = day * DAY);
+        record(&env, &client, &farmer, &parcel_id, suffix, 2450);
+        record(&env, &client, &farmer, &parcel_id, suffix + 1, 2450);
+        suffix += 2;
+
+        if day < 28 {
+            assert_eq!(client.get_farmer_alerts(&farmer, &false).len(), 0);
+        }
+    }
+
+    let alerts = client.get_farmer_alerts(&farmer, &false);
+    assert_eq!(alerts.len(), 1);
+}
+
+#[test]
+fn test_block_tariff_charges() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+    set_block_tariff(&env, &client, &admin, &parcel_id, BillingPeriod::Daily);
+
+    // 4,000 liters in the first block
+    record(&env, &client, &farmer, &parcel_id, 1, 4000);
+    assert_eq!(client.get_usage_charge(&create_test_usage_id(&env, 1)), 40);
+
+    // 1,000 liters at 10 and 5,000 liters at 30
+    record(&env, &client, &farmer, &parcel_id, 2, 6000);
+    assert_eq!(client.get_usage_charge(&create_test_usage_id(&env, 2)), 160);
+
+    // A new day starts back in the first block
+    env.ledger().with_mut(|li| li.timestamp = DAY);
+    record(&env, &client, &farmer, &parcel_id, 3, 2000);
+    assert_eq!(client.get_usage_charge(&create_test_usage_id(&env, 3)), 20);
+
+    let report = client.get_usage_report(&farmer, &Some(parcel_id.clone()), &0, &(2 * DAY));
+    assert_eq!(report.total_usage, 12000);
+    assert_eq!(report.water_bill, 220);
+
+    let first_day = client.get_usage_report(&farmer, &None, &0, &(DAY - 1));
+    assert_eq!(first_day.water_bill, 200);
+}
+
+#[test]
+fn test_usage_above_last_block_uses_top_price() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+    set_block_tariff(&env, &client, &admin, &parcel_id, BillingPeriod::Monthly);
+
+    record(&env, &client, &farmer, &parcel_id, 1, 50000);
+    record(&env, &client, &farmer, &parcel_id, 2, 10000);
+
+    assert_eq!(
+        client.get_usage_charge(&create_test_usage_id(&env, 1)),
+        50 + 450 + 3000
+    );
+    assert_eq!(
+        client.get_usage_charge(&create_test_usage_id(&env, 2)),
+        1000
+    );
+}
+
+#[test]
+fn test_usage_beyond_allocation_billed_as_overage() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+    set_block_tariff(&env, &client, &admin, &parcel_id, BillingPeriod::Daily);
+    client.issue_allocation(&admin, &parcel_id, &0, &(120 * DAY), &5000);
+
+    record(&env, &client, &farmer, &parcel_id, 1, 4000);
+    assert_eq!(client.get_usage_charge(&create_test_usage_id(&env, 1)), 40);
+
+    // 1,000 liters left in the allocation at the block price, 2,000 liters at the overage price
+    record(&env, &client, &farmer, &parcel_id, 2, 3000);
+    assert_eq!(
+        client.get_usage_charge(&create_test_usage_id(&env, 2)),
+        10 + 400
+    );
+
+    // The usage is recorded in full and the overage kept on the allocation
+    let allocation = client.get_allocation(&parcel_id);
+    assert_eq!(allocation.used, 7000);
+    assert_eq!(allocation.overage, 2000);
+
+    // Once the allocation is exhausted everything is overage
+    record(&env, &client, &farmer, &parcel_id, 3, 1000);
+    assert_eq!(client.get_usage_charge(&create_test_usage_id(&env, 3)), 200);
+    assert_eq!(client.get_allocation(&parcel_id).overage, 3000);
+}
+
+#[test]
+fn test_invalid_tariff() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+
+    assert_eq!(
+        client.try_set_tariff(
+            &admin,
+            &parcel_id,
+            &BillingPeriod::Daily,
+            &vec![&env],
+            &OVERAGE_PRICE
+        ),
+        Err(Ok(ContractError::InvalidTariff))
+    );
+    assert_eq!(
+        client.try_set_tariff(
+            &admin,
+            &parcel_id,
+            &BillingPeriod::Daily,
+            &vec![
+                &env,
+                PriceTier {
+                    up_to: 5000,
+                    price: 10
+                },
+                PriceTier {
+                    up_to: 5000,
+                    price: 20
+                },
+            ],
+            &OVERAGE_PRICE,
+        ),
+        Err(Ok(ContractError::InvalidTariff))
+    );
+    // The overage price may not undercut the top block
+    assert_eq!(
+        client.try_set_tariff(
+            &admin,
+            &parcel_id,
+            &BillingPeriod::Daily,
+            &vec![
+                &env,
+                PriceTier {
+                    up_to: 5000,
+                    price: 10
+                }
+            ],
+            &5,
+        ),
+        Err(Ok(ContractError::InvalidTariff))
+    );
+    assert_eq!(
+        client.try_set_tariff(
+            &farmer,
+            &parcel_id,
+            &BillingPeriod::Daily,
+            &vec![
+                &env,
+                PriceTier {
+                    up_to: 5000,
+                    price: 10
+                }
+            ],
+            &OVERAGE_PRICE,
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_get_tariff(&parcel_id),
+        Err(Ok(ContractError::TariffNotFound))
+    );
+
+    // Parcels without a tariff are not billed
+    record(&env, &client, &farmer, &parcel_id, 1, 1000);
+    assert_eq!(client.get_usage_charge(&create_test_usage_id(&env, 1)), 0);
+}
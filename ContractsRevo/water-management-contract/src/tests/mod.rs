@@ -1,5 +1,9 @@
 // Test modules for water management contract
 pub mod alerts;
+pub mod allocations;
+pub mod billing;
 pub mod incentives;
+pub mod oracles;
+pub mod reward_pool;
 pub mod utils;
 pub mod water_usage;
@@ -0,0 +1,245 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, BytesN, Env,
+};
+
+use crate::{AlertType, ContractError, ReadingStatus, WaterManagementContractClient};
+
+use super::utils::*;
+
+struct MeteredParcel {
+    owner: Address,
+    oracle: Address,
+    parcel_id: BytesN<32>,
+}
+
+fn setup_metered_parcel(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+) -> MeteredParcel {
+    let parcel = MeteredParcel {
+        owner: Address::generate(env),
+        oracle: Address::generate(env),
+        parcel_id: create_test_parcel_id(env, 1),
+    };
+
+    client.initialize(admin);
+    client.register_parcel(
+        admin,
+        &parcel.parcel_id,
+        &BytesN::from_array(env, &[4u8; 32]),
+        &parcel.owner,
+    );
+    client.register_oracle(admin, &parcel.oracle, &parcel.parcel_id);
+
+    parcel
+}
+
+fn submit(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    parcel: &MeteredParcel,
+    suffix: u8,
+    meter_reading: i128,
+) -> ReadingStatus {
+    client.submit_reading(
+        &parcel.oracle,
+        &create_test_usage_id(env, suffix),
+        &parcel.parcel_id,
+        &meter_reading,
+        &create_test_data_hash(env, suffix),
+    )
+}
+
+#[test]
+fn test_oracle_reading_records_delta() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+
+    assert_eq!(
+        submit(&env, &client, &parcel, 1, 50_000),
+        ReadingStatus::Baseline
+    );
+    assert!(client
+        .try_get_usage(&create_test_usage_id(&env, 1))
+        .is_err());
+
+    assert_eq!(
+        submit(&env, &client, &parcel, 2, 53_000),
+        ReadingStatus::Recorded
+    );
+    let usage = client.get_usage(&create_test_usage_id(&env, 2));
+    assert_eq!(usage.volume, 3000);
+    assert_eq!(usage.farmer_id, parcel.owner);
+
+    assert_eq!(
+        submit(&env, &client, &parcel, 3, 53_000),
+        ReadingStatus::Unchanged
+    );
+    assert_eq!(
+        client
+            .get_meter_state(&parcel.parcel_id, &parcel.oracle)
+            .last_reading,
+        53_000
+    );
+}
+
+#[test]
+fn test_meter_reset_raises_sensor_malfunction() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+
+    submit(&env, &client, &parcel, 1, 50_000);
+    assert_eq!(
+        submit(&env, &client, &parcel, 2, 100),
+        ReadingStatus::Flagged
+    );
+
+    assert!(client
+        .try_get_usage(&create_test_usage_id(&env, 2))
+        .is_err());
+    let alerts = client.get_farmer_alerts(&parcel.owner, &false);
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(
+        alerts.get(0).unwrap().alert_type,
+        AlertType::SensorMalfunction
+    );
+
+    // The reset reading becomes the new baseline
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    assert_eq!(
+        submit(&env, &client, &parcel, 3, 600),
+        ReadingStatus::Recorded
+    );
+    assert_eq!(client.get_usage(&create_test_usage_id(&env, 3)).volume, 500);
+}
+
+#[test]
+fn test_implausible_jump_is_flagged() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+
+    submit(&env, &client, &parcel, 1, 0);
+    assert_eq!(
+        submit(&env, &client, &parcel, 2, 500_000),
+        ReadingStatus::Flagged
+    );
+
+    assert!(client
+        .try_get_usage(&create_test_usage_id(&env, 2))
+        .is_err());
+    let alerts = client.get_farmer_alerts(&parcel.owner, &false);
+    assert_eq!(
+        alerts.get(0).unwrap().alert_type,
+        AlertType::SensorMalfunction
+    );
+}
+
+#[test]
+fn test_usage_over_meter_downtime_is_recorded() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+
+    submit(&env, &client, &parcel, 1, 0);
+
+    // Three days without readings allow up to three days of usage
+    env.ledger().with_mut(|li| li.timestamp = 3 * 86400);
+    assert_eq!(
+        submit(&env, &client, &parcel, 2, 250_000),
+        ReadingStatus::Recorded
+    );
+    assert_eq!(
+        client.get_usage(&create_test_usage_id(&env, 2)).volume,
+        250_000
+    );
+
+    // The limit restarts from the latest reading
+    env.ledger().with_mut(|li| li.timestamp = 3 * 86400 + 3600);
+    assert_eq!(
+        submit(&env, &client, &parcel, 3, 400_000),
+        ReadingStatus::Flagged
+    );
+}
+
+#[test]
+fn test_unregistered_oracle_rejected() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+    let rogue = Address::generate(&env);
+
+    assert_eq!(
+        client.try_submit_reading(
+            &rogue,
+            &create_test_usage_id(&env, 1),
+            &parcel.parcel_id,
+            &1000,
+            &create_test_data_hash(&env, 1),
+        ),
+        Err(Ok(ContractError::OracleNotRegistered))
+    );
+
+    // Revoked oracles can no longer submit
+    client.revoke_oracle(&admin, &parcel.oracle, &parcel.parcel_id);
+    assert_eq!(
+        client.try_submit_reading(
+            &parcel.oracle,
+            &create_test_usage_id(&env, 1),
+            &parcel.parcel_id,
+            &1000,
+            &create_test_data_hash(&env, 1),
+        ),
+        Err(Ok(ContractError::OracleNotRegistered))
+    );
+}
+
+#[test]
+fn test_self_reporting_blocked_on_metered_parcel() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_record_usage(
+            &create_test_usage_id(&env, 1),
+            &parcel.owner,
+            &parcel.parcel_id,
+            &1000i128,
+            &create_test_data_hash(&env, 1),
+        ),
+        Err(Ok(ContractError::OracleReadingRequired))
+    );
+
+    // Once all oracles are revoked, the parcel accepts self-reports again
+    client.revoke_oracle(&admin, &parcel.oracle, &parcel.parcel_id);
+    client.record_usage(
+        &create_test_usage_id(&env, 1),
+        &parcel.owner,
+        &parcel.parcel_id,
+        &1000i128,
+        &create_test_data_hash(&env, 1),
+    );
+}
+
+#[test]
+fn test_register_oracle_requires_parcel() {
+    let (env, client, admin, _) = setup_test_environment();
+    env.mock_all_auths();
+    let parcel = setup_metered_parcel(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_register_oracle(&admin, &parcel.oracle, &parcel.parcel_id),
+        Err(Ok(ContractError::OracleAlreadyRegistered))
+    );
+    assert_eq!(
+        client.try_register_oracle(&admin, &parcel.oracle, &create_test_parcel_id(&env, 9)),
+        Err(Ok(ContractError::ParcelNotFound))
+    );
+}
@@ -0,0 +1,423 @@
+#![cfg(test)]
+
+use loyalty_token_contract::{
+    EarningRule, LoyaltyContract, LoyaltyContractClient, ProgramSettings, Tier, TierRule,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env,
+};
+
+use crate::{ContractError, RewardPayout, WaterManagementContractClient};
+
+use super::utils::*;
+
+const SEASON_END: u64 = 90 * 86400;
+const PARCEL_CAP: i128 = 300;
+const BASE_REWARD: i128 = 100;
+
+fn setup_token_pool(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+    budget: i128,
+) -> TokenClient<'static> {
+    let reward_token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &reward_token.address()).mint(admin, &budget);
+
+    client.configure_reward_pool(
+        admin,
+        &RewardPayout::Token(reward_token.address()),
+        &0,
+        &SEASON_END,
+        &PARCEL_CAP,
+        &BASE_REWARD,
+    );
+    client.fund_reward_pool(admin, &budget);
+
+    TokenClient::new(env, &reward_token.address())
+}
+
+/// Meters the farmer's parcel on first use, then submits an efficient reading for it
+fn record_efficient_usage(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+    farmer: &Address,
+    oracle: &Address,
+    usage_suffix: u8,
+    parcel_suffix: u8,
+) -> BytesN<32> {
+    let usage_id = create_test_usage_id(env, usage_suffix);
+    let parcel_id = create_test_parcel_id(env, parcel_suffix);
+    if client.try_get_parcel(&parcel_id).is_err() {
+        register_test_parcel(env, client, admin, &parcel_id, farmer);
+        meter_test_parcel(env, client, admin, &parcel_id, oracle);
+        client.set_threshold(admin, &parcel_id, &5000i128, &35000i128, &150000i128);
+    }
+    submit_metered_usage(
+        client,
+        oracle,
+        &usage_id,
+        &parcel_id,
+        1000,
+        &create_test_data_hash(env, usage_suffix),
+    );
+    usage_id
+}
+
+#[test]
+fn test_incentive_paid_in_reward_token() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = setup_token_pool(&env, &client, &admin, 1000);
+
+    let usage_id = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+
+    // Automatic processing pays 2x the default base reward of 100
+    let incentive = client.get_incentive(&usage_id);
+    assert_eq!(incentive.reward_amount, 200);
+    assert_eq!(reward_token.balance(&farmer), 200);
+    assert_eq!(reward_token.balance(&client.address), 800);
+
+    let pool = client.get_reward_pool();
+    assert_eq!(pool.distributed, 200);
+    assert_eq!(
+        client.get_parcel_rewards(&create_test_parcel_id(&env, 1)),
+        200
+    );
+}
+
+#[test]
+fn test_base_reward_set_by_admin() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &reward_token.address()).mint(&admin, &1000);
+
+    client.configure_reward_pool(
+        &admin,
+        &RewardPayout::Token(reward_token.address()),
+        &0,
+        &SEASON_END,
+        &PARCEL_CAP,
+        &40,
+    );
+    client.fund_reward_pool(&admin, &1000);
+
+    // 2x the configured base reward, whatever the farmer would like
+    let usage_id = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+    assert_eq!(client.get_incentive(&usage_id).reward_amount, 80);
+    assert_eq!(
+        TokenClient::new(&env, &reward_token.address()).balance(&farmer),
+        80
+    );
+}
+
+#[test]
+fn test_self_reported_usage_not_rewarded() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = setup_token_pool(&env, &client, &admin, 1000);
+
+    let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
+    client.set_threshold(&admin, &parcel_id, &5000i128, &35000i128, &150000i128);
+
+    // Efficient, but only the farmer's word for it
+    let usage_id = create_test_usage_id(&env, 1);
+    client.record_usage(
+        &usage_id,
+        &farmer,
+        &parcel_id,
+        &1000i128,
+        &create_test_data_hash(&env, 1),
+    );
+    assert_eq!(
+        client.try_issue_incentive(&usage_id),
+        Err(Ok(ContractError::OracleReadingRequired))
+    );
+    assert_eq!(reward_token.balance(&farmer), 0);
+    assert_eq!(client.get_reward_pool().distributed, 0);
+}
+
+#[test]
+fn test_parcel_cap_limits_rewards() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = setup_token_pool(&env, &client, &admin, 1000);
+
+    record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+    let second = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 2, 1);
+
+    // Second reward is trimmed to what is left under the parcel cap
+    assert_eq!(client.get_incentive(&second).reward_amount, 100);
+    assert_eq!(reward_token.balance(&farmer), PARCEL_CAP);
+
+    let third = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 3, 1);
+    assert_eq!(
+        client.try_issue_incentive(&third),
+        Err(Ok(ContractError::ParcelRewardCapReached))
+    );
+
+    // Other parcels still earn rewards
+    record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 4, 2);
+    assert_eq!(reward_token.balance(&farmer), PARCEL_CAP + 200);
+}
+
+#[test]
+fn test_budget_exhausted() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = setup_token_pool(&env, &client, &admin, 250);
+
+    record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+    let second = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 2, 2);
+    assert_eq!(client.get_incentive(&second).reward_amount, 50);
+
+    let third = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 3, 3);
+    assert_eq!(
+        client.try_issue_incentive(&third),
+        Err(Ok(ContractError::RewardBudgetExhausted))
+    );
+    assert_eq!(reward_token.balance(&farmer), 250);
+    assert_eq!(reward_token.balance(&client.address), 0);
+}
+
+fn setup_loyalty_pool(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+) -> (LoyaltyContractClient<'static>, BytesN<32>) {
+    let loyalty = LoyaltyContractClient::new(env, &env.register(LoyaltyContract, ()));
+    let program_id = BytesN::from_array(env, &[7u8; 32]);
+    let owner = Address::generate(env);
+    loyalty.create_loyalty_program(&owner, &program_id, &1, &vec![env]);
+
+    // One point per reward unit settled by this contract
+    loyalty.set_earning_rule(
+        &owner,
+        &program_id,
+        &client.address,
+        &EarningRule {
+            points_per_unit: 1,
+            unit_amount: 1,
+            bonus_points: 0,
+            max_points: 0,
+        },
+    );
+    loyalty.set_program_settings(
+        &owner,
+        &program_id,
+        &ProgramSettings {
+            point_validity: 0,
+            transfers_enabled: false,
+            tiers: vec![
+                env,
+                TierRule {
+                    tier: Tier::Silver,
+                    min_points: 150,
+                    multiplier: 15_000,
+                },
+            ],
+            voucher_validity: 0,
+            refund_expired_vouchers: true,
+        },
+    );
+
+    client.configure_reward_pool(
+        admin,
+        &RewardPayout::LoyaltyProgram(loyalty.address.clone(), program_id.clone()),
+        &0,
+        &SEASON_END,
+        &PARCEL_CAP,
+        &BASE_REWARD,
+    );
+
+    (loyalty, program_id)
+}
+
+#[test]
+fn test_incentive_paid_as_loyalty_points() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let (loyalty, program_id) = setup_loyalty_pool(&env, &client, &admin);
+
+    // Only the admin can top up a points budget
+    assert_eq!(
+        client.try_fund_reward_pool(&farmer, &500),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.fund_reward_pool(&admin, &500);
+
+    let usage_id = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+    assert_eq!(loyalty.get_points_balance(&program_id, &farmer), 200);
+    assert_eq!(client.get_incentive(&usage_id).reward_amount, 200);
+    assert_eq!(client.get_reward_pool().distributed, 200);
+}
+
+#[test]
+fn test_loyalty_budget_charged_points_awarded() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let (loyalty, program_id) = setup_loyalty_pool(&env, &client, &admin);
+    client.fund_reward_pool(&admin, &1000);
+
+    record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+    assert_eq!(loyalty.get_member_tier(&program_id, &farmer), Tier::Silver);
+
+    // Silver earns 1.5x, and the pool pays for the points the member actually got
+    let second = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 2, 2);
+    assert_eq!(client.get_incentive(&second).reward_amount, 300);
+    assert_eq!(loyalty.get_points_balance(&program_id, &farmer), 500);
+    assert_eq!(client.get_reward_pool().distributed, 500);
+    assert_eq!(
+        client.get_parcel_rewards(&create_test_parcel_id(&env, 2)),
+        300
+    );
+}
+
+#[test]
+fn test_loyalty_payout_needs_earning_rule() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let (loyalty, program_id) = setup_loyalty_pool(&env, &client, &admin);
+    client.fund_reward_pool(&admin, &1000);
+
+    let owner = loyalty.get_program_info(&program_id).owner;
+    loyalty.remove_earning_rule(&owner, &program_id, &client.address);
+
+    let usage_id = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+    assert_eq!(
+        client.try_issue_incentive(&usage_id),
+        Err(Ok(ContractError::RewardPayoutFailed))
+    );
+    assert_eq!(loyalty.get_points_balance(&program_id, &farmer), 0);
+    assert_eq!(client.get_reward_pool().distributed, 0);
+}
+
+#[test]
+fn test_no_rewards_outside_season() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = setup_token_pool(&env, &client, &admin, 1000);
+
+    env.ledger().with_mut(|li| li.timestamp = SEASON_END);
+    let usage_id = record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+
+    assert!(client.try_get_incentive(&usage_id).is_err());
+    assert_eq!(
+        client.try_issue_incentive(&usage_id),
+        Err(Ok(ContractError::RewardSeasonInactive))
+    );
+    assert_eq!(reward_token.balance(&farmer), 0);
+}
+
+#[test]
+fn test_new_season_and_withdrawal() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let reward_token = setup_token_pool(&env, &client, &admin, 1000);
+    let treasury = Address::generate(&env);
+
+    record_efficient_usage(&env, &client, &admin, &farmer, &oracle, 1, 1);
+
+    // Pool cannot be reconfigured or drained mid-season
+    assert_eq!(
+        client.try_withdraw_unspent_rewards(&admin, &treasury),
+        Err(Ok(ContractError::RewardSeasonActive))
+    );
+    assert_eq!(
+        client.try_configure_reward_pool(
+            &admin,
+            &RewardPayout::Token(reward_token.address.clone()),
+            &SEASON_END,
+            &(2 * SEASON_END),
+            &PARCEL_CAP,
+            &BASE_REWARD,
+        ),
+        Err(Ok(ContractError::RewardSeasonActive))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = SEASON_END);
+    client.configure_reward_pool(
+        &admin,
+        &RewardPayout::Token(reward_token.address.clone()),
+        &SEASON_END,
+        &(2 * SEASON_END),
+        &PARCEL_CAP,
+        &BASE_REWARD,
+    );
+
+    // Unspent budget carries over and the parcel cap resets
+    let pool = client.get_reward_pool();
+    assert_eq!(pool.season, 2);
+    assert_eq!(pool.budget, 800);
+    assert_eq!(
+        client.get_parcel_rewards(&create_test_parcel_id(&env, 1)),
+        0
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2 * SEASON_END);
+    assert_eq!(client.withdraw_unspent_rewards(&admin, &treasury), 800);
+    assert_eq!(reward_token.balance(&treasury), 800);
+}
+
+#[test]
+fn test_invalid_reward_pool() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let payout = RewardPayout::Token(Address::generate(&env));
+
+    assert_eq!(
+        client.try_configure_reward_pool(
+            &admin,
+            &payout,
+            &SEASON_END,
+            &0,
+            &PARCEL_CAP,
+            &BASE_REWARD
+        ),
+        Err(Ok(ContractError::InvalidRewardPool))
+    );
+    assert_eq!(
+        client.try_configure_reward_pool(&admin, &payout, &0, &SEASON_END, &PARCEL_CAP, &0),
+        Err(Ok(ContractError::InvalidRewardPool))
+    );
+    assert_eq!(
+        client.try_configure_reward_pool(
+            &farmer,
+            &payout,
+            &0,
+            &SEASON_END,
+            &PARCEL_CAP,
+            &BASE_REWARD
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_fund_reward_pool(&admin, &100),
+        Err(Ok(ContractError::RewardPoolNotConfigured))
+    );
+}
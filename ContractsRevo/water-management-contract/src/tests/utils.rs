@@ -23,6 +23,59 @@ pub fn setup_test_environment() -> (
     (env, client, admin, farmer)
 }
 
+/// Registers a parcel to its owner so the owner can report usage for it
+pub fn register_test_parcel(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+    parcel_id: &BytesN<32>,
+    owner: &Address,
+) {
+    client.register_parcel(
+        admin,
+        parcel_id,
+        &BytesN::from_array(env, &[4u8; 32]),
+        owner,
+    );
+}
+
+/// Binds an oracle to a registered parcel and sets its meter baseline to zero
+pub fn meter_test_parcel(
+    env: &Env,
+    client: &WaterManagementContractClient,
+    admin: &Address,
+    parcel_id: &BytesN<32>,
+    oracle: &Address,
+) {
+    client.register_oracle(admin, oracle, parcel_id);
+    client.submit_reading(
+        oracle,
+        &BytesN::from_array(env, &[5u8; 32]),
+        parcel_id,
+        &0,
+        &create_test_data_hash(env, 0),
+    );
+}
+
+/// Submits a reading that advances a metered parcel's meter by `volume`
+pub fn submit_metered_usage(
+    client: &WaterManagementContractClient,
+    oracle: &Address,
+    usage_id: &BytesN<32>,
+    parcel_id: &BytesN<32>,
+    volume: i128,
+    data_hash: &BytesN<32>,
+) {
+    let meter = client.get_meter_state(parcel_id, oracle);
+    client.submit_reading(
+        oracle,
+        usage_id,
+        parcel_id,
+        &(meter.last_reading + volume),
+        data_hash,
+    );
+}
+
 /// Creates a test usage ID with a specific suffix
 pub fn create_test_usage_id(env: &Env, suffix: u8) -> BytesN<32> {
     let mut bytes = [0u8; 32];
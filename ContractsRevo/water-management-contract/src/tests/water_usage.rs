@@ -2,7 +2,7 @@
 
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
 
-use crate::{WaterManagementContract, WaterManagementContractClient};
+use crate::{ContractError, WaterManagementContract, WaterManagementContractClient};
 
 use super::utils::*;
 
@@ -17,6 +17,7 @@ fn test_record_water_usage_success() {
 
     let usage_id = create_test_usage_id(&env, 1);
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
     let volume = 1000i128;
 
@@ -41,6 +42,7 @@ fn test_record_water_usage_duplicate_id() {
 
     let usage_id = create_test_usage_id(&env, 1);
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
     let volume = 1000i128;
 
@@ -62,6 +64,7 @@ fn test_record_water_usage_invalid_volume() {
 
     let usage_id = create_test_usage_id(&env, 1);
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Test negative volume
@@ -86,6 +89,7 @@ fn test_record_water_usage_invalid_data_hash() {
 
     let usage_id = create_test_usage_id(&env, 1);
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let empty_hash = BytesN::from_array(&env, &[0u8; 32]);
     let volume = 1000i128;
 
@@ -115,6 +119,35 @@ fn test_record_water_usage_invalid_identifiers() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_record_usage_requires_parcel_owner() {
+    let (env, client, admin, farmer) = setup_test_environment();
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+
+    let usage_id = create_test_usage_id(&env, 1);
+    let parcel_id = create_test_parcel_id(&env, 1);
+    let data_hash = create_test_data_hash(&env, 1);
+
+    // Unregistered parcels cannot be reported on
+    assert_eq!(
+        client.try_record_usage(&usage_id, &farmer, &parcel_id, &1000i128, &data_hash),
+        Err(Ok(ContractError::ParcelNotFound))
+    );
+
+    // Nor can parcels owned by someone else
+    let owner = Address::generate(&env);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &owner);
+    assert_eq!(
+        client.try_record_usage(&usage_id, &farmer, &parcel_id, &1000i128, &data_hash),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.record_usage(&usage_id, &owner, &parcel_id, &1000i128, &data_hash);
+    assert!(!client.get_usage(&usage_id).metered);
+}
+
 #[test]
 fn test_get_usage_not_found() {
     let (env, client, admin, _) = setup_test_environment();
@@ -136,6 +169,7 @@ fn test_get_farmer_usages() {
     client.initialize(&admin);
 
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Record multiple usages for the same farmer
@@ -163,6 +197,7 @@ fn test_get_parcel_usages() {
     client.initialize(&admin);
 
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Record multiple usages for the same parcel
@@ -191,6 +226,8 @@ fn test_usage_report_farmer_wide() {
 
     let parcel_id1 = create_test_parcel_id(&env, 1);
     let parcel_id2 = create_test_parcel_id(&env, 2);
+    register_test_parcel(&env, &client, &admin, &parcel_id1, &farmer);
+    register_test_parcel(&env, &client, &admin, &parcel_id2, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Record usages across multiple parcels
@@ -235,6 +272,8 @@ fn test_usage_report_parcel_specific() {
 
     let parcel_id1 = create_test_parcel_id(&env, 1);
     let parcel_id2 = create_test_parcel_id(&env, 2);
+    register_test_parcel(&env, &client, &admin, &parcel_id1, &farmer);
+    register_test_parcel(&env, &client, &admin, &parcel_id2, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Record usages across multiple parcels
@@ -309,6 +348,7 @@ fn test_high_volume_usage_recording() {
     client.initialize(&admin);
 
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
 
     // Test recording many usage records to test scalability
@@ -336,6 +376,7 @@ fn test_oracle_data_integration_simulation() {
 
     let usage_id = create_test_usage_id(&env, 1);
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let volume = 1000i128;
 
     // Simulate different types of sensor data hashes
@@ -366,6 +407,7 @@ fn test_usage_timestamp_validation() {
 
     let usage_id = create_test_usage_id(&env, 1);
     let parcel_id = create_test_parcel_id(&env, 1);
+    register_test_parcel(&env, &client, &admin, &parcel_id, &farmer);
     let data_hash = create_test_data_hash(&env, 1);
     let volume = 1000i128;
 
@@ -1,14 +1,17 @@
 use crate::{datatypes::*, error::ContractError};
 use soroban_sdk::{Address, BytesN, Env};
 
+/// Maximum daily usage per parcel: 100,000 liters (reasonable for large agricultural parcels)
+pub const MAX_DAILY_VOLUME: i128 = 100_000;
+
+pub const SECONDS_PER_DAY: u64 = 86400;
+
 /// Validates that a water volume is within acceptable limits
 pub fn validate_water_volume(volume: i128) -> Result<(), ContractError> {
     if volume <= 0 {
         return Err(ContractError::InvalidVolume);
     }
 
-    // Maximum daily usage per parcel: 100,000 liters (reasonable for large agricultural parcels)
-    const MAX_DAILY_VOLUME: i128 = 100_000;
     if volume > MAX_DAILY_VOLUME {
         return Err(ContractError::InvalidVolume);
     }
@@ -16,6 +19,12 @@ pub fn validate_water_volume(volume: i128) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Largest plausible metered volume over `elapsed` seconds: the daily maximum for each started day
+pub fn max_metered_volume(elapsed: u64) -> i128 {
+    let days = elapsed.div_ceil(SECONDS_PER_DAY).max(1);
+    MAX_DAILY_VOLUME.saturating_mul(days as i128)
+}
+
 /// Validates timestamp is not in the future and not too old
 pub fn validate_timestamp(env: &Env, timestamp: u64) -> Result<(), ContractError> {
     let current_time = env.ledger().timestamp();
@@ -115,7 +124,6 @@ pub fn require_admin_auth(env: &Env, caller: &Address) -> Result<(), ContractErr
 
 /// Gets the current day timestamp (start of day)
 pub fn get_day_start(timestamp: u64) -> u64 {
-    const SECONDS_PER_DAY: u64 = 86400;
     (timestamp / SECONDS_PER_DAY) * SECONDS_PER_DAY
 }
 
@@ -1,4 +1,4 @@
-use crate::{datatypes::*, error::ContractError, utils};
+use crate::{allocations, billing, datatypes::*, error::ContractError, utils};
 use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
 
 /// Records water usage data for a parcel or crop
@@ -9,11 +9,14 @@ pub fn record_usage(
     parcel_id: BytesN<32>,
     volume: i128,
     data_hash: BytesN<32>,
+    metered: bool,
 ) -> Result<(), ContractError> {
     // Validate inputs
     utils::validate_identifier(env, &usage_id)?;
     utils::validate_identifier(env, &parcel_id)?;
-    utils::validate_water_volume(volume)?;
+    if volume <= 0 {
+        return Err(ContractError::InvalidVolume);
+    }
     utils::validate_data_hash(env, &data_hash)?;
 
     // Check if usage record already exists
@@ -36,6 +39,7 @@ pub fn record_usage(
         volume,
         timestamp,
         data_hash,
+        metered,
     };
 
     // Store the usage record
@@ -69,6 +73,26 @@ pub fn record_usage(
         .persistent()
         .set(&parcel_usages_key, &parcel_usages);
 
+    // Add to the per-period totals and bill the usage at the parcel's tariff
+    let aggregate_before = get_usage_aggregate(env, parcel_id.clone(), timestamp);
+    let storage = env.storage().persistent();
+    storage.set(
+        &DataKey::DailyUsage(parcel_id.clone(), aggregate_before.day_start),
+        &(aggregate_before.daily_total + volume),
+    );
+    storage.set(
+        &DataKey::WeeklyUsage(parcel_id.clone(), aggregate_before.week_start),
+        &(aggregate_before.weekly_total + volume),
+    );
+    storage.set(
+        &DataKey::MonthlyUsage(parcel_id.clone(), aggregate_before.month_start),
+        &(aggregate_before.monthly_total + volume),
+    );
+
+    // Draw down the parcel's seasonal allocation, then bill any overage at the penalty price
+    let overage = allocations::record_allocation_usage(env, &parcel_id, volume, timestamp);
+    billing::charge_usage(env, &usage, &aggregate_before, overage);
+
     // Emit usage recorded event
     env.events().publish(
         (Symbol::new(env, "water_usage_recorded"), farmer_id.clone()),
@@ -78,6 +102,30 @@ pub fn record_usage(
     Ok(())
 }
 
+/// Gets a parcel's usage totals for the day, week and month containing `timestamp`.
+/// Totals are kept per period, so readings checked after a rollover still see their own period.
+pub fn get_usage_aggregate(env: &Env, parcel_id: BytesN<32>, timestamp: u64) -> UsageAggregate {
+    let day_start = utils::get_day_start(timestamp);
+    let week_start = utils::get_week_start(timestamp);
+    let month_start = utils::get_month_start(timestamp);
+    let storage = env.storage().persistent();
+
+    UsageAggregate {
+        day_start,
+        daily_total: storage
+            .get(&DataKey::DailyUsage(parcel_id.clone(), day_start))
+            .unwrap_or(0),
+        week_start,
+        weekly_total: storage
+            .get(&DataKey::WeeklyUsage(parcel_id.clone(), week_start))
+            .unwrap_or(0),
+        month_start,
+        monthly_total: storage
+            .get(&DataKey::MonthlyUsage(parcel_id, month_start))
+            .unwrap_or(0),
+    }
+}
+
 /// Retrieves water usage record by ID
 pub fn get_usage(env: &Env, usage_id: BytesN<32>) -> Result<WaterUsage, ContractError> {
     env.storage()
@@ -99,6 +147,7 @@ pub fn get_usage_report(
     }
 
     let mut total_usage = 0i128;
+    let mut water_bill = 0i128;
     let mut usage_count = 0u32;
     let mut total_efficiency = 0u32;
 
@@ -129,6 +178,7 @@ pub fn get_usage_report(
                 // If parcel_id is specified, ensure it matches
                 if parcel_id.is_none() || parcel_id.as_ref() == Some(&usage.parcel_id) {
                     total_usage += usage.volume;
+                    water_bill += billing::get_usage_charge(env, usage.usage_id.clone());
                     usage_count += 1;
 
                     // Calculate efficiency if threshold exists
@@ -165,6 +215,7 @@ pub fn get_usage_report(
         period_start,
         period_end,
         efficiency_score,
+        water_bill,
     })
 }
 